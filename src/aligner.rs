@@ -0,0 +1,139 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::Command;
+
+use serde::Deserialize;
+
+use crate::config::downloads_dir;
+use crate::error::{Error, Result};
+use crate::transcriber::{TranscriptData, Word};
+
+/// Interpreter that runs aeneas (`python3 -m aeneas.tools.execute_task`)
+const PYTHON: &str = "python3";
+
+/// aeneas sync map: one fragment per line of the text file, times in decimal seconds
+#[derive(Debug, Deserialize)]
+struct SyncMap {
+    fragments: Vec<Fragment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Fragment {
+    begin: String,
+    end: String,
+}
+
+/// Give a transcript word timings by force-aligning its text against the audio
+///
+/// Meant for captions and imports, which only have cue or paragraph timings. Words keep
+/// the speaker of their utterance; utterances are left as they are. Needs aeneas
+/// (`pip install aeneas`, which also wants espeak and ffmpeg). Returns the word count.
+pub fn align(audio: &Path, data: &mut TranscriptData, language: &str) -> Result<usize> {
+    let words: Vec<(&str, &str)> = data
+        .utterances
+        .iter()
+        .flat_map(|u| u.text.split_whitespace().map(|w| (w, u.speaker.as_str())))
+        .collect();
+    if words.is_empty() {
+        return Err(Error::Aligner("the transcript has no text to align".to_string()));
+    }
+
+    // One word per line makes every word its own fragment
+    fs::create_dir_all(downloads_dir())?;
+    let base = downloads_dir().join(format!("align-{}", &uuid::Uuid::new_v4().to_string()[..8]));
+    let text_file = base.with_extension("txt");
+    let map_file = base.with_extension("json");
+    fs::write(&text_file, words.iter().map(|(w, _)| *w).collect::<Vec<_>>().join("\n"))?;
+
+    let result = run_aeneas(audio, &text_file, &map_file, language)
+        .and_then(|()| Ok(serde_json::from_str::<SyncMap>(&fs::read_to_string(&map_file)?)?));
+    let _ = fs::remove_file(&text_file);
+    let _ = fs::remove_file(&map_file);
+    let fragments = result?.fragments;
+
+    if fragments.len() != words.len() {
+        return Err(Error::Aligner(format!(
+            "got {} timings for {} words",
+            fragments.len(),
+            words.len()
+        )));
+    }
+
+    let aligned: Vec<Word> = words
+        .into_iter()
+        .zip(fragments)
+        .map(|((text, speaker), fragment)| {
+            let start = seconds_to_ms(&fragment.begin);
+            Word {
+                text: text.to_string(),
+                start,
+                end: seconds_to_ms(&fragment.end).max(start),
+                confidence: None,
+                speaker: Some(speaker.to_string()),
+            }
+        })
+        .collect();
+    data.words = aligned;
+    Ok(data.words.len())
+}
+
+fn run_aeneas(audio: &Path, text_file: &Path, map_file: &Path, language: &str) -> Result<()> {
+    let config = format!(
+        "task_language={}|is_text_type=plain|os_task_file_format=json",
+        aeneas_language(language)
+    );
+    let output = Command::new(PYTHON)
+        .args(["-m", "aeneas.tools.execute_task"])
+        .arg(audio)
+        .arg(text_file)
+        .arg(config)
+        .arg(map_file)
+        .output()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => Error::Aligner(format!("{} not found; aeneas needs Python 3", PYTHON)),
+            _ => e.into(),
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("No module named 'aeneas") {
+            return Err(Error::Aligner(
+                "aeneas is not installed. Install it with: pip install aeneas (needs espeak and ffmpeg)".to_string(),
+            ));
+        }
+        return Err(Error::Aligner(stderr.trim().to_string()));
+    }
+    Ok(())
+}
+
+/// aeneas takes ISO 639-3 codes; "en", "es-419", "en_us" and other common codes are translated
+fn aeneas_language(code: &str) -> String {
+    let primary = code.split(['-', '_']).next().unwrap_or_default().trim().to_lowercase();
+    let iso639_3 = match primary.as_str() {
+        "ar" => "ara",
+        "de" => "deu",
+        "en" => "eng",
+        "es" => "spa",
+        "fr" => "fra",
+        "hi" => "hin",
+        "it" => "ita",
+        "ja" => "jpn",
+        "ko" => "kor",
+        "nl" => "nld",
+        "pl" => "pol",
+        "pt" => "por",
+        "ru" => "rus",
+        "sv" => "swe",
+        "tr" => "tur",
+        "uk" => "ukr",
+        "zh" => "zho",
+        other => other,
+    };
+    iso639_3.to_string()
+}
+
+/// aeneas' decimal seconds ("12.345") in milliseconds
+fn seconds_to_ms(seconds: &str) -> i64 {
+    seconds.trim().parse::<f64>().map(|s| (s * 1000.0).round() as i64).unwrap_or(0)
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::transcriber::TranscriptData;
+
+/// PII patterns and their replacement tokens, applied in order
+static PII_PATTERNS: LazyLock<Vec<(Regex, &'static str)>> = LazyLock::new(|| {
+    vec![
+        (Regex::new(r"(?i)\b[A-Z0-9._%+-]+@[A-Z0-9.-]+\.[A-Z]{2,}\b").unwrap(), "[EMAIL]"),
+        (
+            Regex::new(r#"(?i)\b(?:https?://|www\.)[^\s<>"]*[^\s<>".,;:!?)]"#).unwrap(),
+            "[URL]",
+        ),
+        (Regex::new(r"\B@[A-Za-z0-9_.-]{2,}").unwrap(), "[HANDLE]"),
+        (Regex::new(r"\b\d{3}-\d{2}-\d{4}\b").unwrap(), "[SSN]"),
+        (Regex::new(r"\b(?:\d[ -]?){13,16}\b").unwrap(), "[CARD_NUMBER]"),
+        (Regex::new(r"\b(?:\d{1,3}\.){3}\d{1,3}\b").unwrap(), "[IP_ADDRESS]"),
+        (
+            Regex::new(r"(?:\+?\d{1,2}[ .-]?)?\(?\b\d{3}\)?[ .-]?\d{3}[ .-]?\d{4}\b").unwrap(),
+            "[PHONE]",
+        ),
+    ]
+});
+
+/// Replace detectable PII (emails, URLs, handles, phone/card/SSN numbers, IPs) with tokens
+pub fn redact_pii(text: &str) -> String {
+    let mut result = text.to_string();
+    for (pattern, token) in PII_PATTERNS.iter() {
+        result = pattern.replace_all(&result, *token).into_owned();
+    }
+    result
+}
+
+/// Replaces source identities with stable pseudonyms across one export
+#[derive(Debug, Default)]
+pub struct Anonymizer {
+    channels: HashMap<String, String>,
+    documents: usize,
+}
+
+impl Anonymizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pseudonym for a channel, stable for the lifetime of this anonymizer
+    pub fn channel(&mut self, name: &str) -> String {
+        let next = self.channels.len() + 1;
+        self.channels
+            .entry(name.to_string())
+            .or_insert_with(|| format!("channel-{}", next))
+            .clone()
+    }
+
+    /// Next opaque document identifier (replaces video IDs, which identify the source)
+    pub fn document_id(&mut self) -> String {
+        self.documents += 1;
+        format!("doc-{:04}", self.documents)
+    }
+
+    /// Redact PII and known identities from free text
+    pub fn text(&self, text: &str, identities: &[&str]) -> String {
+        redact(text, &identity_patterns(identities))
+    }
+
+    /// Relabel speakers in order of appearance and redact PII and known identities from text
+    pub fn transcript(&self, data: &mut TranscriptData, identities: &[&str]) {
+        let patterns = identity_patterns(identities);
+        let mut speakers: HashMap<String, String> = HashMap::new();
+        let mut relabel = |speaker: &str| {
+            let next = speakers.len() + 1;
+            speakers
+                .entry(speaker.to_string())
+                .or_insert_with(|| next.to_string())
+                .clone()
+        };
+
+        for utterance in &mut data.utterances {
+            utterance.speaker = relabel(&utterance.speaker);
+            utterance.text = redact(&utterance.text, &patterns);
+        }
+
+        for word in &mut data.words {
+            word.speaker = word.speaker.as_deref().map(&mut relabel);
+            word.text = redact(&word.text, &patterns);
+        }
+
+        data.id.clear();
+        data.text = redact(&data.text, &patterns);
+    }
+}
+
+/// Case-insensitive patterns for identity strings (word boundaries only where the edge is a word char)
+fn identity_patterns(identities: &[&str]) -> Vec<Regex> {
+    identities
+        .iter()
+        .map(|i| i.trim())
+        .filter(|i| i.len() > 2)
+        .map(|identity| {
+            let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+            let start = if is_word(identity.chars().next()) { r"\b" } else { "" };
+            let end = if is_word(identity.chars().last()) { r"\b" } else { "" };
+            Regex::new(&format!("(?i){}{}{}", start, regex::escape(identity), end)).unwrap()
+        })
+        .collect()
+}
+
+fn redact(text: &str, identities: &[Regex]) -> String {
+    let mut result = redact_pii(text);
+    for pattern in identities {
+        result = pattern.replace_all(&result, "[REDACTED]").into_owned();
+    }
+    result
+}
@@ -0,0 +1,74 @@
+use std::collections::BTreeMap;
+
+use crate::config::assemblyai_api_key;
+use crate::database::{get_usage, UsageSummary};
+use crate::error::Result;
+use crate::format;
+use crate::transcriber::AssemblyAI;
+
+/// Number of recent jobs fetched from the AssemblyAI history
+const HISTORY_LIMIT: u32 = 200;
+
+pub async fn run() -> Result<()> {
+    println!("AssemblyAI Account");
+    println!("==================");
+    match assemblyai_api_key() {
+        Some(key) => print_remote(AssemblyAI::with_key(key)?).await,
+        None => println!("No API key configured (run `yt-cli init`)."),
+    }
+    println!("Balance and plan limits: https://www.assemblyai.com/app/account");
+
+    let (month, total) = get_usage()?;
+    println!("\nTracked Locally");
+    println!("===============");
+    print_usage("This month:", &month);
+    print_usage("All time:  ", &total);
+    println!("\nSpend by month: yt-cli usage");
+
+    Ok(())
+}
+
+/// Summarize the account's recent job history by status
+async fn print_remote(client: AssemblyAI) {
+    let jobs = match client.recent_transcripts(HISTORY_LIMIT).await {
+        Ok(jobs) => jobs,
+        Err(e) => {
+            println!("Could not fetch account history: {}", e);
+            return;
+        }
+    };
+
+    let mut by_status: BTreeMap<&str, usize> = BTreeMap::new();
+    for job in &jobs {
+        *by_status.entry(job.status.as_str()).or_default() += 1;
+    }
+    let statuses = by_status
+        .iter()
+        .map(|(status, count)| format!("{} {}", count, status))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    println!("Recent jobs:   {} ({})", jobs.len(), if statuses.is_empty() { "none" } else { &statuses });
+    if let Some(created) = jobs.first().and_then(|j| j.created.as_deref()) {
+        println!("Latest job:    {}", created);
+    }
+}
+
+fn print_usage(label: &str, usage: &[UsageSummary]) {
+    if usage.is_empty() {
+        println!("{} nothing transcribed", label);
+        return;
+    }
+
+    for (i, u) in usage.iter().enumerate() {
+        let cost = u.cost.map(|c| format!(", ~${:.2}", c)).unwrap_or_default();
+        println!(
+            "{} {:<11} {} ({} transcript(s){})",
+            if i == 0 { label } else { "           " },
+            u.backend,
+            format::duration(u.seconds),
+            u.transcripts,
+            cost
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::aligner;
+use crate::commands::history;
+use crate::commands::read::resolve_path;
+use crate::commands::reindex::index_video_dir;
+use crate::config::{ensure_writable, settings};
+use crate::error::{Error, Result};
+use crate::storage::{get_transcript, load_metadata, save_transcript, CAPTIONS_DIR_SUFFIX};
+use crate::transcriber::format_transcript_markdown;
+
+/// Add word timings to a captions or imported transcript by aligning it against audio
+///
+/// The audio is `audio` if given, else the transcript's `audio.mp3`, else the one kept with
+/// the same video's API transcript. Transcripts that already have word timings are left
+/// alone unless `force`.
+pub fn run(path_or_id: &str, audio: Option<&str>, force: bool) -> Result<()> {
+    ensure_writable("align transcripts")?;
+
+    let path = PathBuf::from(resolve_path(path_or_id)?);
+    let mut data = get_transcript(&path.to_string_lossy())?
+        .structured
+        .ok_or_else(|| Error::FileNotFound(format!("No transcript.json at {}", path.display())))?;
+    if !data.words.is_empty() && !force {
+        return Err(Error::Config(format!(
+            "{} already has word timings (use --force to align it again)",
+            path.display()
+        )));
+    }
+
+    let audio = match audio {
+        Some(audio) => PathBuf::from(audio),
+        None => stored_audio(&path).ok_or_else(|| {
+            Error::FileNotFound(format!("No audio.mp3 stored for {} (pass one with --audio)", path.display()))
+        })?,
+    };
+    if !audio.is_file() {
+        return Err(Error::FileNotFound(audio.display().to_string()));
+    }
+
+    let language = data
+        .language
+        .clone()
+        .or_else(|| load_metadata(&path).get("language").and_then(|v| v.as_str()).map(String::from))
+        .or_else(|| settings().language.clone())
+        .unwrap_or_else(|| "en".to_string());
+
+    eprintln!("Aligning against {}...", audio.display());
+    let count = aligner::align(&audio, &mut data, &language)?;
+
+    // Utterances are unchanged, so the markdown stays as it is
+    let markdown = fs::read_to_string(path.join("transcript.md")).unwrap_or_else(|_| format_transcript_markdown(&data));
+    save_transcript(&path, &markdown, &data)?;
+    index_video_dir(&path)?;
+
+    println!("Aligned {} words; search results now point at the moment each sentence is said.", count);
+    history::record("align", Some(path_or_id), None, true);
+    Ok(())
+}
+
+/// The transcript's own audio, or for captions, the audio of the video's API transcript
+fn stored_audio(path: &Path) -> Option<PathBuf> {
+    let own = path.join("audio.mp3");
+    if own.is_file() {
+        return Some(own);
+    }
+    let name = path.file_name()?.to_string_lossy();
+    let video_dir = path.with_file_name(name.strip_suffix(CAPTIONS_DIR_SUFFIX)?);
+    Some(video_dir.join("audio.mp3")).filter(|audio| audio.is_file())
+}
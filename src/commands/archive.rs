@@ -0,0 +1,165 @@
+use std::fs;
+use std::path::{self, Path};
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::backup::run_tar;
+use crate::commands::history;
+use crate::commands::reindex::index_local_only;
+use crate::config::{data_dir, database_path, ensure_writable, transcripts_dir};
+use crate::database::{backup_database, keep_replaced_database, replace_database};
+use crate::error::{Error, Result};
+use crate::format;
+
+/// Describes an archive, stored in it as `archive.json`
+const MANIFEST_FILE: &str = "archive.json";
+/// Name of the database copy inside an archive
+const DATABASE_FILE: &str = "transcripts.db";
+/// Name of the transcripts directory inside an archive
+const TRANSCRIPTS_DIR: &str = "transcripts";
+/// Layout version written into new archives
+const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    /// Data directory the archive was made from, which stored paths start with
+    data_dir: String,
+}
+
+/// Pack the whole library, a consistent copy of the database and the transcripts
+/// directory, into `file` for `archive import` on another machine
+///
+/// Compression follows the file name (`.tar.zst`, `.tar.gz`, `.tar.xz`, ...) and needs
+/// `tar` in PATH, plus `zstd` for `.tar.zst` with GNU tar.
+pub fn export(file: &str) -> Result<()> {
+    let file = path::absolute(file)?;
+    if file.exists() {
+        return Err(Error::Config(format!("{} already exists; remove it or pick another path", file.display())));
+    }
+
+    let staging = std::env::temp_dir().join(format!("yt-cli-archive-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&staging)?;
+    let result = pack(&file, &staging);
+    let _ = fs::remove_dir_all(&staging);
+    if result.is_err() {
+        let _ = fs::remove_file(&file);
+    }
+    result?;
+
+    println!("Archived the library to {} ({}).", file.display(), format::size(fs::metadata(&file)?.len()));
+    println!("On the other machine, run: yt-cli archive import {}", file.display());
+    history::record("archive-export", Some(&file.to_string_lossy()), None, true);
+    Ok(())
+}
+
+fn pack(file: &Path, staging: &Path) -> Result<()> {
+    eprintln!("Backing up the database...");
+    backup_database(&staging.join(DATABASE_FILE))?;
+    let manifest = Manifest { version: ARCHIVE_VERSION, data_dir: data_dir().to_string_lossy().to_string() };
+    fs::write(staging.join(MANIFEST_FILE), serde_json::to_string_pretty(&manifest)?)?;
+
+    let mut cmd = Command::new("tar");
+    cmd.arg("-caf").arg(file).arg("-C").arg(staging).args([MANIFEST_FILE, DATABASE_FILE]);
+    if transcripts_dir().is_dir() {
+        eprintln!("Archiving {}...", transcripts_dir().display());
+        cmd.arg("-C").arg(data_dir()).arg(TRANSCRIPTS_DIR);
+    }
+    run_tar(&mut cmd, "archive export")
+}
+
+/// Unpack an `archive export` file into this machine's data directory and point the
+/// database's stored paths at it
+///
+/// A data directory that already holds a library is only touched with `force`: its
+/// database is replaced and the archived transcript files are merged over its own.
+pub fn import(file: &str, force: bool) -> Result<()> {
+    ensure_writable("import an archive")?;
+    let file = Path::new(file)
+        .canonicalize()
+        .map_err(|_| Error::FileNotFound(format!("Archive not found: {}", file)))?;
+
+    let has_library = database_path().exists()
+        || fs::read_dir(transcripts_dir()).is_ok_and(|mut entries| entries.next().is_some());
+    if has_library && !force {
+        return Err(Error::Config(format!(
+            "{} already holds a library; pass --force to replace its database and merge in the archived files",
+            data_dir().display()
+        )));
+    }
+
+    if let Some(copy) = keep_replaced_database("import")? {
+        println!("Saved the current database to {}.", copy.display());
+    }
+
+    // Unpacked next to its destination, so everything is moved into place by renaming
+    fs::create_dir_all(data_dir())?;
+    let staging = data_dir().join(format!(".archive-import-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&staging)?;
+    let result = unpack(&file, &staging);
+    let _ = fs::remove_dir_all(&staging);
+    let remapped = result?;
+
+    println!("Imported {} into {}.", file.display(), data_dir().display());
+    if remapped > 0 {
+        println!("Pointed {} stored path(s) at the new data directory.", remapped);
+    }
+    if has_library {
+        index_local_only()?;
+    }
+    history::record_irreversible("archive-import", Some(&file.to_string_lossy()), None);
+    Ok(())
+}
+
+/// Move an archive's contents into place; returns how many stored paths were remapped
+fn unpack(file: &Path, staging: &Path) -> Result<usize> {
+    eprintln!("Unpacking {}...", file.display());
+    run_tar(Command::new("tar").arg("-xf").arg(file).arg("-C").arg(staging), "archive import")?;
+
+    let manifest: Manifest = fs::read_to_string(staging.join(MANIFEST_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .ok_or_else(|| Error::Config(format!("{} is not a yt-cli archive (no {})", file.display(), MANIFEST_FILE)))?;
+    if manifest.version > ARCHIVE_VERSION {
+        return Err(Error::Config(format!(
+            "{} was made by a newer yt-cli (archive version {}); update with `yt-cli self-update`",
+            file.display(),
+            manifest.version
+        )));
+    }
+    let database = staging.join(DATABASE_FILE);
+    if !database.is_file() {
+        return Err(Error::Config(format!("{} holds no {}", file.display(), DATABASE_FILE)));
+    }
+
+    let transcripts = staging.join(TRANSCRIPTS_DIR);
+    if transcripts.is_dir() {
+        move_tree(&transcripts, &transcripts_dir())?;
+    }
+    replace_database(&database, &manifest.data_dir)
+}
+
+/// Move everything under `source` to the same place under `dest`, replacing files that
+/// exist in both
+fn move_tree(source: &Path, dest: &Path) -> Result<()> {
+    if !dest.exists() {
+        fs::rename(source, dest)?;
+        return Ok(());
+    }
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let target = dest.join(entry.file_name());
+        if entry.file_type()?.is_dir() && target.is_dir() {
+            move_tree(&entry.path(), &target)?;
+        } else {
+            if target.is_dir() {
+                fs::remove_dir_all(&target)?;
+            } else if target.exists() {
+                fs::remove_file(&target)?;
+            }
+            fs::rename(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
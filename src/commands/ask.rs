@@ -0,0 +1,245 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::config::{assemblyai_api_key, settings};
+use crate::database::{search_transcripts_any, SearchResult, TranscriptFilter};
+use crate::error::{Error, Result};
+use crate::platform;
+use crate::storage::get_transcript;
+use crate::transcriber::{format_timestamp, speaker_label, TranscriptData};
+
+/// Transcripts searched for excerpts
+const SEARCH_LIMIT: i32 = 10;
+/// An excerpt ends once it spans this long (ms) or this many words
+const CHUNK_MS: i64 = 90_000;
+const CHUNK_WORDS: usize = 250;
+
+/// Words too common to help find excerpts
+const STOPWORDS: &[&str] = &[
+    "about", "after", "all", "and", "any", "are", "been", "but", "can", "could", "did", "does",
+    "for", "from", "had", "has", "have", "her", "him", "his", "how", "into", "its", "not", "our",
+    "said", "say", "says", "she", "should", "talk", "talked", "tell", "that", "the", "their",
+    "them", "there", "they", "think", "this", "was", "were", "what", "when", "where", "which",
+    "who", "why", "will", "with", "would", "you", "your",
+];
+
+const SYSTEM_PROMPT: &str = "You answer questions about a library of video and podcast transcripts. \
+Use only the numbered excerpts provided. Cite the excerpts you rely on inline as [1], [2], etc. \
+If the excerpts don't answer the question, say so instead of guessing.";
+
+/// A stretch of one transcript, times in milliseconds
+struct Excerpt<'a> {
+    source: &'a SearchResult,
+    start: i64,
+    /// Speaker of the latest text, to label each turn
+    speaker: Option<String>,
+    text: String,
+    score: usize,
+}
+
+/// Answer a question from the library: find matching excerpts with full-text search, then
+/// have a language model answer from them with citations
+pub async fn run(question: &str, chunks: usize, sources_only: bool) -> Result<()> {
+    let terms = keywords(question);
+    if terms.is_empty() {
+        return Err(Error::Config("Ask a question with some searchable words in it".to_string()));
+    }
+
+    let results = search_transcripts_any(&terms, SEARCH_LIMIT, &TranscriptFilter::default())?;
+    let mut excerpts = Vec::new();
+    for result in &results {
+        match get_transcript(&result.path) {
+            Ok(content) => {
+                if let Some(data) = content.structured {
+                    excerpts.extend(split_excerpts(result, &data, &terms));
+                }
+            }
+            Err(e) => eprintln!("Skipping {}: {}", result.path, e),
+        }
+    }
+
+    excerpts.retain(|e| e.score > 0);
+    excerpts.sort_by_key(|e| Reverse(e.score));
+    excerpts.truncate(chunks.max(1));
+    if excerpts.is_empty() {
+        println!("Nothing in the library matches: {}", question);
+        return Ok(());
+    }
+
+    if !sources_only {
+        eprintln!("Asking {} with {} excerpt(s)...\n", settings().ask.model, excerpts.len());
+        let answer = complete(&prompt(question, &excerpts)).await?;
+        println!("{}\n", answer.trim());
+    }
+
+    println!("Sources:");
+    for (i, excerpt) in excerpts.iter().enumerate() {
+        println!(
+            "  [{}] {} ({}) at {}",
+            i + 1,
+            excerpt.source.title,
+            excerpt.source.channel,
+            format_timestamp(excerpt.start)
+        );
+        if let Some(url) = &excerpt.source.url {
+            println!("      {}", platform::timestamp_url(url, excerpt.start / 1000));
+        }
+        if sources_only {
+            println!("      {}", excerpt.text);
+        }
+    }
+
+    Ok(())
+}
+
+/// Lowercased search terms from a question, without duplicates or stopwords
+fn keywords(question: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    question
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() >= 3 && !STOPWORDS.contains(&word.as_str()))
+        .filter(|word| seen.insert(word.clone()))
+        .collect()
+}
+
+/// Cut a transcript into excerpts of consecutive utterances (or words), scored against `terms`
+fn split_excerpts<'a>(source: &'a SearchResult, data: &TranscriptData, terms: &[String]) -> Vec<Excerpt<'a>> {
+    let pieces: Vec<(i64, Option<&str>, &str)> = if !data.utterances.is_empty() {
+        data.utterances
+            .iter()
+            .map(|u| (u.start, Some(u.speaker.as_str()), u.text.as_str()))
+            .collect()
+    } else if !data.words.is_empty() {
+        data.words
+            .iter()
+            .map(|w| (w.start, w.speaker.as_deref(), w.text.as_str()))
+            .collect()
+    } else {
+        vec![(0, None, data.text.as_str())]
+    };
+
+    let mut excerpts: Vec<Excerpt> = Vec::new();
+    let mut current: Option<Excerpt> = None;
+    for (start, speaker, text) in pieces {
+        if let Some(excerpt) = &current
+            && (start - excerpt.start >= CHUNK_MS || excerpt.text.split_whitespace().count() >= CHUNK_WORDS)
+        {
+            excerpts.extend(current.take());
+        }
+
+        let excerpt = current.get_or_insert_with(|| Excerpt {
+            source,
+            start,
+            speaker: None,
+            text: String::new(),
+            score: 0,
+        });
+        if !excerpt.text.is_empty() {
+            excerpt.text.push(' ');
+        }
+        if let Some(speaker) = speaker
+            && excerpt.speaker.as_deref() != Some(speaker)
+        {
+            excerpt.text.push_str(&format!("{}: ", speaker_label(speaker)));
+            excerpt.speaker = Some(speaker.to_string());
+        }
+        excerpt.text.push_str(text.trim());
+    }
+    excerpts.extend(current);
+
+    for excerpt in &mut excerpts {
+        excerpt.score = score(&excerpt.text, terms);
+    }
+    excerpts
+}
+
+/// Distinct terms matched count most, then total occurrences
+fn score(text: &str, terms: &[String]) -> usize {
+    let text = text.to_lowercase();
+    terms
+        .iter()
+        .map(|term| text.matches(term.as_str()).count())
+        .filter(|&count| count > 0)
+        .map(|count| 10 + count)
+        .sum()
+}
+
+fn prompt(question: &str, excerpts: &[Excerpt]) -> String {
+    let mut prompt = String::from("Excerpts:\n\n");
+    for (i, excerpt) in excerpts.iter().enumerate() {
+        prompt.push_str(&format!(
+            "[{}] \"{}\" ({}) at {}:\n{}\n\n",
+            i + 1,
+            excerpt.source.title,
+            excerpt.source.channel,
+            format_timestamp(excerpt.start),
+            excerpt.text
+        ));
+    }
+    prompt.push_str(&format!("Question: {}", question));
+    prompt
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    content: String,
+}
+
+/// Send the prompt to the `[ask]` chat completions endpoint
+async fn complete(prompt: &str) -> Result<String> {
+    let config = &settings().ask;
+    // AssemblyAI takes its key as is; other services expect a Bearer token
+    let authorization = match &config.api_key_env {
+        Some(var) => {
+            let key = std::env::var(var)
+                .map_err(|_| Error::Config(format!("{} is not set ([ask] api_key_env)", var)))?;
+            format!("Bearer {}", key)
+        }
+        None => assemblyai_api_key().ok_or(Error::ApiKeyMissing)?,
+    };
+
+    let client = Client::builder().timeout(Duration::from_secs(120)).build()?;
+    let response = client
+        .post(&config.endpoint)
+        .header("Authorization", authorization)
+        .json(&json!({
+            "model": config.model,
+            "messages": [
+                { "role": "system", "content": SYSTEM_PROMPT },
+                { "role": "user", "content": prompt },
+            ],
+            "max_tokens": 1000,
+        }))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(Error::Llm(format!("{}: {}", status, text)));
+    }
+
+    let response: ChatResponse = response.json().await?;
+    response
+        .choices
+        .into_iter()
+        .next()
+        .map(|choice| choice.message.content)
+        .ok_or_else(|| Error::Llm("empty response".to_string()))
+}
@@ -0,0 +1,121 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{self, Path};
+use std::process::Command;
+
+use regex::Regex;
+
+use crate::commands::history;
+use crate::commands::read::resolve_path;
+use crate::config::ensure_writable;
+use crate::database::{index_attachment, remove_attachment_index};
+use crate::error::{Error, Result};
+use crate::storage::{sanitize_filename, save_attachment, save_attachment_text, ATTACHMENTS_DIR};
+
+/// Extensions read as plain text by `--extract`
+const TEXT_EXTENSIONS: &[&str] = &["txt", "md", "markdown", "srt", "vtt", "csv", "json", "rst", "org"];
+
+/// Copy a file (slides, show notes, ...) into a transcript's directory
+///
+/// With `extract`, its text is stored beside it and indexed so `search` finds it: plain
+/// text and HTML are read directly, PDFs go through `pdftotext` (poppler).
+pub fn run(path_or_id: &str, file: &str, name: Option<&str>, extract: bool, force: bool) -> Result<()> {
+    ensure_writable("attach files")?;
+
+    let source = Path::new(file);
+    if !source.is_file() {
+        return Err(Error::FileNotFound(file.to_string()));
+    }
+    let name = name
+        .map(String::from)
+        .or_else(|| source.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_default();
+    let name = sanitize_filename(name.trim_start_matches('.'), 120);
+
+    let storage_path = path::absolute(resolve_path(path_or_id)?)?;
+    if storage_path.join(ATTACHMENTS_DIR).join(&name).exists() && !force {
+        return Err(Error::Config(format!(
+            "{} already has an attachment named {} (use --force to replace it)",
+            storage_path.display(),
+            name
+        )));
+    }
+
+    // Extract first, so a file that can't be read isn't left half-attached
+    let text = if extract { Some(extract_text(source)?) } else { None };
+
+    let dest = save_attachment(&storage_path, &name, source)?;
+    println!("Attached {}", dest.display());
+
+    // A replaced attachment's old text no longer applies
+    let text_file = dest.with_file_name(format!(".{}.txt", name));
+    if text.as_deref().is_none_or(|t| t.trim().is_empty()) && text_file.exists() {
+        fs::remove_file(&text_file)?;
+        remove_attachment_index(&storage_path.to_string_lossy(), &name)?;
+    }
+
+    if let Some(text) = text {
+        if text.trim().is_empty() {
+            eprintln!("No text found in {} (scanned PDF?), nothing indexed.", name);
+        } else {
+            save_attachment_text(&storage_path, &name, &text)?;
+            index_attachment(&storage_path.to_string_lossy(), &name, &text)?;
+            println!("Indexed {} words for search.", text.split_whitespace().count());
+        }
+    }
+
+    history::record("attach", Some(path_or_id), Some(&name), true);
+    Ok(())
+}
+
+/// Text content of a plain text, HTML or PDF file
+fn extract_text(path: &Path) -> Result<String> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+    match extension.as_str() {
+        "pdf" => pdf_text(path),
+        "html" | "htm" => Ok(html_text(&String::from_utf8_lossy(&fs::read(path)?))),
+        e if TEXT_EXTENSIONS.contains(&e) => Ok(String::from_utf8_lossy(&fs::read(path)?).to_string()),
+        _ => Err(Error::Config(format!(
+            "Can't extract text from {} (supported: pdf, html, {}); attach it without --extract",
+            path.display(),
+            TEXT_EXTENSIONS.join(", ")
+        ))),
+    }
+}
+
+fn pdf_text(path: &Path) -> Result<String> {
+    let output = Command::new("pdftotext")
+        .args(["-enc", "UTF-8"])
+        .arg(path)
+        .arg("-")
+        .output()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => Error::Config(
+                "pdftotext not found. Install poppler (brew install poppler / apt install poppler-utils)".to_string(),
+            ),
+            _ => e.into(),
+        })?;
+    if !output.status.success() {
+        return Err(Error::Config(format!(
+            "pdftotext failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Visible text of an HTML page: scripts, styles and tags removed, common entities decoded
+fn html_text(html: &str) -> String {
+    let hidden = Regex::new(r"(?is)<(script|style)\b.*?</(script|style)>").unwrap();
+    let tag = Regex::new(r"(?s)<[^>]*>").unwrap();
+    let text = hidden.replace_all(html, " ");
+    let text = tag.replace_all(&text, " ");
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,319 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::commands::history;
+use crate::config::{database_path, settings, transcripts_dir, StorageConfig};
+use crate::database::backup_database;
+use crate::error::{Error, Result};
+use crate::format;
+use crate::storage::sha256;
+
+/// Paths listed per kind of drift before the rest are summarised
+const MAX_LISTED: usize = 20;
+
+/// A copy of the transcripts directory: a local folder or an S3 prefix
+enum Backup {
+    Local(PathBuf),
+    S3 {
+        bucket: String,
+        prefix: String,
+        endpoint: Option<String>,
+        profile: Option<String>,
+    },
+}
+
+impl Backup {
+    /// `s3://bucket/prefix`, a directory, or the `[storage]` S3 bucket when omitted
+    fn parse(location: Option<&str>, endpoint: Option<String>, profile: Option<String>) -> Result<Self> {
+        let Some(location) = location else {
+            return match settings().storage.clone() {
+                StorageConfig::S3 { bucket, prefix, endpoint: configured_endpoint, profile: configured_profile } => {
+                    Ok(Backup::S3 {
+                        bucket,
+                        prefix: prefix.unwrap_or_default().trim_matches('/').to_string(),
+                        endpoint: endpoint.or(configured_endpoint),
+                        profile: profile.or(configured_profile),
+                    })
+                }
+                _ => Err(Error::Config(
+                    "No backup location given and [storage] isn't S3 (pass a directory or s3://bucket/prefix)"
+                        .to_string(),
+                )),
+            };
+        };
+
+        if let Some(rest) = location.strip_prefix("s3://") {
+            let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+            if bucket.is_empty() {
+                return Err(Error::Config(format!("Invalid S3 location '{}'", location)));
+            }
+            return Ok(Backup::S3 {
+                bucket: bucket.to_string(),
+                prefix: prefix.trim_matches('/').to_string(),
+                endpoint,
+                profile,
+            });
+        }
+
+        let path = PathBuf::from(location);
+        if !path.is_dir() {
+            return Err(Error::FileNotFound(format!("Backup directory not found: {}", location)));
+        }
+        Ok(Backup::Local(path))
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Backup::Local(path) => path.display().to_string(),
+            Backup::S3 { .. } => self.uri(""),
+        }
+    }
+
+    fn uri(&self, key: &str) -> String {
+        match self {
+            Backup::S3 { bucket, prefix, .. } if !prefix.is_empty() => format!("s3://{}/{}/{}", bucket, prefix, key),
+            Backup::S3 { bucket, .. } => format!("s3://{}/{}", bucket, key),
+            Backup::Local(path) => path.join(key).display().to_string(),
+        }
+    }
+
+    fn aws(&self) -> Command {
+        let mut cmd = Command::new("aws");
+        if let Backup::S3 { endpoint, profile, .. } = self {
+            if let Some(endpoint) = endpoint {
+                cmd.args(["--endpoint-url", endpoint]);
+            }
+            if let Some(profile) = profile {
+                cmd.args(["--profile", profile]);
+            }
+        }
+        cmd
+    }
+
+    /// Every file in the backup with its size, keyed like the live library
+    fn files(&self) -> Result<BTreeMap<String, u64>> {
+        match self {
+            Backup::Local(path) => Ok(local_files(path)?.into_iter().map(|(key, (size, _))| (key, size)).collect()),
+            Backup::S3 { .. } => {
+                let mut cmd = self.aws();
+                cmd.args(["s3", "ls", "--recursive", &self.uri("")]);
+                let output = cmd.output().map_err(aws_error)?;
+                // `aws s3 ls` exits 1 when nothing matches the prefix
+                if !output.status.success() && !output.stderr.is_empty() {
+                    return Err(Error::Storage(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+                }
+
+                let root = match self {
+                    Backup::S3 { prefix, .. } if !prefix.is_empty() => format!("{}/", prefix),
+                    _ => String::new(),
+                };
+                let mut files = BTreeMap::new();
+                // "2024-05-01 12:00:00       1234 prefix/youtube/Channel/ID/transcript.md"
+                for line in String::from_utf8_lossy(&output.stdout).lines() {
+                    let mut rest = line;
+                    let mut fields = Vec::new();
+                    for _ in 0..3 {
+                        let Some((field, tail)) = rest.trim_start().split_once(' ') else { break };
+                        fields.push(field);
+                        rest = tail;
+                    }
+                    let key = rest.trim_start();
+                    let (Some(Ok(size)), Some(key)) = (fields.get(2).map(|s| s.parse::<u64>()), key.strip_prefix(root.as_str()))
+                    else {
+                        continue;
+                    };
+                    files.insert(key.to_string(), size);
+                }
+                Ok(files)
+            }
+        }
+    }
+
+    /// SHA-256 of one file in the backup (S3 objects are streamed through `aws s3 cp`)
+    fn checksum(&self, key: &str) -> Result<String> {
+        match self {
+            Backup::Local(path) => sha256(File::open(path.join(key))?),
+            Backup::S3 { .. } => {
+                let mut cmd = self.aws();
+                cmd.args(["s3", "cp", "--only-show-errors", &self.uri(key), "-"]);
+                let mut child = cmd
+                    .stdout(Stdio::piped())
+                    .stderr(Stdio::piped())
+                    .spawn()
+                    .map_err(aws_error)?;
+                let digest = sha256(child.stdout.take().expect("piped stdout"))?;
+                let output = child.wait_with_output()?;
+                if !output.status.success() {
+                    return Err(Error::Storage(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+                }
+                Ok(digest)
+            }
+        }
+    }
+}
+
+/// Write a consistent copy of the database to `path`, or with `with_transcripts` a gzipped
+/// tarball of that copy and the transcripts directory (without stored audio unless
+/// `include_audio`)
+///
+/// Copying `transcripts.db` by hand while yt-cli writes to it can leave a corrupt copy;
+/// this goes through SQLite's online backup API instead. The tarball needs `tar` in PATH.
+pub fn create(path: &str, with_transcripts: bool, include_audio: bool) -> Result<()> {
+    let path = Path::new(path);
+    if path.exists() {
+        return Err(Error::Config(format!("{} already exists; remove it or pick another path", path.display())));
+    }
+
+    eprintln!("Backing up the database...");
+    if with_transcripts {
+        let staging = std::env::temp_dir().join(format!("yt-cli-backup-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&staging)?;
+        let result = write_tarball(path, &staging, include_audio);
+        let _ = fs::remove_dir_all(&staging);
+        if result.is_err() {
+            let _ = fs::remove_file(path);
+        }
+        result?;
+    } else if let Err(e) = backup_database(path) {
+        let _ = fs::remove_file(path);
+        return Err(e);
+    }
+
+    let size = format::size(fs::metadata(path)?.len());
+    println!("Backed up to {} ({}).", path.display(), size);
+    let detail = if with_transcripts { "database and transcripts" } else { "database" };
+    history::record("backup", Some(&path.to_string_lossy()), Some(detail), true);
+    Ok(())
+}
+
+/// Back up the database into `staging`, then archive it with the transcripts directory
+fn write_tarball(path: &Path, staging: &Path, include_audio: bool) -> Result<()> {
+    let db_name = database_path().file_name().map(|n| n.to_os_string()).unwrap_or_else(|| "transcripts.db".into());
+    backup_database(&staging.join(&db_name))?;
+
+    let mut cmd = Command::new("tar");
+    cmd.arg("-czf").arg(path);
+    if !include_audio {
+        cmd.arg("--exclude=audio.mp3");
+    }
+    cmd.arg("-C").arg(staging).arg(&db_name);
+
+    let transcripts = transcripts_dir();
+    if let (Some(parent), Some(name)) = (transcripts.parent(), transcripts.file_name())
+        && transcripts.is_dir()
+    {
+        eprintln!("Archiving {}...", transcripts.display());
+        cmd.arg("-C").arg(parent).arg(name);
+    }
+
+    run_tar(&mut cmd, "--with-transcripts")
+}
+
+/// Run a `tar` command, failing with its error output; `required_for` names the feature
+/// needing it when `tar` is missing
+pub fn run_tar(cmd: &mut Command, required_for: &str) -> Result<()> {
+    let output = cmd.stdin(Stdio::null()).output().map_err(|e| {
+        if e.kind() == io::ErrorKind::NotFound {
+            Error::Storage(format!("`tar` not found in PATH (required for {})", required_for))
+        } else {
+            Error::Io(e)
+        }
+    })?;
+    if !output.status.success() {
+        return Err(Error::Storage(format!("tar failed: {}", String::from_utf8_lossy(&output.stderr).trim())));
+    }
+    Ok(())
+}
+
+/// Compare a backup of the transcripts directory with the live library
+///
+/// Files are matched by path: missing and extra files are reported, as are files whose
+/// size or SHA-256 differ. `quick` compares sizes only, which avoids downloading every
+/// object of an S3 backup. Any drift makes the command fail so it can run from cron.
+pub fn verify(location: Option<&str>, endpoint: Option<String>, profile: Option<String>, quick: bool) -> Result<()> {
+    let backup = Backup::parse(location, endpoint, profile)?;
+    let live_root = transcripts_dir();
+    let live = if live_root.exists() { local_files(&live_root)? } else { BTreeMap::new() };
+
+    eprintln!("Verifying {} against {}...", backup.describe(), live_root.display());
+    let mut backed_up = backup.files()?;
+
+    let mut missing = Vec::new();
+    let mut changed = Vec::new();
+    let mut checked = 0;
+    for (key, (size, path)) in &live {
+        let Some(backup_size) = backed_up.remove(key) else {
+            missing.push(key.as_str());
+            continue;
+        };
+        checked += 1;
+        if backup_size != *size || (!quick && sha256(File::open(path)?)? != backup.checksum(key)?) {
+            changed.push(key.as_str());
+        }
+    }
+    let extra: Vec<&str> = backed_up.keys().map(String::as_str).collect();
+
+    println!("Files in library: {}", live.len());
+    println!("Compared:         {} ({})", checked, if quick { "size" } else { "SHA-256" });
+    print_drift("Missing from backup", &missing);
+    print_drift("Changed since backup", &changed);
+    print_drift("Only in backup", &extra);
+
+    if missing.is_empty() && changed.is_empty() && extra.is_empty() {
+        println!("\nBackup matches the library.");
+        return Ok(());
+    }
+    Err(Error::Storage(format!(
+        "Backup has drifted: {} missing, {} changed, {} only in backup",
+        missing.len(),
+        changed.len(),
+        extra.len()
+    )))
+}
+
+fn print_drift(label: &str, keys: &[&str]) {
+    if keys.is_empty() {
+        return;
+    }
+    println!("\n{} ({}):", label, keys.len());
+    for key in keys.iter().take(MAX_LISTED) {
+        println!("  {}", key);
+    }
+    if keys.len() > MAX_LISTED {
+        println!("  ... and {} more", keys.len() - MAX_LISTED);
+    }
+}
+
+/// Files under `root` by `/`-separated relative path, with size and full path
+fn local_files(root: &Path) -> Result<BTreeMap<String, (u64, PathBuf)>> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(root) else { continue };
+            let key = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(key, (fs::metadata(&path)?.len(), path));
+        }
+    }
+    Ok(files)
+}
+
+fn aws_error(e: io::Error) -> Error {
+    if e.kind() == io::ErrorKind::NotFound {
+        Error::Storage("`aws` not found in PATH (required to verify S3 backups)".to_string())
+    } else {
+        Error::Io(e)
+    }
+}
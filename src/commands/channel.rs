@@ -1,10 +1,18 @@
-use crate::downloader::{fetch_channel_videos, PlaylistEntry};
-use crate::error::Result;
+use std::io::{self, IsTerminal, Write};
 
-pub fn run(channel: &str, limit: usize) -> Result<()> {
+use crate::downloader::{fetch_channel_videos, search_channels, ChannelMatch, PlaylistEntry};
+use crate::error::{Error, Result};
+use crate::format;
+use crate::platform::YouTube;
+
+/// Channels offered when a bare name is ambiguous
+const MAX_CHOICES: usize = 8;
+
+pub fn run(channel: &str, limit: usize, first: bool) -> Result<()> {
+    let channel = resolve(channel, first)?;
     eprintln!("Fetching videos from channel...");
 
-    let videos = fetch_channel_videos(channel, limit)?;
+    let videos = fetch_channel_videos(&channel, limit)?;
 
     if videos.is_empty() {
         println!("No videos found for channel: {}", channel);
@@ -25,24 +33,17 @@ pub fn run(channel: &str, limit: usize) -> Result<()> {
 
 fn print_video_entry(index: usize, video: &PlaylistEntry) {
     // Title line with duration
-    let duration_str = video
-        .duration
-        .map(|d| {
-            let mins = d / 60;
-            let secs = d % 60;
-            format!(" ({}:{:02})", mins, secs)
-        })
-        .unwrap_or_default();
+    let duration_str = video.duration.map(|d| format!(" ({})", format::clock(d))).unwrap_or_default();
 
     println!("{}. {}{}", index, video.title, duration_str);
 
     // View count and upload date
     let mut meta_parts = Vec::new();
     if let Some(views) = video.view_count {
-        meta_parts.push(format_view_count(views));
+        meta_parts.push(format!("{} views", format::compact_number(views)));
     }
     if let Some(date) = &video.upload_date {
-        meta_parts.push(format_upload_date(date));
+        meta_parts.push(format::upload_date(date));
     }
     if !meta_parts.is_empty() {
         println!("   {}", meta_parts.join(" | "));
@@ -53,21 +54,63 @@ fn print_video_entry(index: usize, video: &PlaylistEntry) {
     println!();
 }
 
-fn format_view_count(views: i64) -> String {
-    if views >= 1_000_000 {
-        format!("{:.1}M views", views as f64 / 1_000_000.0)
-    } else if views >= 1_000 {
-        format!("{:.1}K views", views as f64 / 1_000.0)
-    } else {
-        format!("{} views", views)
+/// Turn a channel argument into something yt-dlp can list
+///
+/// URLs, @handles and channel IDs pass through. A bare name is searched for: a single match
+/// is used, several are offered in a picker on a terminal, or the top one is taken with
+/// `first`. Without a terminal or `first`, several matches are an error listing them.
+pub fn resolve(channel: &str, first: bool) -> Result<String> {
+    if !YouTube::is_channel_name(channel) {
+        return Ok(channel.to_string());
+    }
+
+    eprintln!("Looking up channels named \"{}\"...", channel);
+    let matches = search_channels(channel, MAX_CHOICES)?;
+    let chosen = match matches.len() {
+        0 => return Err(Error::Download(format!("No channel found matching \"{}\"", channel))),
+        1 => &matches[0],
+        _ if first => &matches[0],
+        _ if io::stdin().is_terminal() && io::stderr().is_terminal() => pick(channel, &matches)?,
+        _ => {
+            let choices: Vec<String> = matches.iter().map(describe).collect();
+            return Err(Error::Download(format!(
+                "\"{}\" matches {} channels; pass a URL or @handle, or --first for the top one:\n  {}",
+                channel,
+                matches.len(),
+                choices.join("\n  ")
+            )));
+        }
+    };
+
+    eprintln!("Using {}", describe(chosen));
+    Ok(chosen.url.clone())
+}
+
+/// Ask which of several matching channels was meant
+fn pick<'a>(name: &str, matches: &'a [ChannelMatch]) -> Result<&'a ChannelMatch> {
+    eprintln!("Several channels match \"{}\":", name);
+    for (i, channel) in matches.iter().enumerate() {
+        eprintln!("  {}. {}", i + 1, describe(channel));
     }
+    eprint!("Pick one [1-{}, Enter to cancel]: ", matches.len());
+    io::stderr().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    input
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|i| matches.get(i))
+        .ok_or_else(|| Error::Download("No channel picked".to_string()))
 }
 
-fn format_upload_date(date: &str) -> String {
-    // yt-dlp returns YYYYMMDD format
-    if date.len() == 8 {
-        format!("{}-{}-{}", &date[0..4], &date[4..6], &date[6..8])
-    } else {
-        date.to_string()
+/// `Name (@handle, 1.2M subscribers)`
+fn describe(channel: &ChannelMatch) -> String {
+    let mut details = vec![channel.handle.clone().unwrap_or_else(|| channel.id.clone())];
+    if let Some(followers) = channel.followers {
+        details.push(format!("{} subscribers", format::compact_number(followers)));
     }
+    format!("{} ({})", channel.name, details.join(", "))
 }
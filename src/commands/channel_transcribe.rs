@@ -0,0 +1,92 @@
+use crate::commands::channel::resolve as resolve_channel;
+use crate::commands::history;
+use crate::commands::transcribe::run_many;
+use crate::config::ensure_writable;
+use crate::database::get_transcript_by_id;
+use crate::downloader::{fetch_channel_videos, PlaylistEntry};
+use crate::error::Result;
+use crate::format;
+use crate::transcriber::Backend;
+
+/// Duration bounds in seconds; videos of unknown length are skipped when either is set
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DurationFilter {
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+impl DurationFilter {
+    pub fn matches(&self, duration: Option<i64>) -> bool {
+        if self.min.is_none() && self.max.is_none() {
+            return true;
+        }
+        let Some(duration) = duration else {
+            return false;
+        };
+        self.min.is_none_or(|min| duration >= min) && self.max.is_none_or(|max| duration <= max)
+    }
+}
+
+/// Transcribe a channel's latest videos that aren't in the database yet
+pub async fn run(
+    channel: &str,
+    limit: usize,
+    durations: DurationFilter,
+    backend: Backend,
+    captions_lang: Option<&str>,
+    jobs: usize,
+    first: bool,
+) -> Result<()> {
+    ensure_writable("transcribe")?;
+
+    let channel = &resolve_channel(channel, first)?;
+    eprintln!("Fetching videos from channel...");
+    let videos = fetch_channel_videos(channel, limit)?;
+
+    if videos.is_empty() {
+        println!("No videos found for channel: {}", channel);
+        return Ok(());
+    }
+
+    let mut existing = 0;
+    let mut out_of_range: Vec<&PlaylistEntry> = Vec::new();
+    let mut pending: Vec<&str> = Vec::new();
+
+    for video in &videos {
+        if get_transcript_by_id(&video.id)?.is_some() {
+            existing += 1;
+        } else if !durations.matches(video.duration) {
+            out_of_range.push(video);
+        } else {
+            pending.push(&video.url);
+        }
+    }
+
+    println!(
+        "Found {} video(s): {} already transcribed, {} outside duration limits, {} to transcribe.",
+        videos.len(),
+        existing,
+        out_of_range.len(),
+        pending.len()
+    );
+    for video in &out_of_range {
+        let length = video
+            .duration
+            .map(format::duration)
+            .unwrap_or_else(|| "unknown length".to_string());
+        println!("  Skipped: {} ({})", video.title, length);
+    }
+
+    if pending.is_empty() {
+        history::record("channel-transcribe", Some(channel), Some("no new videos"), true);
+        return Ok(());
+    }
+
+    let result = run_many(&pending, backend, captions_lang, jobs).await;
+    let outcome = match &result {
+        Ok(()) => format!("{} new video(s) transcribed", pending.len()),
+        Err(e) => e.to_string(),
+    };
+    history::record("channel-transcribe", Some(channel), Some(&outcome), result.is_ok());
+    result
+}
@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::commands::history;
+use crate::config::{downloads_dir, ensure_writable, settings, transcripts_dir};
+use crate::database::list_inflight;
+use crate::error::Result;
+use crate::format;
+
+/// Age in hours after which `clean` removes leftovers when neither `--older-than` nor
+/// `clean_after_hours` is given
+const DEFAULT_MAX_AGE_HOURS: u64 = 24;
+
+/// Extensions of yt-dlp's partial downloads and fragments
+const PARTIAL_EXTENSIONS: &[&str] = &["part", "ytdl", "temp"];
+
+/// Remove files left in the downloads directory by failed or interrupted runs, and with
+/// `dirs` empty directories under the transcripts directory
+///
+/// Audio of unfinished jobs (see `yt-cli resume`) is kept, as is anything modified within
+/// `older_than_hours` (default: `clean_after_hours` in config.toml, else a day), which
+/// covers downloads still in progress.
+pub fn run(older_than_hours: Option<u64>, dry_run: bool, dirs: bool) -> Result<()> {
+    if !dry_run {
+        ensure_writable("clean downloads")?;
+    }
+
+    clean_downloads(older_than_hours, dry_run)?;
+    if dirs {
+        remove_empty_dirs(dry_run);
+    }
+    Ok(())
+}
+
+fn clean_downloads(older_than_hours: Option<u64>, dry_run: bool) -> Result<()> {
+    let hours = older_than_hours.or(settings().clean_after_hours).unwrap_or(DEFAULT_MAX_AGE_HOURS);
+    let cutoff = SystemTime::now() - Duration::from_secs(hours * 3600);
+    let keep: HashSet<PathBuf> = list_inflight()?
+        .into_iter()
+        .filter_map(|job| job.audio_path)
+        .map(PathBuf::from)
+        .collect();
+
+    let dir = downloads_dir();
+    let mut stale = Vec::new();
+    let mut stale_dirs = Vec::new();
+    collect_stale(&dir, cutoff, &keep, &mut stale, &mut stale_dirs);
+
+    if stale.is_empty() {
+        println!("Nothing older than {}h in {}.", hours, dir.display());
+        return Ok(());
+    }
+
+    let total: u64 = stale.iter().map(|(_, size)| size).sum();
+    let partial = stale.iter().filter(|(path, _)| is_partial(path)).count();
+    let verb = if dry_run { "Would remove" } else { "Removing" };
+    for (path, size) in &stale {
+        println!("{} {} ({})", verb, path.display(), format::size(*size));
+    }
+
+    if dry_run {
+        println!(
+            "\n{} file(s), {} ({} partial download(s)); nothing removed.",
+            stale.len(),
+            format::size(total),
+            partial
+        );
+        return Ok(());
+    }
+
+    let mut removed = 0;
+    let mut freed = 0;
+    for (path, size) in &stale {
+        match fs::remove_file(path) {
+            Ok(()) => {
+                removed += 1;
+                freed += size;
+            }
+            Err(e) => eprintln!("Could not remove {}: {}", path.display(), e),
+        }
+    }
+    // Subdirectories (e.g. an aligner's working directory) emptied by the cleanup; removing
+    // one still holding files fails, as intended
+    for dir in &stale_dirs {
+        let _ = fs::remove_dir(dir);
+    }
+
+    println!("\nRemoved {} file(s), freed {} ({} partial download(s)).", removed, format::size(freed), partial);
+    history::record("clean", None, Some(&format!("{} file(s), {}", removed, format::size(freed))), true);
+    Ok(())
+}
+
+/// Remove channel and platform directories under the transcripts directory left empty by
+/// deleted or moved transcripts
+fn remove_empty_dirs(dry_run: bool) {
+    let root = transcripts_dir();
+    let mut empty = Vec::new();
+    if let Ok(entries) = fs::read_dir(&root) {
+        for entry in entries.flatten() {
+            collect_empty(&entry.path(), &mut empty);
+        }
+    }
+
+    if empty.is_empty() {
+        println!("No empty directories in {}.", root.display());
+        return;
+    }
+
+    let mut removed = 0;
+    for dir in &empty {
+        if dry_run {
+            println!("Would remove empty directory {}", dir.display());
+        } else if let Err(e) = fs::remove_dir(dir) {
+            eprintln!("Could not remove {}: {}", dir.display(), e);
+        } else {
+            removed += 1;
+        }
+    }
+
+    if dry_run {
+        println!("\n{} empty directory(ies); nothing removed.", empty.len());
+    } else {
+        println!("Removed {} empty directory(ies).", removed);
+        history::record("clean", None, Some(&format!("{} empty directory(ies)", removed)), true);
+    }
+}
+
+/// Add `dir` to `empty` if nothing but empty directories is under it (innermost first);
+/// returns whether it was
+fn collect_empty(dir: &Path, empty: &mut Vec<PathBuf>) -> bool {
+    if !dir.is_dir() || dir.is_symlink() {
+        return false;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    let mut all_empty = true;
+    for entry in entries.flatten() {
+        // Every child is visited, so empty directories beside non-empty ones are found too
+        all_empty &= collect_empty(&entry.path(), empty);
+    }
+    if all_empty {
+        empty.push(dir.to_path_buf());
+    }
+    all_empty
+}
+
+/// Files under `dir` last modified before `cutoff`, with their sizes, and subdirectories
+/// last modified before it (innermost first)
+fn collect_stale(
+    dir: &Path,
+    cutoff: SystemTime,
+    keep: &HashSet<PathBuf>,
+    stale: &mut Vec<(PathBuf, u64)>,
+    stale_dirs: &mut Vec<PathBuf>,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        let old = meta.modified().is_ok_and(|modified| modified < cutoff);
+        if meta.is_dir() {
+            collect_stale(&path, cutoff, keep, stale, stale_dirs);
+            if old {
+                stale_dirs.push(path);
+            }
+        } else if old && !keep.contains(&path) {
+            stale.push((path, meta.len()));
+        }
+    }
+}
+
+/// yt-dlp's `.part`/`.ytdl` files and `.part-Frag12` fragments
+fn is_partial(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_lowercase()).unwrap_or_default();
+    name.contains(".part-frag")
+        || path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| PARTIAL_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
@@ -0,0 +1,107 @@
+use std::path::PathBuf;
+
+use crate::commands::read::resolve_path;
+use crate::error::{Error, Result};
+use crate::ffmpeg;
+use crate::storage::get_transcript;
+use crate::transcriber::{format_timestamp, speaker_label, TranscriptData};
+
+/// Cut the stored audio of a transcript between two timestamps and print what was said
+///
+/// Without `output`, the clip is written to `VIDEO_ID_START-END.mp3` (seconds) in the
+/// current directory.
+pub fn run(path_or_id: &str, from: &str, to: &str, output: Option<&str>) -> Result<()> {
+    let start = parse_timestamp(from)?;
+    let end = parse_timestamp(to)?;
+    if end <= start {
+        return Err(Error::Config(format!("--to ({}) must be after --from ({})", to, from)));
+    }
+
+    let path = PathBuf::from(resolve_path(path_or_id)?);
+    let audio = path.join("audio.mp3");
+    if !audio.exists() {
+        return Err(Error::FileNotFound(format!("No audio.mp3 stored in {}", path.display())));
+    }
+
+    let output = match output {
+        Some(output) => PathBuf::from(output),
+        None => {
+            let video_id = path.file_name().unwrap_or_default().to_string_lossy();
+            PathBuf::from(format!("{}_{}-{}.mp3", video_id, start / 1000, end / 1000))
+        }
+    };
+    ffmpeg::cut(&audio, &output, start, end)?;
+
+    if let Ok(content) = get_transcript(&path.to_string_lossy())
+        && let Some(data) = content.structured
+    {
+        for line in excerpt(&data, start, end) {
+            println!("{}", line);
+        }
+        println!();
+    }
+    eprintln!(
+        "Saved {}-{} to {}",
+        format_timestamp(start),
+        format_timestamp(end),
+        output.display()
+    );
+
+    Ok(())
+}
+
+/// `SS`, `MM:SS` or `HH:MM:SS`, optionally with a decimal fraction, in milliseconds
+pub fn parse_timestamp(value: &str) -> Result<i64> {
+    let invalid = || Error::Config(format!("Invalid timestamp '{}' (expected e.g. 12:30 or 1:02:03)", value));
+
+    let (clock, fraction) = value.trim().split_once('.').unwrap_or((value.trim(), ""));
+    let mut seconds = 0i64;
+    for (i, part) in clock.split(':').enumerate() {
+        let part: i64 = part.parse().map_err(|_| invalid())?;
+        if i > 2 || (i > 0 && part >= 60) {
+            return Err(invalid());
+        }
+        seconds = seconds * 60 + part;
+    }
+
+    let millis = match fraction {
+        "" => 0,
+        digits if digits.len() <= 3 && digits.bytes().all(|b| b.is_ascii_digit()) => {
+            format!("{:0<3}", digits).parse::<i64>().map_err(|_| invalid())?
+        }
+        _ => return Err(invalid()),
+    };
+    Ok(seconds * 1000 + millis)
+}
+
+/// Transcript lines spoken between `start` and `end`: words in range grouped by speaker,
+/// or the overlapping utterances when there are no word timings
+fn excerpt(data: &TranscriptData, start: i64, end: i64) -> Vec<String> {
+    if data.words.is_empty() {
+        return data
+            .utterances
+            .iter()
+            .filter(|u| u.end > start && u.start < end)
+            .map(|u| format!("[{}] {}: {}", format_timestamp(u.start), speaker_label(&u.speaker), u.text.trim()))
+            .collect();
+    }
+
+    let mut lines: Vec<(i64, Option<&str>, String)> = Vec::new();
+    for word in data.words.iter().filter(|w| w.end > start && w.start < end) {
+        match lines.last_mut() {
+            Some((_, speaker, text)) if *speaker == word.speaker.as_deref() => {
+                text.push(' ');
+                text.push_str(&word.text);
+            }
+            _ => lines.push((word.start, word.speaker.as_deref(), word.text.clone())),
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|(start, speaker, text)| match speaker {
+            Some(speaker) => format!("[{}] {}: {}", format_timestamp(start), speaker_label(speaker), text),
+            None => format!("[{}] {}", format_timestamp(start), text),
+        })
+        .collect()
+}
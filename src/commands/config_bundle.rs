@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::history;
+use crate::config::{
+    assemblyai_api_key, config_file_path, deepgram_api_key, ensure_directories, ensure_writable,
+    env_file_path,
+};
+use crate::database::{add_subscription, list_subscriptions};
+use crate::error::{Error, Result};
+
+const BUNDLE_VERSION: u32 = 1;
+
+/// Portable snapshot of a yt-cli setup
+#[derive(Debug, Serialize, Deserialize)]
+struct Bundle {
+    version: u32,
+    /// Contents of config.toml, if there was one
+    config_toml: Option<String>,
+    #[serde(default)]
+    subscriptions: Vec<BundledSubscription>,
+    /// API keys by environment variable name (only with --with-secrets)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    secrets: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BundledSubscription {
+    url: String,
+    name: Option<String>,
+    last_video_id: Option<String>,
+}
+
+pub fn export(path: &str, with_secrets: bool) -> Result<()> {
+    let config_path = config_file_path();
+    let config_toml = if config_path.exists() {
+        let content = std::fs::read_to_string(&config_path)?;
+        Some(if with_secrets { content } else { strip_config_secrets(&content)? })
+    } else {
+        None
+    };
+
+    let subscriptions = list_subscriptions()?
+        .into_iter()
+        .map(|s| BundledSubscription {
+            url: s.url,
+            name: s.name,
+            last_video_id: s.last_video_id,
+        })
+        .collect::<Vec<_>>();
+
+    let mut secrets = BTreeMap::new();
+    if with_secrets {
+        if let Some(key) = assemblyai_api_key() {
+            secrets.insert("ASSEMBLYAI_API_KEY".to_string(), key);
+        }
+        if let Some(key) = deepgram_api_key() {
+            secrets.insert("DEEPGRAM_API_KEY".to_string(), key);
+        }
+    }
+
+    let bundle = Bundle {
+        version: BUNDLE_VERSION,
+        config_toml,
+        subscriptions,
+        secrets,
+    };
+    let json = serde_json::to_string_pretty(&bundle)?;
+    if with_secrets {
+        write_private(Path::new(path), &json)?;
+    } else {
+        std::fs::write(path, json)?;
+    }
+
+    println!("Exported configuration to {}", path);
+    println!("  config.toml:   {}", if bundle.config_toml.is_some() { "included" } else { "none" });
+    println!("  subscriptions: {}", bundle.subscriptions.len());
+    if with_secrets {
+        println!("  secrets:       {} (keep this file private)", bundle.secrets.len());
+    } else {
+        println!("  secrets:       excluded (use --with-secrets to include API keys)");
+    }
+
+    Ok(())
+}
+
+pub fn import(path: &str, force: bool) -> Result<()> {
+    ensure_writable("import configuration")?;
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Error::FileNotFound(format!("{}: {}", path, e)))?;
+    let bundle: Bundle = serde_json::from_str(&content)
+        .map_err(|e| Error::Config(format!("Not a yt-cli config bundle ({}): {}", path, e)))?;
+    if bundle.version > BUNDLE_VERSION {
+        return Err(Error::Config(format!(
+            "Bundle version {} is newer than this yt-cli supports ({}); run `yt-cli self-update`",
+            bundle.version, BUNDLE_VERSION
+        )));
+    }
+
+    ensure_directories()?;
+    println!("Importing configuration from {}", path);
+
+    if let Some(config_toml) = &bundle.config_toml {
+        let config_path = config_file_path();
+        if config_path.exists() && !force {
+            println!("  config.toml:   kept existing {} (use --force to overwrite)", config_path.display());
+        } else {
+            toml::from_str::<toml::Value>(config_toml)
+                .map_err(|e| Error::Config(format!("Bundled config.toml is invalid: {}", e)))?;
+            std::fs::write(&config_path, config_toml)?;
+            println!("  config.toml:   written to {}", config_path.display());
+        }
+    }
+
+    let mut added = 0;
+    for s in &bundle.subscriptions {
+        if add_subscription(&s.url, s.name.as_deref(), s.last_video_id.as_deref())? {
+            added += 1;
+        }
+    }
+    println!(
+        "  subscriptions: {} added, {} already present",
+        added,
+        bundle.subscriptions.len() - added
+    );
+
+    if !bundle.secrets.is_empty() {
+        merge_env_file(&env_file_path(), &bundle.secrets, force)?;
+    }
+
+    history::record(
+        "config-import",
+        Some(path),
+        Some(&format!("{} subscription(s) added", added)),
+        true,
+    );
+
+    Ok(())
+}
+
+/// Drop credentials (e.g. the WebDAV password, notifier webhook URLs) from config.toml
+/// before exporting
+fn strip_config_secrets(content: &str) -> Result<String> {
+    let mut value: toml::Value = toml::from_str(content)
+        .map_err(|e| Error::Config(format!("Invalid {}: {}", config_file_path().display(), e)))?;
+
+    let mut removed = value
+        .get_mut("storage")
+        .and_then(|storage| storage.as_table_mut())
+        .and_then(|storage| storage.remove("password"))
+        .is_some();
+    // Slack, Discord and webhook URLs carry their own token
+    if let Some(notifiers) = value.get_mut("notify").and_then(|notify| notify.as_array_mut()) {
+        for notifier in notifiers.iter_mut().filter_map(|notifier| notifier.as_table_mut()) {
+            removed |= notifier.remove("url").is_some();
+        }
+    }
+
+    if !removed {
+        // Keep comments and layout when there's nothing to strip
+        return Ok(content.to_string());
+    }
+    toml::to_string(&value).map_err(|e| Error::Config(format!("Could not write config.toml: {}", e)))
+}
+
+/// Add keys to .env, leaving existing values alone unless `force` is set
+fn merge_env_file(env_file: &Path, secrets: &BTreeMap<String, String>, force: bool) -> Result<()> {
+    let existing = std::fs::read_to_string(env_file).unwrap_or_default();
+    let mut lines: Vec<String> = existing.lines().map(String::from).collect();
+
+    for (name, value) in secrets {
+        let prefix = format!("{}=", name);
+        match lines.iter().position(|line| line.trim_start().starts_with(&prefix)) {
+            Some(_) if !force => {
+                println!("  {}: kept existing value (use --force to overwrite)", name);
+                continue;
+            }
+            Some(index) => lines[index] = format!("{}{}", prefix, value),
+            None => lines.push(format!("{}{}", prefix, value)),
+        }
+        println!("  {}: saved to {}", name, env_file.display());
+    }
+
+    write_private(env_file, &(lines.join("\n") + "\n"))?;
+    Ok(())
+}
+
+/// Write a file holding API keys or passwords so only its owner can read it
+fn write_private(path: &Path, content: &str) -> Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // The mode only applies to new files; an existing one may have been readable by all
+        if path.exists() {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    options.open(path)?.write_all(content.as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::anonymize::Anonymizer;
+use crate::database::TranscriptFilter;
+use crate::error::{Error, Result};
+use crate::storage::{get_transcript, list_transcripts, load_metadata, TranscriptInfo};
+use crate::transcriber::format_transcript;
+
+const DOCUMENT_SEPARATOR: &str = "<|endofdocument|>";
+
+pub fn run(channel: &str, out: Option<&str>, anonymize: bool) -> Result<()> {
+    let mut transcripts = list_transcripts(None, Some(channel), None, &TranscriptFilter::default())?;
+
+    if transcripts.is_empty() {
+        return Err(Error::FileNotFound(format!(
+            "No transcripts found for channel: {}",
+            channel
+        )));
+    }
+
+    // Oldest first so the corpus reads chronologically
+    transcripts.sort_by(|a, b| a.upload_date.cmp(&b.upload_date));
+
+    let mut corpus = String::new();
+    let mut count = 0;
+    let mut anonymizer = anonymize.then(Anonymizer::new);
+
+    for t in &transcripts {
+        let content = match get_transcript(&t.path) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Skipping {}: {}", t.path, e);
+                continue;
+            }
+        };
+
+        let metadata = load_metadata(Path::new(&t.path));
+        let mut identities: Vec<&str> = vec![&t.channel];
+        identities.extend(
+            ["uploader", "uploader_id"]
+                .iter()
+                .filter_map(|key| metadata.get(*key).and_then(|v| v.as_str())),
+        );
+
+        let text = match (content.structured, content.text) {
+            (Some(mut structured), _) => {
+                if let Some(anonymizer) = &anonymizer {
+                    anonymizer.transcript(&mut structured, &identities);
+                }
+                format_transcript(&structured)
+            }
+            (None, Some(text)) => match &anonymizer {
+                Some(anonymizer) => anonymizer.text(&text, &identities),
+                None => text,
+            },
+            (None, None) => continue,
+        };
+
+        if count > 0 {
+            corpus.push_str(DOCUMENT_SEPARATOR);
+            corpus.push_str("\n\n");
+        }
+        let header = match &mut anonymizer {
+            Some(anonymizer) => anonymous_header(anonymizer, &t.channel),
+            None => document_header(t),
+        };
+        corpus.push_str(&header);
+        corpus.push_str(text.trim());
+        corpus.push_str("\n\n");
+        count += 1;
+    }
+
+    match out {
+        Some(out) => {
+            std::fs::write(out, &corpus)?;
+            eprintln!("Wrote {} document(s) to {}", count, out);
+        }
+        None => print!("{}", corpus),
+    }
+
+    Ok(())
+}
+
+/// Header for anonymized documents: only pseudonymous identifiers
+fn anonymous_header(anonymizer: &mut Anonymizer, channel: &str) -> String {
+    format!(
+        "# Document: {}\n# Channel: {}\n\n",
+        anonymizer.document_id(),
+        anonymizer.channel(channel)
+    )
+}
+
+/// Metadata header written before each document
+fn document_header(t: &TranscriptInfo) -> String {
+    let dir = Path::new(&t.path);
+    let video_id = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    // The listing only knows the directory name, so take the title from metadata
+    let metadata = load_metadata(dir);
+    let title = metadata
+        .get("title")
+        .and_then(|v| v.as_str())
+        .unwrap_or(&t.title);
+
+    let mut header = String::new();
+    let _ = writeln!(header, "# Title: {}", title);
+    let _ = writeln!(header, "# Channel: {}", t.channel);
+    let _ = writeln!(header, "# Platform: {}", t.platform);
+    let _ = writeln!(header, "# Video ID: {}", video_id);
+    if let Some(url) = &t.url {
+        let _ = writeln!(header, "# URL: {}", url);
+    }
+    if let Some(date) = &t.upload_date {
+        let _ = writeln!(header, "# Upload date: {}", date);
+    }
+    if let Some(duration) = t.duration {
+        let _ = writeln!(header, "# Duration: {}s", duration);
+    }
+    header.push('\n');
+    header
+}
@@ -0,0 +1,74 @@
+use crate::commands::channel::resolve as resolve_channel;
+use crate::config::settings;
+use crate::database::get_transcript_by_id;
+use crate::downloader::fetch_channel_videos;
+use crate::error::Result;
+use crate::format;
+use crate::transcriber::Backend;
+
+/// How much of a channel's upload list is in the library, and what finishing it would cost
+///
+/// The cost is an estimate from the list price of `backend` and the configured model (or
+/// `price_per_hour`) and only covers videos whose length the listing reports.
+pub fn run(
+    channel: &str,
+    limit: Option<usize>,
+    backend: Backend,
+    price_per_hour: Option<f64>,
+    first: bool,
+) -> Result<()> {
+    let channel = resolve_channel(channel, first)?;
+    eprintln!("Fetching the channel's uploads...");
+    let videos = fetch_channel_videos(&channel, limit.unwrap_or(usize::MAX))?;
+
+    if videos.is_empty() {
+        println!("No videos found for channel: {}", channel);
+        return Ok(());
+    }
+
+    let mut transcribed = 0;
+    let mut missing = 0;
+    let mut missing_seconds = 0;
+    let mut unknown_length = 0;
+    for video in &videos {
+        if get_transcript_by_id(&video.id)?.is_some() {
+            transcribed += 1;
+            continue;
+        }
+        missing += 1;
+        match video.duration {
+            Some(duration) => missing_seconds += duration,
+            None => unknown_length += 1,
+        }
+    }
+
+    let percent = transcribed as f64 * 100.0 / videos.len() as f64;
+    println!("Channel:        {}", channel);
+    println!(
+        "Transcribed:    {} of {} video(s) ({:.1}%)",
+        format::number(transcribed),
+        format::number(videos.len() as i64),
+        percent
+    );
+    if missing == 0 {
+        println!("Untranscribed:  none, the archive is complete.");
+        return Ok(());
+    }
+
+    let hours = missing_seconds as f64 / 3600.0;
+    println!("Untranscribed:  {} video(s), {:.1} hours", format::number(missing), hours);
+    if unknown_length > 0 {
+        println!("                ({} of unknown length not counted)", unknown_length);
+    }
+
+    let rate = price_per_hour.unwrap_or_else(|| backend.price_per_hour(settings().transcription.speech_model));
+    println!(
+        "Estimated cost: ${:.2} with {} at ${:.2}/hour",
+        hours * rate,
+        backend.id(),
+        rate
+    );
+    println!("\nTo transcribe them, run:");
+    println!("  yt-cli channel-transcribe {} -n {}", channel, videos.len());
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tokio::sync::watch;
+
+use crate::commands::history;
+use crate::commands::reindex::index_pending;
+use crate::commands::subscriptions::{sync, SyncOptions};
+use crate::config::{data_dir, ensure_directories, ensure_writable};
+use crate::error::{print_error, Error, Result};
+
+/// Removes the PID file when the daemon exits
+struct PidFile(PathBuf);
+
+impl PidFile {
+    /// Write our PID, refusing to start if another daemon still owns the file
+    fn create(path: PathBuf) -> Result<Self> {
+        if let Ok(content) = std::fs::read_to_string(&path)
+            && let Ok(pid) = content.trim().parse::<u32>()
+            && pid != std::process::id()
+            && process_alive(pid)
+        {
+            return Err(Error::Config(format!(
+                "Daemon already running (PID {}, {})",
+                pid,
+                path.display()
+            )));
+        }
+
+        std::fs::write(&path, format!("{}\n", std::process::id()))?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Whether a process with this PID exists (stale PID files are overwritten)
+///
+/// When it can't be told, the process is taken to be alive, so its PID file and queue jobs
+/// aren't taken over while it may still be running.
+pub(crate) fn process_alive(pid: u32) -> bool {
+    if cfg!(target_os = "linux") {
+        return Path::new(&format!("/proc/{}", pid)).exists();
+    }
+    if cfg!(windows) {
+        // A CSV row quoting the PID when it exists, an "INFO: No tasks" line otherwise
+        let filter = format!("PID eq {}", pid);
+        return match std::process::Command::new("tasklist").args(["/FI", &filter, "/NH", "/FO", "CSV"]).output() {
+            Ok(output) if output.status.success() => {
+                String::from_utf8_lossy(&output.stdout).contains(&format!("\"{}\"", pid))
+            }
+            _ => true,
+        };
+    }
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .map_or(true, |status| status.success())
+}
+
+/// Sync subscriptions every `interval_minutes` until SIGINT/SIGTERM
+///
+/// A signal during a sync lets the current transcription finish; a second one exits at once.
+pub async fn run(interval_minutes: u64, pid_file: Option<PathBuf>, options: SyncOptions) -> Result<()> {
+    ensure_writable("run the daemon")?;
+    ensure_directories()?;
+
+    let _pid_file = PidFile::create(pid_file.unwrap_or_else(|| data_dir().join("daemon.pid")))?;
+    let interval = Duration::from_secs(interval_minutes.max(1) * 60);
+
+    let (shutdown_tx, mut shutdown) = watch::channel(false);
+    tokio::spawn(async move {
+        wait_for_signal().await;
+        eprintln!("Shutdown requested, finishing the current sync (signal again to exit now)...");
+        let _ = shutdown_tx.send(true);
+        wait_for_signal().await;
+        std::process::exit(130);
+    });
+
+    eprintln!(
+        "Daemon started (PID {}), syncing every {} minute(s).",
+        std::process::id(),
+        interval.as_secs() / 60
+    );
+    history::record("daemon", None, Some("started"), true);
+
+    loop {
+        match index_pending(false) {
+            Ok((completed, _)) if completed > 0 => {
+                eprintln!("Finished indexing {} transcript(s) that weren't searchable.", completed)
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Pending index retry failed:");
+                print_error(&e);
+            }
+        }
+        if let Err(e) = sync(&options).await {
+            eprintln!("Sync failed:");
+            print_error(&e);
+        }
+
+        if *shutdown.borrow() {
+            break;
+        }
+        eprintln!("Next sync in {} minute(s).", interval.as_secs() / 60);
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = shutdown.changed() => break,
+        }
+    }
+
+    eprintln!("Daemon stopped.");
+    history::record("daemon", None, Some("stopped"), true);
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut sigterm) => {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = sigterm.recv() => {}
+            }
+        }
+        Err(_) => {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
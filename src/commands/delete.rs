@@ -0,0 +1,132 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::commands::history;
+use crate::commands::undo::{RestoreDir, UndoAction};
+use crate::config::{ensure_writable, transcripts_dir, trash_dir};
+use crate::database::{delete_transcript, get_tags, transcripts_for_video};
+use crate::error::{Error, Result};
+use crate::storage::{backend, remove_empty_parents};
+
+/// Remove a video's transcripts from the library: database rows, search index and, unless
+/// `keep_files`, their directories under the transcripts directory
+///
+/// Directories are moved to the trash rather than removed, so `yt-cli undo` can put them
+/// back. With `dry_run`, only prints what would go. Copies in a remote `[storage]` backend
+/// are left alone.
+pub fn run(video_id: &str, keep_files: bool, dry_run: bool) -> Result<()> {
+    if !dry_run {
+        ensure_writable("delete transcripts")?;
+    }
+
+    let records = transcripts_for_video(video_id)?;
+    if records.is_empty() {
+        return Err(Error::FileNotFound(format!("No transcript for video {}", video_id)));
+    }
+
+    let mut directories: Vec<PathBuf> = records.iter().map(|r| PathBuf::from(&r.path)).collect();
+    directories.sort();
+    directories.dedup();
+    if !keep_files {
+        for dir in &directories {
+            check_inside_library(dir)?;
+        }
+    }
+
+    let verb = if dry_run { "Would delete" } else { "Deleting" };
+    for record in &records {
+        println!("{} {} [{}] from the index", verb, record.title, record.source);
+    }
+    if !keep_files {
+        let verb = if dry_run { "Would move" } else { "Moving" };
+        for dir in directories.iter().filter(|d| d.exists()) {
+            println!("{} {} ({} file(s)) to the trash", verb, dir.display(), count_files(dir));
+        }
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    // Tags live only in the database, so they are kept for undo alongside the files
+    let mut restore = Vec::new();
+    for dir in &directories {
+        let path = dir.to_string_lossy().into_owned();
+        let tags = get_tags(&path)?;
+        restore.push(RestoreDir { path, trashed: None, tags });
+    }
+
+    delete_transcript(video_id)?;
+    // The rows are gone, so undo is recorded even if a directory can't be moved: it then
+    // reindexes the ones still in place and brings back the ones that reached the trash
+    let moved = if keep_files { Ok(()) } else { move_to_trash(video_id, &directories, &mut restore) };
+    let detail = match (&moved, keep_files) {
+        (Err(_), _) => "some files could not be moved to the trash",
+        (Ok(()), true) => "kept files",
+        (Ok(()), false) => "moved files to the trash",
+    };
+    history::record_undoable(
+        "delete",
+        Some(video_id),
+        Some(detail),
+        &UndoAction::Restore { video_id: video_id.to_string(), directories: restore },
+    );
+    moved?;
+
+    if !keep_files && backend().name() != "local" {
+        eprintln!("Note: copies in the {} storage backend were not removed.", backend().name());
+    }
+    println!("Deleted {}. `yt-cli undo` restores it.", video_id);
+    Ok(())
+}
+
+/// Move each existing directory into a new trash entry, noting where it went in `restore`
+fn move_to_trash(video_id: &str, directories: &[PathBuf], restore: &mut [RestoreDir]) -> Result<()> {
+    let trash = trash_entry(video_id)?;
+    for (dir, entry) in directories.iter().zip(restore).filter(|(d, _)| d.exists()) {
+        let trashed = trash.join(dir.file_name().unwrap_or_default());
+        fs::rename(dir, &trashed)?;
+        remove_empty_parents(dir);
+        entry.trashed = Some(trashed.to_string_lossy().into_owned());
+    }
+    Ok(())
+}
+
+/// Create a new trash directory for a video's deleted transcripts
+fn trash_entry(video_id: &str) -> Result<PathBuf> {
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis()).unwrap_or_default();
+    let dir = trash_dir().join(format!("{}-{}", stamp, video_id));
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Refuse to remove a directory the database points outside the transcripts directory
+fn check_inside_library(dir: &Path) -> Result<()> {
+    let Ok(dir) = dir.canonicalize() else {
+        // Already gone; nothing to remove
+        return Ok(());
+    };
+    let root = transcripts_dir().canonicalize()?;
+    if dir == root || !dir.starts_with(&root) {
+        return Err(Error::Config(format!(
+            "{} is not inside the transcripts directory {}; rerun with --keep-files and remove it yourself",
+            dir.display(),
+            root.display()
+        )));
+    }
+    Ok(())
+}
+
+fn count_files(dir: &Path) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| match entry.file_type() {
+                    Ok(t) if t.is_dir() => count_files(&entry.path()),
+                    _ => 1,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
@@ -0,0 +1,54 @@
+use std::path::Path;
+
+use crate::commands::read::resolve_path;
+use crate::error::Result;
+use crate::format;
+use crate::storage::{list_attachments, load_metadata};
+
+/// Files every transcript directory may hold, in display order
+const TRANSCRIPT_FILES: &[&str] = &["transcript.md", "transcript.txt", "transcript.json", "metadata.json", "audio.mp3"];
+
+/// Print what the library holds for one transcript: its metadata, files and attachments
+pub fn run(path_or_id: &str) -> Result<()> {
+    let path = resolve_path(path_or_id)?;
+    let path = Path::new(&path);
+    let metadata = load_metadata(path);
+    let field = |key: &str| metadata.get(key).and_then(|v| v.as_str()).map(String::from);
+
+    if let Some(title) = field("title") {
+        println!("Title:    {}", title);
+    }
+    if let Some(channel) = field("channel") {
+        println!("Channel:  {}", channel);
+    }
+    if let Some(url) = field("webpage_url").or_else(|| field("url")) {
+        println!("URL:      {}", url);
+    }
+    if let Some(date) = field("upload_date") {
+        println!("Uploaded: {}", format::upload_date(&date));
+    }
+    if let Some(duration) = metadata.get("duration").and_then(|v| v.as_i64()) {
+        println!("Duration: {}", format::duration(duration));
+    }
+    println!("Path:     {}", path.display());
+
+    println!("\nFiles:");
+    for name in TRANSCRIPT_FILES {
+        if let Ok(meta) = path.join(name).metadata() {
+            println!("  {:<30} {:>10}", name, format::size(meta.len()));
+        }
+    }
+
+    let attachments = list_attachments(path);
+    if attachments.is_empty() {
+        println!("\nNo attachments (add some with `yt-cli attach`).");
+        return Ok(());
+    }
+    println!("\nAttachments:");
+    for attachment in attachments {
+        let indexed = if attachment.text.is_some() { "  (searchable)" } else { "" };
+        println!("  {:<30} {:>10}{}", attachment.name, format::size(attachment.size), indexed);
+    }
+
+    Ok(())
+}
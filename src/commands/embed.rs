@@ -0,0 +1,40 @@
+use crate::config::{ensure_writable, settings};
+use crate::database::{remove_orphan_embeddings, save_embedding, transcripts_missing_embedding};
+use crate::embeddings;
+use crate::error::Result;
+
+/// Transcripts embedded per request
+const BATCH_SIZE: usize = 16;
+
+/// Embed every indexed transcript that has no vector from the configured model yet,
+/// for `search --hybrid`
+pub async fn run() -> Result<()> {
+    ensure_writable("store embeddings")?;
+
+    let removed = remove_orphan_embeddings()?;
+    if removed > 0 {
+        eprintln!("Removed {} embedding(s) of transcripts no longer indexed.", removed);
+    }
+
+    let model = &settings().embeddings.model;
+    let pending = transcripts_missing_embedding(model)?;
+    if pending.is_empty() {
+        println!("All transcripts are embedded with {}.", model);
+        return Ok(());
+    }
+
+    eprintln!("Embedding {} transcript(s) with {}...", pending.len(), model);
+    let mut done = 0;
+    for batch in pending.chunks(BATCH_SIZE) {
+        let texts: Vec<String> = batch.iter().map(|(_, text)| text.clone()).collect();
+        let vectors = embeddings::embed(&texts).await?;
+        for ((transcript_id, _), vector) in batch.iter().zip(&vectors) {
+            save_embedding(*transcript_id, model, vector)?;
+        }
+        done += batch.len();
+        eprintln!("  {}/{}", done, pending.len());
+    }
+
+    println!("Embedded {} transcript(s).", done);
+    Ok(())
+}
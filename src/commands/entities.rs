@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use crate::commands::read::resolve_path;
+use crate::database::top_entities;
+use crate::error::{Error, Result};
+use crate::storage::get_transcript;
+use crate::transcriber::format_timestamp;
+
+/// Moments listed per entity of a single transcript
+const MOMENTS_SHOWN: usize = 5;
+
+/// Print the people, organizations and places named in one transcript, or the most
+/// widespread ones across the library
+///
+/// Entities only exist for transcripts made with `transcribe --entities`.
+pub fn run(path_or_id: Option<&str>, channel: Option<&str>, entity_type: Option<&str>, limit: i32) -> Result<()> {
+    let Some(path_or_id) = path_or_id else {
+        return library(channel, entity_type, limit);
+    };
+
+    let path = resolve_path(path_or_id)?;
+    let data = get_transcript(&path)?
+        .structured
+        .ok_or_else(|| Error::FileNotFound(format!("No transcript.json at {}", path)))?;
+    if data.entities.is_empty() {
+        println!("No entities in this transcript (transcribe it with --entities).");
+        return Ok(());
+    }
+
+    // Group mentions by type, then name (case-insensitive, first spelling kept)
+    let mut groups: BTreeMap<&str, BTreeMap<String, (&str, Vec<i64>)>> = BTreeMap::new();
+    for entity in data.entities.iter().filter(|e| entity_type.is_none_or(|t| t == e.entity_type)) {
+        let name = entity.text.trim();
+        groups
+            .entry(&entity.entity_type)
+            .or_default()
+            .entry(name.to_lowercase())
+            .or_insert_with(|| (name, Vec::new()))
+            .1
+            .push(entity.start);
+    }
+    if groups.is_empty() {
+        println!("No {} entities in this transcript.", entity_type.unwrap_or_default());
+        return Ok(());
+    }
+
+    for (kind, names) in groups {
+        println!("{}:", kind);
+        let mut names: Vec<_> = names.into_values().collect();
+        names.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(b.0)));
+        for (name, starts) in names {
+            let mut moments: Vec<String> = starts.iter().take(MOMENTS_SHOWN).map(|&s| format_timestamp(s)).collect();
+            if starts.len() > MOMENTS_SHOWN {
+                moments.push("...".to_string());
+            }
+            println!("  - {} ({}x: {})", name, starts.len(), moments.join(", "));
+        }
+    }
+    Ok(())
+}
+
+fn library(channel: Option<&str>, entity_type: Option<&str>, limit: i32) -> Result<()> {
+    let entities = top_entities(limit, channel, entity_type)?;
+    if entities.is_empty() {
+        match channel {
+            Some(channel) => println!("No entities from channels matching '{}'.", channel),
+            None => println!("No entities indexed yet (transcribe with --entities, or run `yt-cli reindex`)."),
+        }
+        return Ok(());
+    }
+
+    println!("{:<30} {:<16} {:>11} {:>8}", "Entity", "Type", "Transcripts", "Mentions");
+    for entity in entities {
+        println!(
+            "{:<30} {:<16} {:>11} {:>8}",
+            entity.name, entity.entity_type, entity.transcripts, entity.mentions
+        );
+    }
+    Ok(())
+}
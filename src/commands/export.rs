@@ -0,0 +1,422 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::anonymize::Anonymizer;
+use crate::commands::clip::parse_timestamp;
+use crate::commands::read::resolve_path;
+use crate::database::TranscriptFilter;
+use crate::error::{Error, Result};
+use crate::storage::{get_transcript, list_transcripts, load_metadata, metadata_sections};
+use crate::transcriber::{
+    format_srt, format_transcript, format_transcript_markdown_sections, format_ttml, format_vtt,
+    html_lang, mark_direction, Section, TranscriptData,
+};
+
+/// Supported export formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// One JSON object per utterance
+    Jsonl,
+    /// One JSON object per word, with timings, confidence and speaker
+    WordsJsonl,
+    /// One CSV row per word, with timings, confidence and speaker
+    WordsCsv,
+    /// One CSV row per speaker turn, with timings and word count (conversation analysis)
+    TurnsCsv,
+    /// Plain text with speaker labels
+    Text,
+    /// Markdown with speaker paragraphs and chapter headings
+    Markdown,
+    /// The full structured transcript (words, utterances, timings)
+    Json,
+    /// SubRip subtitles (for video editors)
+    Srt,
+    /// WebVTT subtitles (for web players)
+    Vtt,
+    /// TTML subtitles (for broadcast and streaming tools)
+    Ttml,
+}
+
+impl ExportFormat {
+    /// Whether the format is timed subtitles
+    fn is_subtitles(self) -> bool {
+        matches!(self, ExportFormat::Srt | ExportFormat::Vtt | ExportFormat::Ttml)
+    }
+
+    /// Whether the format holds a single transcript rather than a dataset
+    fn is_document(self) -> bool {
+        !matches!(
+            self,
+            ExportFormat::Jsonl | ExportFormat::WordsJsonl | ExportFormat::WordsCsv | ExportFormat::TurnsCsv
+        )
+    }
+}
+
+/// A transcript selected for export
+struct ExportItem {
+    video_id: String,
+    channel: String,
+    /// Other names identifying the source (uploader, handle), used for anonymization
+    aliases: Vec<String>,
+    /// Chapters from the platform metadata
+    sections: Vec<Section>,
+    /// Spoken language from the platform metadata
+    language: Option<String>,
+    data: TranscriptData,
+}
+
+impl ExportItem {
+    /// Language detected by the transcription service, else the platform's
+    fn language(&self) -> Option<&str> {
+        self.data.language.as_deref().or(self.language.as_deref())
+    }
+}
+
+/// One utterance in the JSONL dataset
+#[derive(Serialize)]
+struct UtteranceRecord<'a> {
+    video_id: &'a str,
+    channel: &'a str,
+    speaker: Option<&'a str>,
+    start: i64,
+    end: i64,
+    text: &'a str,
+}
+
+/// One word in the word-level dataset
+#[derive(Serialize)]
+struct WordRecord<'a> {
+    video_id: &'a str,
+    channel: &'a str,
+    index: usize,
+    speaker: Option<&'a str>,
+    start: i64,
+    end: i64,
+    confidence: Option<f64>,
+    text: &'a str,
+}
+
+pub fn run(
+    video_ids: &[String],
+    platform: Option<&str>,
+    channel: Option<&str>,
+    format: ExportFormat,
+    output: Option<&str>,
+    anonymize: bool,
+    offset: Option<&str>,
+) -> Result<()> {
+    let offset = offset.map(parse_offset).transpose()?;
+    if offset.is_some() && !format.is_subtitles() {
+        return Err(Error::Config("--offset only applies to srt, vtt and ttml exports".to_string()));
+    }
+
+    let mut items = select_transcripts(video_ids, platform, channel)?;
+
+    if items.is_empty() {
+        return Err(Error::FileNotFound("No transcripts matched the selection".to_string()));
+    }
+
+    if format.is_document() && items.len() != 1 {
+        return Err(Error::Config(format!(
+            "{:?} export takes a single video ID ({} transcripts selected)",
+            format,
+            items.len()
+        )));
+    }
+
+    if anonymize {
+        anonymize_items(&mut items);
+    }
+    if let Some(offset) = offset {
+        for item in &mut items {
+            shift_timings(&mut item.data, offset);
+        }
+    }
+
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+
+    match format {
+        ExportFormat::Jsonl => write_jsonl(&mut writer, &items)?,
+        ExportFormat::WordsJsonl | ExportFormat::WordsCsv => write_words(&mut writer, &items, format)?,
+        ExportFormat::TurnsCsv => write_turns(&mut writer, &items)?,
+        _ => writer.write_all(render(format, &items[0])?.as_bytes())?,
+    }
+
+    writer.flush()?;
+
+    if let Some(path) = output {
+        eprintln!("Exported {} transcript(s) to {}", items.len(), path);
+    }
+
+    Ok(())
+}
+
+/// Render a single transcript in a document format
+fn render(format: ExportFormat, item: &ExportItem) -> Result<String> {
+    Ok(match format {
+        ExportFormat::Text => format_transcript(&item.data) + "\n",
+        ExportFormat::Markdown => {
+            let markdown = format_transcript_markdown_sections(&item.data, &item.sections);
+            mark_direction(&markdown, item.language()) + "\n"
+        }
+        ExportFormat::Json => serde_json::to_string_pretty(&item.data)? + "\n",
+        ExportFormat::Srt => format_srt(&item.data),
+        ExportFormat::Vtt => format_vtt(&item.data),
+        ExportFormat::Ttml => format_ttml(&item.data, item.language().map(html_lang).as_deref()),
+        ExportFormat::Jsonl | ExportFormat::WordsJsonl | ExportFormat::WordsCsv | ExportFormat::TurnsCsv => {
+            unreachable!("datasets are written per record")
+        }
+    })
+}
+
+/// `--offset` in milliseconds: a timestamp like `12:30` or `90.5`, negative with a leading `-`
+fn parse_offset(value: &str) -> Result<i64> {
+    match value.trim().strip_prefix('-') {
+        Some(value) => parse_timestamp(value).map(|ms| -ms),
+        None => parse_timestamp(value),
+    }
+}
+
+/// Move every word and utterance by `offset` milliseconds, dropping what would end up
+/// before the start
+fn shift_timings(data: &mut TranscriptData, offset: i64) {
+    let timed = !data.words.is_empty() || !data.utterances.is_empty();
+    data.words.retain(|w| w.end + offset > 0);
+    for word in &mut data.words {
+        word.start = (word.start + offset).max(0);
+        word.end += offset;
+    }
+    data.utterances.retain(|u| u.end + offset > 0);
+    for utterance in &mut data.utterances {
+        utterance.start = (utterance.start + offset).max(0);
+        utterance.end += offset;
+    }
+    // Otherwise the subtitles would fall back to one cue holding the whole text
+    if timed && data.words.is_empty() && data.utterances.is_empty() {
+        data.text.clear();
+    }
+}
+
+/// Resolve explicit video IDs, or fall back to every transcript matching the filters
+fn select_transcripts(
+    video_ids: &[String],
+    platform: Option<&str>,
+    channel: Option<&str>,
+) -> Result<Vec<ExportItem>> {
+    let paths: Vec<PathBuf> = if video_ids.is_empty() {
+        list_transcripts(platform, channel, None, &TranscriptFilter::default())?
+            .into_iter()
+            .map(|t| PathBuf::from(t.path))
+            .collect()
+    } else {
+        video_ids
+            .iter()
+            .map(|id| resolve_path(id).map(PathBuf::from))
+            .collect::<Result<_>>()?
+    };
+
+    let mut items = Vec::new();
+    for path in paths {
+        match load_item(&path) {
+            Ok(Some(item)) => items.push(item),
+            Ok(None) => eprintln!("Skipping {}: no structured transcript", path.display()),
+            Err(e) => eprintln!("Skipping {}: {}", path.display(), e),
+        }
+    }
+
+    Ok(items)
+}
+
+fn load_item(path: &Path) -> Result<Option<ExportItem>> {
+    let content = get_transcript(&path.to_string_lossy())?;
+    let Some(data) = content.structured else {
+        return Ok(None);
+    };
+
+    let metadata = load_metadata(path);
+    let video_id = metadata
+        .get("id")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .unwrap_or_else(|| path.file_name().unwrap_or_default().to_string_lossy().to_string());
+    let channel = metadata
+        .get("channel")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .unwrap_or_else(|| {
+            path.parent()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Unknown".to_string())
+        });
+
+    let aliases = ["uploader", "uploader_id"]
+        .iter()
+        .filter_map(|key| metadata.get(*key).and_then(|v| v.as_str()).map(String::from))
+        .collect();
+
+    let language = metadata.get("language").and_then(|v| v.as_str()).map(String::from);
+    let sections = metadata_sections(&metadata);
+
+    Ok(Some(ExportItem { video_id, channel, aliases, sections, language, data }))
+}
+
+/// Replace channel names, video IDs and speaker labels, and redact PII from text
+fn anonymize_items(items: &mut [ExportItem]) {
+    let mut anonymizer = Anonymizer::new();
+
+    for item in items {
+        let mut identities: Vec<&str> = vec![&item.channel];
+        identities.extend(item.aliases.iter().map(String::as_str));
+        anonymizer.transcript(&mut item.data, &identities);
+
+        item.channel = anonymizer.channel(&item.channel);
+        item.video_id = anonymizer.document_id();
+        item.aliases.clear();
+    }
+}
+
+fn write_jsonl(writer: &mut dyn Write, items: &[ExportItem]) -> Result<()> {
+    for item in items {
+        if item.data.utterances.is_empty() {
+            // No diarization: emit the whole transcript as one record
+            let record = UtteranceRecord {
+                video_id: &item.video_id,
+                channel: &item.channel,
+                speaker: None,
+                start: 0,
+                end: item.data.audio_duration.unwrap_or(0) * 1000,
+                text: &item.data.text,
+            };
+            serde_json::to_writer(&mut *writer, &record)?;
+            writeln!(writer)?;
+            continue;
+        }
+
+        for utterance in &item.data.utterances {
+            let record = UtteranceRecord {
+                video_id: &item.video_id,
+                channel: &item.channel,
+                speaker: Some(&utterance.speaker),
+                start: utterance.start,
+                end: utterance.end,
+                text: &utterance.text,
+            };
+            serde_json::to_writer(&mut *writer, &record)?;
+            writeln!(writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Flatten every word into JSONL or CSV records (transcripts without word timings are skipped)
+fn write_words(writer: &mut dyn Write, items: &[ExportItem], format: ExportFormat) -> Result<()> {
+    let csv = format == ExportFormat::WordsCsv;
+    if csv {
+        writeln!(writer, "video_id,channel,index,speaker,start,end,confidence,text")?;
+    }
+
+    for item in items {
+        if item.data.words.is_empty() {
+            eprintln!("Skipping {}: no word timings", item.video_id);
+            continue;
+        }
+
+        for (index, word) in item.data.words.iter().enumerate() {
+            let record = WordRecord {
+                video_id: &item.video_id,
+                channel: &item.channel,
+                index,
+                speaker: word.speaker.as_deref(),
+                start: word.start,
+                end: word.end,
+                confidence: word.confidence,
+                text: &word.text,
+            };
+            if csv {
+                write_csv_row(writer, &record)?;
+            } else {
+                serde_json::to_writer(&mut *writer, &record)?;
+                writeln!(writer)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// One CSV row per speaker turn: consecutive utterances by the same speaker, merged
+/// (transcripts without speaker labels are skipped)
+fn write_turns(writer: &mut dyn Write, items: &[ExportItem]) -> Result<()> {
+    writeln!(writer, "video_id,channel,turn,speaker,start,end,duration,words")?;
+
+    for item in items {
+        if item.data.utterances.is_empty() {
+            eprintln!("Skipping {}: no speaker turns", item.video_id);
+            continue;
+        }
+
+        // (speaker, start, end, words)
+        let mut turns: Vec<(&str, i64, i64, usize)> = Vec::new();
+        for utterance in &item.data.utterances {
+            let words = utterance.text.split_whitespace().count();
+            match turns.last_mut() {
+                Some(turn) if turn.0 == utterance.speaker => {
+                    turn.2 = turn.2.max(utterance.end);
+                    turn.3 += words;
+                }
+                _ => turns.push((&utterance.speaker, utterance.start, utterance.end, words)),
+            }
+        }
+
+        for (index, (speaker, start, end, words)) in turns.into_iter().enumerate() {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{},{}",
+                csv_field(&item.video_id),
+                csv_field(&item.channel),
+                index,
+                csv_field(speaker),
+                start,
+                end,
+                end - start,
+                words
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn write_csv_row(writer: &mut dyn Write, record: &WordRecord) -> Result<()> {
+    writeln!(
+        writer,
+        "{},{},{},{},{},{},{},{}",
+        csv_field(record.video_id),
+        csv_field(record.channel),
+        record.index,
+        csv_field(record.speaker.unwrap_or_default()),
+        record.start,
+        record.end,
+        record.confidence.map(|c| c.to_string()).unwrap_or_default(),
+        csv_field(record.text)
+    )?;
+    Ok(())
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
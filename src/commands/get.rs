@@ -1,33 +1,8 @@
-use crate::database::get_transcript_by_id;
+use crate::database::{get_transcript_by_id, get_transcript_by_url};
+use crate::downloader::extract_metadata;
 use crate::error::{Error, Result};
-use crate::storage::get_platform_from_url;
-
-/// Extract video ID from URL
-fn extract_video_id(url: &str) -> Option<String> {
-    let url_lower = url.to_lowercase();
-
-    // YouTube: various formats
-    if url_lower.contains("youtube.com") || url_lower.contains("youtu.be") {
-        // youtube.com/watch?v=VIDEO_ID
-        if let Some(pos) = url.find("v=") {
-            let start = pos + 2;
-            let end = url[start..].find('&').map(|i| start + i).unwrap_or(url.len());
-            return Some(url[start..end].to_string());
-        }
-        // youtu.be/VIDEO_ID
-        if url_lower.contains("youtu.be/") {
-            if let Some(pos) = url.find("youtu.be/") {
-                let start = pos + 9;
-                let end = url[start..].find('?').map(|i| start + i).unwrap_or(url.len());
-                return Some(url[start..end].to_string());
-            }
-        }
-    }
-
-    // For other platforms, try to get the last path segment
-    let path = url.split('?').next().unwrap_or(url);
-    path.split('/').filter(|s| !s.is_empty()).last().map(String::from)
-}
+use crate::platform;
+use crate::transcriber::Backend;
 
 /// Try to find an existing transcript path for the given video ID
 fn find_transcript_path(url: &str, video_id: &str) -> Option<String> {
@@ -36,8 +11,13 @@ fn find_transcript_path(url: &str, video_id: &str) -> Option<String> {
         return Some(record.path);
     }
 
+    // The URL's ID doesn't always match yt-dlp's (e.g. Rumble slugs, Odysee claims)
+    if let Ok(Some(record)) = get_transcript_by_url(url) {
+        return Some(record.path);
+    }
+
     // Also try checking by constructing the expected path
-    let platform = get_platform_from_url(url);
+    let platform = platform::platform_name(url);
     let transcripts_dir = crate::config::transcripts_dir();
 
     // Search for the video ID in the transcripts directory
@@ -53,8 +33,22 @@ fn find_transcript_path(url: &str, video_id: &str) -> Option<String> {
     None
 }
 
-pub async fn run(url: &str) -> Result<()> {
-    let video_id = extract_video_id(url)
+/// The ID a URL's transcript is stored under: the URL's own on platforms with stable IDs,
+/// else yt-dlp's (Rumble slugs and Odysee claims aren't it), looked up through the metadata
+/// cache so repeated checks don't rerun yt-dlp
+pub fn stored_video_id(url: &str) -> Option<String> {
+    let stable_id = platform::detect(url)
+        .and_then(|platform| platform.extract_id(url).filter(|id| platform.canonical_url(id).is_some()));
+    stable_id.or_else(|| extract_metadata(url).ok().map(|metadata| metadata.id))
+}
+
+pub async fn run(url: &str, backend: Backend) -> Result<()> {
+    let lookup = {
+        let url = url.to_string();
+        tokio::task::spawn_blocking(move || stored_video_id(&url)).await.ok().flatten()
+    };
+    let video_id = lookup
+        .or_else(|| platform::extract_id(url))
         .ok_or_else(|| Error::Config("Could not extract video ID from URL".to_string()))?;
 
     // Check if transcript already exists
@@ -65,7 +59,7 @@ pub async fn run(url: &str) -> Result<()> {
 
     // Transcript not found - transcribe it
     eprintln!("Transcript not found, transcribing...");
-    super::transcribe::run(url).await?;
+    super::transcribe::run(url, backend, None).await?;
 
     // Now find the path
     if let Some(path) = find_transcript_path(url, &video_id) {
@@ -0,0 +1,49 @@
+use regex::RegexBuilder;
+
+use crate::commands::read::resolve_path;
+use crate::error::{Error, Result};
+use crate::storage::get_transcript;
+use crate::transcriber::{format_timestamp, speaker_label};
+
+/// Print the utterances of one transcript that match `pattern`, with timestamps and speakers
+///
+/// The pattern is a regular expression unless `fixed` is set; matches are marked like
+/// `search` snippets.
+pub fn run(path_or_id: &str, pattern: &str, fixed: bool, ignore_case: bool) -> Result<()> {
+    let expression = if fixed { regex::escape(pattern) } else { pattern.to_string() };
+    let regex = RegexBuilder::new(&expression)
+        .case_insensitive(ignore_case)
+        .build()
+        .map_err(|e| Error::Config(format!("Invalid pattern: {}", e)))?;
+
+    let path = resolve_path(path_or_id)?;
+    let data = get_transcript(&path)?
+        .structured
+        .ok_or_else(|| Error::FileNotFound(format!("No transcript.json with timings at {}", path)))?;
+    if data.utterances.is_empty() {
+        return Err(Error::FileNotFound(format!("No utterances in the transcript at {}", path)));
+    }
+
+    let mut matches = 0;
+    for utterance in &data.utterances {
+        let text = utterance.text.trim();
+        if !regex.is_match(text) {
+            continue;
+        }
+        matches += 1;
+        println!(
+            "[{}] {}: {}",
+            format_timestamp(utterance.start),
+            speaker_label(&utterance.speaker),
+            regex.replace_all(text, ">>> $0 <<<")
+        );
+    }
+
+    if matches == 0 {
+        eprintln!("No utterances match '{}'.", pattern);
+    } else {
+        eprintln!("\n{} matching utterance(s).", matches);
+    }
+
+    Ok(())
+}
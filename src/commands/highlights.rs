@@ -0,0 +1,43 @@
+use crate::commands::read::resolve_path;
+use crate::database::top_highlights;
+use crate::error::{Error, Result};
+use crate::storage::get_transcript;
+use crate::transcriber::format_highlights;
+
+/// Print the key phrases of one transcript, or the most widespread ones across the library
+///
+/// Key phrases only exist for transcripts made with `transcribe --highlights`.
+pub fn run(path_or_id: Option<&str>, channel: Option<&str>, limit: i32) -> Result<()> {
+    let Some(path_or_id) = path_or_id else {
+        return library(channel, limit);
+    };
+
+    let path = resolve_path(path_or_id)?;
+    let data = get_transcript(&path)?
+        .structured
+        .ok_or_else(|| Error::FileNotFound(format!("No transcript.json at {}", path)))?;
+    if data.highlights.is_empty() {
+        println!("No key phrases in this transcript (transcribe it with --highlights).");
+        return Ok(());
+    }
+
+    println!("{}", format_highlights(&data));
+    Ok(())
+}
+
+fn library(channel: Option<&str>, limit: i32) -> Result<()> {
+    let phrases = top_highlights(limit, channel)?;
+    if phrases.is_empty() {
+        match channel {
+            Some(channel) => println!("No key phrases from channels matching '{}'.", channel),
+            None => println!("No key phrases indexed yet (transcribe with --highlights, or run `yt-cli reindex`)."),
+        }
+        return Ok(());
+    }
+
+    println!("{:<40} {:>11} {:>8}", "Phrase", "Transcripts", "Mentions");
+    for phrase in phrases {
+        println!("{:<40} {:>11} {:>8}", phrase.text, phrase.transcripts, phrase.mentions);
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use crate::commands::undo::UndoAction;
+use crate::config::read_only;
+use crate::database::{log_activity, recent_activity};
+use crate::error::Result;
+use crate::format;
+
+/// Log a command outcome to the activity table; logging problems only warn
+pub fn record(action: &str, subject: Option<&str>, detail: Option<&str>, ok: bool) {
+    log(action, subject, detail, ok, None);
+}
+
+/// Log a successful destructive operation together with how `yt-cli undo` can reverse it
+pub fn record_undoable(action: &str, subject: Option<&str>, detail: Option<&str>, undo: &UndoAction) {
+    match serde_json::to_string(undo) {
+        Ok(json) => log(action, subject, detail, true, Some(&json)),
+        Err(e) => eprintln!("Warning: could not record activity: {}", e),
+    }
+}
+
+/// Log a successful destructive operation that `yt-cli undo` can't reverse, so undo refuses
+/// instead of reversing an older operation
+pub fn record_irreversible(action: &str, subject: Option<&str>, detail: Option<&str>) {
+    record_undoable(action, subject, detail, &UndoAction::Irreversible);
+}
+
+fn log(action: &str, subject: Option<&str>, detail: Option<&str>, ok: bool, undo: Option<&str>) {
+    if read_only() {
+        return;
+    }
+    if let Err(e) = log_activity(action, subject, detail, ok, undo) {
+        eprintln!("Warning: could not record activity: {}", e);
+    }
+}
+
+pub fn run(limit: i32, action: Option<&str>, failed_only: bool) -> Result<()> {
+    let entries = recent_activity(limit, action, failed_only)?;
+
+    if entries.is_empty() {
+        println!("No activity recorded yet.");
+        return Ok(());
+    }
+
+    for entry in entries.iter().rev() {
+        let mut line = format!(
+            "{} {} {:<18}",
+            format::timestamp(&entry.created_at),
+            if entry.ok { " " } else { "!" },
+            entry.action
+        );
+        if let Some(subject) = &entry.subject {
+            line.push_str(&format!(" {}", subject));
+        }
+        if let Some(detail) = &entry.detail {
+            line.push_str(&format!(" - {}", detail));
+        }
+        println!("{}", line.trim_end());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use crate::commands::history;
+use crate::commands::reindex::find_video_on_disk;
+use crate::commands::transcribe::save_and_index;
+use crate::config::{ensure_directories, ensure_writable};
+use crate::downloader::VideoMetadata;
+use crate::error::{Error, Result};
+use crate::platform;
+use crate::storage::sanitize_filename;
+use crate::transcriber::{parse_import, IMPORT_EXTENSIONS};
+
+/// Platform name for imports that can't be traced to a video
+const IMPORTED_PLATFORM: &str = "imported";
+/// Channel for imports when `--channel` isn't given
+const DEFAULT_CHANNEL: &str = "Imported";
+/// Transcript IDs of imported transcripts start with this, followed by the video ID
+const IMPORT_ID_PREFIX: &str = "import-";
+
+/// Add transcripts made by other tools (whisperX, yt-whisper, Descript) to the library
+///
+/// Each path is a transcript file or a folder of them. Files of one recording share a name
+/// (`talk.json`, `talk.srt`, ...); the richest format is imported. Names ending in
+/// `[VIDEO_ID]`, as yt-dlp writes them, are filed under that YouTube video.
+pub fn run(paths: &[String], channel: Option<&str>, force: bool) -> Result<()> {
+    ensure_writable("import transcripts")?;
+    ensure_directories()?;
+
+    let mut recordings: BTreeMap<PathBuf, PathBuf> = BTreeMap::new();
+    for path in paths.iter().map(PathBuf::from) {
+        if path.is_dir() {
+            for entry in fs::read_dir(&path)? {
+                add_candidate(&mut recordings, entry?.path());
+            }
+        } else if path.is_file() {
+            recordings.insert(path.with_extension(""), path);
+        } else {
+            return Err(Error::FileNotFound(path.display().to_string()));
+        }
+    }
+    if recordings.is_empty() {
+        return Err(Error::FileNotFound(format!(
+            "No transcripts found (expected .{} files)",
+            IMPORT_EXTENSIONS.join(", .")
+        )));
+    }
+
+    let mut imported = 0;
+    let mut failed = 0;
+    for file in recordings.values() {
+        match import_file(file, channel, force) {
+            Ok(storage_path) => {
+                imported += 1;
+                println!("Imported: {} -> {}", file.display(), storage_path.display());
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("Skipped {}: {}", file.display(), e);
+            }
+        }
+    }
+
+    if failed > 0 {
+        println!("\nImported {} transcript(s), {} failed.", imported, failed);
+    } else {
+        println!("\nImported {} transcript(s).", imported);
+    }
+    history::record("import", None, Some(&format!("{} transcript(s)", imported)), failed == 0);
+    Ok(())
+}
+
+/// Keep `path` as the file for its recording if no better format was seen yet
+fn add_candidate(recordings: &mut BTreeMap<PathBuf, PathBuf>, path: PathBuf) {
+    let Some(priority) = format_priority(&path) else {
+        return;
+    };
+    let stem = path.with_extension("");
+    match recordings.get(&stem) {
+        Some(existing) if format_priority(existing).is_some_and(|p| p <= priority) => {}
+        _ => {
+            recordings.insert(stem, path);
+        }
+    }
+}
+
+fn format_priority(path: &Path) -> Option<usize> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    IMPORT_EXTENSIONS.iter().position(|e| *e == extension)
+}
+
+fn import_file(file: &Path, channel: Option<&str>, force: bool) -> Result<PathBuf> {
+    let (metadata, platform) = import_metadata(file, channel)?;
+    if !force && let Some(existing) = find_video_on_disk(&metadata.id) {
+        return Err(Error::Config(format!(
+            "{} is already in the library at {} (use --force to replace it)",
+            metadata.id,
+            existing.display()
+        )));
+    }
+    let mut transcript_data = parse_import(file, &format!("{}{}", IMPORT_ID_PREFIX, metadata.id))?;
+
+    let metadata = VideoMetadata {
+        duration: transcript_data.audio_duration,
+        ..metadata
+    };
+    save_and_index(&metadata.url, platform, &metadata, &mut transcript_data, None)
+}
+
+/// Title, ID and URL from a file name: `Title [dQw4w9WgXcQ]` is a YouTube video, anything
+/// else gets an ID made from the name and the file's location as its URL
+fn import_metadata(file: &Path, channel: Option<&str>) -> Result<(VideoMetadata, &'static str)> {
+    let stem = file.file_stem().unwrap_or_default().to_string_lossy();
+    let youtube_id = Regex::new(r"^(.*?)\s*\[([A-Za-z0-9_-]{11})\]$").unwrap();
+    let youtube = platform::by_name("youtube");
+
+    let (title, id, url, platform) = match (youtube_id.captures(&stem), youtube) {
+        (Some(captures), Some(youtube)) => {
+            let id = captures[2].to_string();
+            let url = youtube.canonical_url(&id).unwrap_or_default();
+            (captures[1].to_string(), id, url, youtube.name())
+        }
+        _ => {
+            let url = format!("file://{}", fs::canonicalize(file)?.display());
+            (stem.to_string(), sanitize_filename(&stem, 60), url, IMPORTED_PLATFORM)
+        }
+    };
+
+    let metadata = VideoMetadata {
+        id,
+        title,
+        channel: channel.unwrap_or(DEFAULT_CHANNEL).to_string(),
+        uploader: None,
+        uploader_id: None,
+        duration: None,
+        upload_date: None,
+        description: None,
+        view_count: None,
+        like_count: None,
+        thumbnail: None,
+        url,
+        webpage_url: None,
+        extractor: None,
+        chapters: Vec::new(),
+        series: None,
+        episode: None,
+        episode_number: None,
+        season_number: None,
+        transcribed_by: None,
+        language: None,
+    };
+    Ok((metadata, platform))
+}
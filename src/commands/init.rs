@@ -1,14 +1,32 @@
 use std::io::{self, Write};
 
-use crate::config::{data_dir, ensure_directories, env_file_path};
-use crate::error::Result;
+use crate::commands::history;
+use crate::config::{
+    data_dir, database_key, ensure_directories, ensure_writable, env_file_path, API_KEY_SECRET,
+};
+use crate::database::{encryption_supported, get_secret, set_secret};
+use crate::error::{Error, Result};
+use crate::transcriber::AssemblyAI;
 
-pub fn run(api_key: Option<String>, force: bool) -> Result<()> {
+pub async fn run(
+    api_key: Option<String>,
+    force: bool,
+    encrypt: bool,
+    skip_verify: bool,
+) -> Result<()> {
+    ensure_writable("write configuration")?;
     ensure_directories()?;
 
     let env_file = env_file_path();
 
-    if env_file.exists() && !force {
+    if encrypt {
+        check_encryption()?;
+        if get_secret(API_KEY_SECRET)?.is_some() && !force {
+            println!("API key already stored in the encrypted database.");
+            println!("Use --force to overwrite.");
+            return Ok(());
+        }
+    } else if env_file.exists() && !force {
         println!("Config already exists at {}", env_file.display());
         println!("Use --force to overwrite.");
         return Ok(());
@@ -25,14 +43,92 @@ pub fn run(api_key: Option<String>, force: bool) -> Result<()> {
     };
 
     if api_key.is_empty() {
-        eprintln!("Error: API key is required.");
-        std::process::exit(1);
+        return Err(Error::Config("API key is required".to_string()));
     }
 
-    std::fs::write(&env_file, format!("ASSEMBLYAI_API_KEY={}\n", api_key))?;
+    if !skip_verify {
+        verify_key(&api_key).await?;
+    }
 
-    println!("Config saved to {}", env_file.display());
+    if encrypt {
+        set_secret(API_KEY_SECRET, &api_key)?;
+        remove_plaintext_key()?;
+        println!("API key stored in the encrypted database.");
+        println!(
+            "Keep YT_TRANSCRIBE_DB_KEY out of {} so the key stays protected.",
+            env_file.display()
+        );
+    } else {
+        std::fs::write(&env_file, format!("ASSEMBLYAI_API_KEY={}\n", api_key))?;
+        println!("Config saved to {}", env_file.display());
+    }
     println!("Data directory: {}", data_dir().display());
+    history::record("init", None, Some(if encrypt { "API key stored encrypted" } else { "API key saved" }), true);
+
+    Ok(())
+}
+
+/// Reject keys AssemblyAI doesn't accept; only warn when the API can't be reached
+pub async fn verify_key(api_key: &str) -> Result<()> {
+    print!("Verifying API key with AssemblyAI... ");
+    io::stdout().flush()?;
+
+    match AssemblyAI::with_key(api_key.to_string())?.verify_key().await {
+        Ok(true) => {
+            println!("valid.");
+            Ok(())
+        }
+        Ok(false) => {
+            println!("rejected.");
+            Err(Error::Config(
+                "AssemblyAI rejected this API key; check it at https://www.assemblyai.com/app"
+                    .to_string(),
+            ))
+        }
+        Err(e) => {
+            println!("skipped.");
+            eprintln!("Warning: could not verify the key ({}); saving it anyway.", e);
+            Ok(())
+        }
+    }
+}
+
+fn check_encryption() -> Result<()> {
+    if !encryption_supported() {
+        return Err(Error::Config(
+            "--encrypt needs SQLCipher support (rebuild with `cargo install --features sqlcipher`)"
+                .to_string(),
+        ));
+    }
+    if database_key().is_none() {
+        return Err(Error::Config(
+            "--encrypt needs a database passphrase in YT_TRANSCRIBE_DB_KEY".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Drop a previously saved plaintext API key from .env, keeping other settings
+fn remove_plaintext_key() -> Result<()> {
+    let env_file = env_file_path();
+    if !env_file.exists() {
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(&env_file)?;
+    let kept: Vec<&str> = content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("ASSEMBLYAI_API_KEY="))
+        .collect();
+
+    if kept.len() != content.lines().count() {
+        if kept.iter().all(|line| line.trim().is_empty()) {
+            std::fs::remove_file(&env_file)?;
+        } else {
+            std::fs::write(&env_file, kept.join("\n") + "\n")?;
+        }
+        println!("Removed plaintext API key from {}", env_file.display());
+    }
 
     Ok(())
 }
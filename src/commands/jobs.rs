@@ -0,0 +1,171 @@
+use std::fs;
+use std::path::Path;
+
+use crate::commands::history;
+use crate::config::{downloads_dir, ensure_writable};
+use crate::database::{list_inflight, mark_inflight_cancelled, remove_inflight, InflightTranscription};
+use crate::downloader::VideoMetadata;
+use crate::error::Result;
+use crate::format;
+use crate::transcriber::{AssemblyAI, Backend, JobStatus, TranscriptListItem};
+
+/// Jobs fetched from the account history to work out queue positions
+const HISTORY_LIMIT: u32 = 200;
+
+/// Ask AssemblyAI how the outstanding jobs (the ones `yt-cli resume` would finish) are doing
+///
+/// With `job_id`, only that job is checked, recorded or not. The API reports no global queue
+/// position, so queued jobs are placed among this account's own queued jobs.
+pub async fn status(job_id: Option<&str>) -> Result<()> {
+    let jobs: Vec<InflightTranscription> = list_inflight()?
+        .into_iter()
+        .filter(|job| job_id.is_none_or(|id| job.transcript_id == id))
+        .collect();
+    if jobs.is_empty() && job_id.is_none() {
+        println!("No outstanding transcription jobs.");
+        return Ok(());
+    }
+
+    let client = AssemblyAI::new()?;
+    let mut statuses = Vec::new();
+    match job_id {
+        Some(id) if jobs.is_empty() => statuses.push((None, client.job_status(id).await)),
+        _ => {
+            for job in &jobs {
+                if job.backend != Backend::AssemblyAI.id() {
+                    println!("Job {}: {} answers synchronously, nothing to check.", job.transcript_id, job.backend);
+                    continue;
+                }
+                statuses.push((Some(job), client.job_status(&job.transcript_id).await));
+            }
+        }
+    }
+
+    let queue = if statuses.iter().any(|(_, s)| s.as_ref().is_ok_and(|s| s.status == "queued")) {
+        queued_jobs(&client).await
+    } else {
+        Vec::new()
+    };
+
+    for (i, (job, status)) in statuses.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        let id = job.map(|j| j.transcript_id.as_str()).or(job_id).unwrap_or_default();
+        print_job(id, *job, status, &queue);
+    }
+
+    let finished = statuses
+        .iter()
+        .filter(|(job, s)| job.is_some() && s.as_ref().is_ok_and(|s| s.status == "completed" || s.status == "error"))
+        .count();
+    if finished > 0 {
+        println!("\n{} job(s) finished; run `yt-cli resume` to save or clear them.", finished);
+    }
+    Ok(())
+}
+
+/// Stop tracking a job so `yt-cli resume` never saves it, deleting it from AssemblyAI first
+///
+/// AssemblyAI has no way to stop a job that is already running: deleting only works once it
+/// has finished, so a running job is still billed, but its transcript is thrown away. Until
+/// the delete succeeds the job stays recorded as cancelled, and `yt-cli resume` retries it.
+pub async fn cancel(job_id: &str) -> Result<()> {
+    ensure_writable("cancel jobs")?;
+
+    let job = list_inflight()?.into_iter().find(|job| job.transcript_id == job_id);
+    let is_assemblyai = job.as_ref().is_none_or(|job| job.backend == Backend::AssemblyAI.id());
+    let deleted = if is_assemblyai {
+        match AssemblyAI::new()?.delete_transcript(job_id).await {
+            Ok(()) => true,
+            Err(e) if job.is_some() => {
+                eprintln!("Warning: AssemblyAI did not delete job {} ({})", job_id, e);
+                eprintln!(
+                    "It may still be running and will be billed. yt-cli won't save it; `yt-cli resume` \
+                     (or `jobs cancel` again) retries the delete."
+                );
+                false
+            }
+            Err(e) => return Err(e),
+        }
+    } else {
+        false
+    };
+
+    let Some(job) = job else {
+        println!("Deleted job {} from AssemblyAI.", job_id);
+        history::record("jobs cancel", Some(job_id), None, true);
+        return Ok(());
+    };
+
+    if deleted || !is_assemblyai {
+        remove_inflight(job_id)?;
+    } else {
+        mark_inflight_cancelled(job_id)?;
+    }
+    // Audio downloaded for the job was only kept so `resume` could store it
+    if let Some(audio) = job.audio_path.as_deref().map(Path::new)
+        && audio.starts_with(downloads_dir())
+        && audio.exists()
+        && let Err(e) = fs::remove_file(audio)
+    {
+        eprintln!("Warning: could not remove {}: {}", audio.display(), e);
+    }
+
+    if deleted {
+        println!("Cancelled job {} ({}) and deleted it from AssemblyAI.", job_id, job.url);
+    } else {
+        println!("Cancelled job {} ({}).", job_id, job.url);
+    }
+    history::record("jobs cancel", Some(&job.url), Some(job_id), true);
+    Ok(())
+}
+
+fn print_job(id: &str, job: Option<&InflightTranscription>, status: &Result<JobStatus>, queue: &[String]) {
+    println!("Job {}", id);
+    if let Some(job) = job {
+        if let Ok(metadata) = serde_json::from_str::<VideoMetadata>(&job.metadata) {
+            println!("  Title:    {}", metadata.title);
+        }
+        println!("  URL:      {}", job.url);
+        println!("  Started:  {}", format::timestamp(&job.started_at));
+        if job.cancel_pending {
+            println!("  Cancelled; not yet deleted from AssemblyAI");
+        }
+    } else {
+        println!("  (not recorded by yt-cli)");
+    }
+
+    let status = match status {
+        Ok(status) => status,
+        Err(e) => {
+            println!("  Status:   unknown ({})", e);
+            return;
+        }
+    };
+    match queue.iter().position(|queued| *queued == status.id) {
+        Some(position) if status.status == "queued" => {
+            println!("  Status:   queued ({} of {} queued on this account)", position + 1, queue.len())
+        }
+        _ => println!("  Status:   {}", status.status),
+    }
+    if let Some(duration) = status.audio_duration {
+        println!("  Audio:    {}", format::duration(duration));
+    }
+    if let Some(error) = &status.error {
+        println!("  Error:    {}", error);
+    }
+}
+
+/// IDs of the account's queued jobs, oldest (next to start) first
+async fn queued_jobs(client: &AssemblyAI) -> Vec<String> {
+    let mut queued: Vec<TranscriptListItem> = match client.recent_transcripts(HISTORY_LIMIT).await {
+        Ok(jobs) => jobs.into_iter().filter(|j| j.status == "queued").collect(),
+        Err(e) => {
+            eprintln!("Warning: could not fetch the account's queue: {}", e);
+            return Vec::new();
+        }
+    };
+    queued.sort_by(|a, b| a.created.cmp(&b.created));
+    queued.into_iter().map(|j| j.id).collect()
+}
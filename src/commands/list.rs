@@ -1,8 +1,52 @@
+use clap::ValueEnum;
+
+use crate::database::{incomplete_transcripts, TranscriptFilter};
 use crate::error::Result;
-use crate::storage::list_transcripts;
+use crate::format;
+use crate::storage::{list_transcripts, TranscriptInfo};
+use crate::transcriber::TranscriptSource;
+
+/// Orders for `list --sort`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListSort {
+    /// By show, then episode number (transcripts without one last)
+    Episode,
+    /// Newest upload first
+    Date,
+}
 
-pub fn run(platform: Option<&str>, channel: Option<&str>, handle: Option<&str>) -> Result<()> {
-    let transcripts = list_transcripts(platform, channel, handle)?;
+pub fn run(
+    platform: Option<&str>,
+    channel: Option<&str>,
+    handle: Option<&str>,
+    series: Option<&str>,
+    sort: Option<ListSort>,
+    incomplete: bool,
+    filter: &TranscriptFilter,
+) -> Result<()> {
+    let mut transcripts = list_transcripts(platform, channel, handle, filter)?;
+    let missing_tails = incomplete_transcripts()?;
+    if incomplete {
+        transcripts.retain(|t| missing_tails.contains_key(&t.path));
+    }
+
+    if let Some(series) = series {
+        let series = series.trim();
+        transcripts.retain(|t| t.series.as_deref().is_some_and(|s| s.eq_ignore_ascii_case(series)));
+    }
+    match sort {
+        Some(ListSort::Episode) => transcripts.sort_by(|a, b| {
+            let key = |t: &TranscriptInfo| {
+                (t.episode_number.is_none(), t.series.is_none(), t.series.as_deref().map(str::to_lowercase))
+            };
+            key(a)
+                .cmp(&key(b))
+                .then(a.episode_number.cmp(&b.episode_number))
+                .then(a.upload_date.cmp(&b.upload_date))
+        }),
+        Some(ListSort::Date) => transcripts.sort_by(|a, b| b.upload_date.cmp(&a.upload_date)),
+        None => {}
+    }
 
     if transcripts.is_empty() {
         println!("No transcripts found.");
@@ -25,12 +69,24 @@ pub fn run(platform: Option<&str>, channel: Option<&str>, handle: Option<&str>)
 
         let mut line = format!("- {}/{}/{}", t.platform, channel_display, t.title);
         if let Some(duration) = t.duration {
-            let mins = duration / 60;
-            let secs = duration % 60;
-            line.push_str(&format!(" ({}m {}s)", mins, secs));
+            line.push_str(&format!(" ({})", format::duration(duration)));
         }
         println!("{}", line);
         println!("  Path: {}", t.path);
+        match (&t.series, t.episode_number) {
+            (Some(series), Some(number)) => println!("  Episode: {} #{}", series, number),
+            (None, Some(number)) => println!("  Episode: #{}", number),
+            _ => {}
+        }
+        if t.source.as_deref() == Some(TranscriptSource::Captions.id()) {
+            println!("  Source: captions");
+        }
+        if let Some(user) = &t.transcribed_by {
+            println!("  Transcribed by: {}", user);
+        }
+        if let Some(missing) = missing_tails.get(&t.path) {
+            println!("  Incomplete: the last {} of the audio isn't transcribed", format::duration(*missing));
+        }
     }
 
     Ok(())
@@ -1,10 +1,51 @@
+pub mod account;
+pub mod align;
+pub mod ask;
+pub mod archive;
+pub mod attach;
+pub mod backup;
 pub mod channel;
+pub mod channel_transcribe;
+pub mod clean;
+pub mod clip;
+pub mod config_bundle;
+pub mod corpus;
+pub mod coverage;
+pub mod daemon;
+pub mod delete;
+pub mod describe;
+pub mod embed;
+pub mod entities;
+pub mod export;
 pub mod get;
+pub mod grep;
+pub mod highlights;
+pub mod import;
+pub mod history;
 pub mod init;
+pub mod jobs;
 pub mod list;
+pub mod notify_test;
+pub mod prune_audio;
+pub mod publish;
+pub mod queue;
 pub mod read;
+pub mod recent;
 pub mod reindex;
+pub mod resume;
+pub mod retranscribe;
+pub mod script;
 pub mod search;
+pub mod self_update;
+pub mod setup;
+pub mod speakers;
 pub mod stats;
+pub mod subscriptions;
+pub mod sync_remote;
+pub mod tag;
 pub mod transcribe;
+pub mod transcribe_url;
+pub mod undo;
+pub mod usage;
+pub mod vault;
 pub mod yt_search;
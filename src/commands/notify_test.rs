@@ -0,0 +1,39 @@
+use crate::config::NotifyEvent;
+use crate::error::{Error, Result};
+use crate::notify::{routes, Notification};
+
+/// Send a test notification to each `[[notify]]` notifier, or with `event` to the ones
+/// routed that event, reporting how each went
+pub fn run(event: Option<NotifyEvent>) -> Result<()> {
+    let routes: Vec<_> = routes().iter().filter(|route| event.is_none_or(|event| route.wants(event))).collect();
+    if routes.is_empty() {
+        println!("No notifiers configured{}; add [[notify]] tables to config.toml.", match event {
+            Some(event) => format!(" for {}", event.id()),
+            None => String::new(),
+        });
+        return Ok(());
+    }
+
+    let message = "If you can read this, yt-cli notifications work.";
+    let notification = Notification {
+        event: event.unwrap_or(NotifyEvent::Completed),
+        title: "Test notification",
+        message,
+        url: None,
+    };
+    let mut failed = 0;
+    for route in &routes {
+        match route.notifier.send(&notification) {
+            Ok(()) => println!("{}: sent", route.notifier.name()),
+            Err(e) => {
+                println!("{}: {}", route.notifier.name(), e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(Error::Notify(format!("{} of {} notifier(s) failed", failed, routes.len())));
+    }
+    Ok(())
+}
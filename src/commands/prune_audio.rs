@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::commands::history;
+use crate::config::{ensure_writable, settings, transcripts_dir};
+use crate::error::{Error, Result};
+use crate::format;
+
+/// Name of the audio file kept in a transcript directory
+const AUDIO_FILE: &str = "audio.mp3";
+
+/// Delete the stored audio.mp3 of transcripts older than `older_than` (e.g. `90d`, `12w`),
+/// keeping the transcripts themselves
+///
+/// Without `older_than`, each platform's `[platforms.<name>] prune_audio_after` applies and
+/// platforms without one are left alone. Age is taken from the audio file, i.e. when it
+/// was transcribed. Audio hard-linked to another transcript's only frees space once every
+/// copy is gone.
+pub fn run(older_than: Option<&str>, dry_run: bool) -> Result<()> {
+    if !dry_run {
+        ensure_writable("prune audio")?;
+    }
+
+    let age = older_than.map(parse_age).transpose()?;
+    let mut platform_ages = HashMap::new();
+    for (platform, config) in &settings().platforms {
+        if let Some(after) = &config.prune_audio_after {
+            platform_ages.insert(platform.to_lowercase(), parse_age(after)?);
+        }
+    }
+    if age.is_none() && platform_ages.is_empty() {
+        return Err(Error::Config(
+            "Pass --older-than, or set prune_audio_after under [platforms.<name>] in config.toml".to_string(),
+        ));
+    }
+
+    let root = transcripts_dir();
+    let mut audio = Vec::new();
+    find_audio(&root, &mut audio);
+
+    let now = SystemTime::now();
+    let stale: Vec<(PathBuf, u64)> = audio
+        .into_iter()
+        .filter_map(|path| {
+            let platform = path.strip_prefix(&root).ok()?.components().next()?.as_os_str().to_string_lossy();
+            let age = age.or_else(|| platform_ages.get(&platform.to_lowercase()).copied())?;
+            let meta = fs::metadata(&path).ok()?;
+            meta.modified().is_ok_and(|modified| modified < now - age).then_some((path, meta.len()))
+        })
+        .collect();
+
+    if stale.is_empty() {
+        println!("No stored audio old enough to prune.");
+        return Ok(());
+    }
+
+    let total: u64 = stale.iter().map(|(_, size)| size).sum();
+    if dry_run {
+        for (path, size) in &stale {
+            println!("Would remove {} ({})", path.display(), format::size(*size));
+        }
+        println!("\n{} audio file(s), {}; nothing removed.", stale.len(), format::size(total));
+        return Ok(());
+    }
+
+    let mut removed = 0;
+    let mut freed = 0;
+    for (path, size) in &stale {
+        match fs::remove_file(path) {
+            Ok(()) => {
+                removed += 1;
+                freed += size;
+            }
+            Err(e) => eprintln!("Could not remove {}: {}", path.display(), e),
+        }
+    }
+
+    println!("Removed {} audio file(s), {}.", removed, format::size(freed));
+    let detail = format!("{} file(s), {}", removed, format::size(freed));
+    if removed > 0 {
+        history::record_irreversible("prune-audio", older_than, Some(&detail));
+    } else {
+        history::record("prune-audio", older_than, Some(&detail), true);
+    }
+    Ok(())
+}
+
+/// `90d`, `12w`, `36h` or a plain number of days
+fn parse_age(value: &str) -> Result<Duration> {
+    let value = value.trim();
+    let (number, unit_seconds) = match value.char_indices().last() {
+        Some((i, 'h')) => (&value[..i], 3600),
+        Some((i, 'd')) => (&value[..i], 86_400),
+        Some((i, 'w')) => (&value[..i], 7 * 86_400),
+        _ => (value, 86_400),
+    };
+    number
+        .trim()
+        .parse::<u64>()
+        .map(|n| Duration::from_secs(n * unit_seconds))
+        .map_err(|_| Error::Config(format!("Invalid age '{}': expected e.g. 90d, 12w or 36h", value)))
+}
+
+/// Every stored audio file under `dir`
+fn find_audio(dir: &Path, audio: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            find_audio(&path, audio);
+        } else if entry.file_name() == AUDIO_FILE {
+            audio.push(path);
+        }
+    }
+}
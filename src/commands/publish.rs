@@ -0,0 +1,133 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::history;
+use crate::commands::vault::{last_modified, manifest_key, render_note};
+use crate::database::{list_all_transcripts, TranscriptFilter, TranscriptRecord};
+use crate::error::Result;
+use crate::storage::sanitize_filename;
+
+/// Records what each publish wrote, so the next one can skip or remove it
+const MANIFEST_FILE: &str = ".yt-cli-publish.json";
+
+/// Files written by earlier publishes, keyed by transcript directory (relative to the library)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    published: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    /// `<name>.md` and `<name>.json` in the output directory
+    name: String,
+    /// Newest modification time of the transcript's files when it was published
+    modified: u64,
+}
+
+/// Export the transcripts tagged `tag` into a directory a blog or static site builds from
+///
+/// Each one becomes `<video_id>.md` (markdown with YAML frontmatter) and `<video_id>.json`
+/// (its metadata). Transcripts unchanged since the last publish are skipped, and the files
+/// of ones that lost the tag or were deleted are removed.
+pub fn run(tag: &str, out: &str) -> Result<()> {
+    let filter = TranscriptFilter { tag: Some(tag.to_string()), ..Default::default() };
+    // A negative LIMIT is no limit in SQLite
+    let transcripts = list_all_transcripts(None, None, None, &filter, -1)?;
+    if transcripts.is_empty() {
+        eprintln!("No transcripts are tagged '{}' (add some with `yt-cli tag VIDEO_ID {}`).", tag, tag);
+    }
+
+    let out = PathBuf::from(out);
+    fs::create_dir_all(&out)?;
+    let manifest_path = out.join(MANIFEST_FILE);
+    let mut manifest: Manifest = fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    // File names in use (lowercased for case-insensitive filesystems) and their transcripts
+    let mut taken: HashMap<String, String> = manifest
+        .published
+        .iter()
+        .map(|(key, entry)| (entry.name.to_lowercase(), key.clone()))
+        .collect();
+
+    let mut current = HashSet::new();
+    let (mut written, mut unchanged) = (0, 0);
+    for t in &transcripts {
+        let key = manifest_key(&t.path);
+        current.insert(key.clone());
+        let name = file_name(t, &key, &taken);
+        taken.insert(name.to_lowercase(), key.clone());
+
+        let modified = last_modified(Path::new(&t.path));
+        let previous = manifest.published.get(&key);
+        if previous.is_some_and(|p| p.name == name && p.modified >= modified)
+            && out.join(format!("{}.md", name)).exists()
+        {
+            unchanged += 1;
+            continue;
+        }
+
+        let content = match render_note(t) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Skipping {}: {}", t.path, e);
+                continue;
+            }
+        };
+        fs::write(out.join(format!("{}.md", name)), content)?;
+        let metadata = Path::new(&t.path).join("metadata.json");
+        if metadata.exists() {
+            fs::copy(&metadata, out.join(format!("{}.json", name)))?;
+        }
+
+        if let Some(previous) = previous.filter(|p| p.name != name) {
+            remove_published(&out, &previous.name);
+        }
+        manifest.published.insert(key, ManifestEntry { name, modified });
+        written += 1;
+    }
+
+    // Untagged or deleted since the last publish
+    let stale: Vec<String> = manifest
+        .published
+        .keys()
+        .filter(|key| !current.contains(*key))
+        .cloned()
+        .collect();
+    for key in &stale {
+        if let Some(entry) = manifest.published.remove(key) {
+            remove_published(&out, &entry.name);
+        }
+    }
+
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+    println!(
+        "Published {} transcript(s) to {} ({} unchanged, {} removed).",
+        written,
+        out.display(),
+        unchanged,
+        stale.len()
+    );
+    history::record("publish", None, Some(tag), true);
+    Ok(())
+}
+
+/// The video ID, prefixed with the platform when another transcript already has it
+fn file_name(t: &TranscriptRecord, key: &str, taken: &HashMap<String, String>) -> String {
+    let plain = sanitize_filename(&t.video_id, 100);
+    match taken.get(&plain.to_lowercase()) {
+        Some(owner) if owner != key => format!("{}-{}", sanitize_filename(&t.platform, 20), plain),
+        _ => plain,
+    }
+}
+
+fn remove_published(out: &Path, name: &str) {
+    for extension in ["md", "json"] {
+        let _ = fs::remove_file(out.join(format!("{}.{}", name, extension)));
+    }
+}
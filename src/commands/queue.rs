@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use clap::ValueEnum;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::commands::daemon::process_alive;
+use crate::commands::get::stored_video_id;
+use crate::commands::history;
+use crate::commands::transcribe::{self, Slot};
+use crate::config::ensure_writable;
+use crate::database::{
+    claim_next_job, enqueue_job, finish_job, get_transcript_by_id, get_transcript_by_url, list_jobs,
+    requeue_orphaned_jobs, retry_failed_jobs, Job,
+};
+use crate::error::{print_error, Error, Result};
+use crate::format;
+use crate::hooks;
+use crate::platform;
+use crate::transcriber::Backend;
+
+/// Queue URLs (or those listed in `from_file`) for a later `queue run`
+///
+/// URLs already archived or waiting in the queue are skipped.
+pub fn add(
+    urls: Vec<String>,
+    from_file: Option<&str>,
+    backend: Backend,
+    captions_lang: Option<&str>,
+) -> Result<()> {
+    ensure_writable("queue jobs")?;
+
+    let urls = match from_file {
+        Some(path) => transcribe::read_url_list(path)?,
+        None => urls,
+    };
+
+    let mut added = 0;
+    for url in &urls {
+        let url = &match hooks::rewrite_url(url) {
+            Ok(url) => platform::normalize_url(&url),
+            Err(e) => {
+                eprintln!("Skipping {}: {}", url, e);
+                continue;
+            }
+        };
+        if already_transcribed(url)? {
+            println!("Already transcribed: {}", url);
+            continue;
+        }
+        match enqueue_job(url, backend.id(), captions_lang)? {
+            Some(id) => {
+                println!("Queued #{}: {}", id, url);
+                added += 1;
+            }
+            None => println!("Already queued: {}", url),
+        }
+    }
+
+    println!("\n{} job(s) added. Run them with `yt-cli queue run`.", added);
+    if added > 0 {
+        history::record("queue-add", None, Some(&format!("{} job(s) queued", added)), true);
+    }
+
+    Ok(())
+}
+
+/// Whether `url` (normalized) was transcribed before: by URL, or by the ID it's stored
+/// under, so transcripts saved under another link style count too
+fn already_transcribed(url: &str) -> Result<bool> {
+    if get_transcript_by_url(url)?.is_some() {
+        return Ok(true);
+    }
+    match stored_video_id(url) {
+        Some(id) => Ok(get_transcript_by_id(&id)?.is_some_and(|t| t.platform == platform::platform_name(url))),
+        None => Ok(false),
+    }
+}
+
+/// Work through the queue in order, with up to `jobs` videos downloading and uploading at once
+///
+/// A job gives up its slot once its audio is submitted, so the next job is claimed while
+/// earlier ones are still being transcribed. Each job is attempted at most once per run;
+/// failed jobs are picked up again by later runs until they have been tried `max_attempts` times.
+/// Jobs left running by a `queue run` that no longer exists go back to the queue first.
+pub async fn run(limit: Option<usize>, max_attempts: i32, jobs: usize) -> Result<()> {
+    ensure_writable("run jobs")?;
+
+    let requeued = requeue_orphaned_jobs(process_alive)?;
+    if requeued > 0 {
+        eprintln!("Requeued {} job(s) from an interrupted run.", requeued);
+    }
+
+    let slots = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut tasks = JoinSet::new();
+    // Which job each task runs, to fail one that panicked
+    let mut task_jobs = HashMap::new();
+    // Later claims only look past the highest job ID claimed so far
+    let (mut last_id, mut claimed) = (0, 0);
+    let mut claim_error = None;
+    while limit.is_none_or(|limit| claimed < limit) {
+        let slot = slots.clone().acquire_owned().await.ok();
+        let job = match claim_next_job(last_id, max_attempts) {
+            Ok(Some(job)) => job,
+            Ok(None) => break,
+            Err(e) => {
+                // Let claimed jobs finish rather than abandoning them mid-transcription
+                claim_error = Some(e);
+                break;
+            }
+        };
+        last_id = job.id;
+        claimed += 1;
+        let job_id = job.id;
+        let task = tasks.spawn(run_job(job, max_attempts, slot));
+        task_jobs.insert(task.id(), job_id);
+    }
+
+    // Every job is waited for, so one that fails to record its outcome or panics doesn't
+    // abandon the others mid-transcription
+    let (mut done, mut failed) = (0, 0);
+    let mut run_error = claim_error;
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(Ok(true)) => done += 1,
+            Ok(Ok(false)) => failed += 1,
+            Ok(Err(e)) => {
+                failed += 1;
+                run_error.get_or_insert(e);
+            }
+            Err(e) => {
+                failed += 1;
+                let Some(job_id) = task_jobs.remove(&e.id()) else {
+                    continue;
+                };
+                let error = Error::Transcription(format!("job panicked: {}", e));
+                eprintln!("Failed: job #{}", job_id);
+                print_error(&error);
+                if let Err(e) = finish_job(job_id, Some(&error.to_string())) {
+                    eprintln!("Warning: could not update job #{}: {}", job_id, e);
+                }
+            }
+        }
+    }
+    if let Some(e) = run_error {
+        return Err(e);
+    }
+
+    if done + failed == 0 {
+        println!("No jobs to run (see `yt-cli queue status`).");
+        return Ok(());
+    }
+
+    let outcome = format!("{} done, {} failed", done, failed);
+    println!("\nQueue run complete: {}.", outcome);
+    history::record("queue-run", None, Some(&outcome), failed == 0);
+
+    if failed > 0 {
+        return Err(Error::Transcription(format!(
+            "{} of {} job(s) failed (see `yt-cli queue status`)",
+            failed,
+            done + failed
+        )));
+    }
+
+    Ok(())
+}
+
+/// Run one claimed job and record the outcome: whether it succeeded
+async fn run_job(job: Job, max_attempts: i32, slot: Slot) -> Result<bool> {
+    eprintln!("\n[job #{}, attempt {}/{}] {}", job.id, job.attempts, max_attempts, job.url);
+    let result = match Backend::from_str(&job.backend, true) {
+        Ok(backend) => {
+            // `add` already rewrote the URL
+            transcribe::run_queued(&job.url, backend, job.captions_lang.as_deref(), slot).await
+        }
+        Err(_) => Err(Error::Config(format!("Unknown backend '{}'", job.backend))),
+    };
+
+    match result {
+        Ok(()) => {
+            finish_job(job.id, None)?;
+            Ok(true)
+        }
+        Err(e) => {
+            eprintln!("Failed: job #{}", job.id);
+            print_error(&e);
+            finish_job(job.id, Some(&e.to_string()))?;
+            Ok(false)
+        }
+    }
+}
+
+/// Show queue counts and every job that isn't done (all jobs with `all`)
+pub fn status(all: bool) -> Result<()> {
+    let jobs = list_jobs(None)?;
+    if jobs.is_empty() {
+        println!("Queue is empty. Add jobs with `yt-cli queue add <url>`.");
+        return Ok(());
+    }
+
+    let count = |status: &str| jobs.iter().filter(|j| j.status == status).count();
+    println!(
+        "{} pending, {} running, {} failed, {} done\n",
+        count("pending"),
+        count("running"),
+        count("failed"),
+        count("done")
+    );
+
+    for job in jobs.iter().filter(|j| all || j.status != "done") {
+        print_job(job);
+    }
+
+    Ok(())
+}
+
+/// Reset failed jobs so the next `queue run` tries them again
+pub fn retry() -> Result<()> {
+    ensure_writable("retry jobs")?;
+
+    let count = retry_failed_jobs()?;
+    println!("{} failed job(s) requeued.", count);
+
+    Ok(())
+}
+
+fn print_job(job: &Job) {
+    println!("#{} [{}] {}", job.id, job.status, job.url);
+    let mut details = vec![format!("backend: {}", job.backend)];
+    if let Some(lang) = &job.captions_lang {
+        details.push(format!("captions: {}", lang));
+    }
+    if job.attempts > 0 {
+        details.push(format!("attempts: {}", job.attempts));
+    }
+    details.push(format!("updated: {}", format::timestamp(&job.updated_at)));
+    println!("  {}", details.join(", "));
+    if let Some(error) = &job.error {
+        println!("  Error: {}", error);
+    }
+}
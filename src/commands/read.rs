@@ -1,10 +1,20 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+
+use serde::de::{self, DeserializeOwned, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Serialize;
+
 use crate::commands::reindex::{find_video_on_disk, index_video_dir};
 use crate::database::get_transcript_by_id;
 use crate::error::{Error, Result};
-use crate::storage::get_transcript;
+use crate::storage::transcript_files;
+use crate::transcriber::{Chapter, Entity, Highlight, Utterance, Word};
 
 /// Resolve a video ID or path to an actual transcript path
-fn resolve_path(path_or_id: &str) -> Result<String> {
+pub fn resolve_path(path_or_id: &str) -> Result<String> {
     // First, check if it's already a valid path
     let as_path = std::path::Path::new(path_or_id);
     if as_path.exists() {
@@ -29,21 +39,129 @@ fn resolve_path(path_or_id: &str) -> Result<String> {
     )))
 }
 
-pub fn run(path_or_id: &str, json: bool) -> Result<()> {
+/// Print a transcript, streaming it from disk: `--json` output is written one utterance
+/// or word at a time, so even multi-hour transcripts don't have to fit in memory
+pub fn run(path_or_id: &str, json: bool, words: bool) -> Result<()> {
     let path = resolve_path(path_or_id)?;
-    let data = get_transcript(&path)?;
+    let (text_file, json_file) = transcript_files(Path::new(&path));
+    if !text_file.exists() && !json_file.exists() {
+        return Err(Error::FileNotFound(format!("No transcript found at {}", path)));
+    }
 
+    let mut out = BufWriter::new(io::stdout().lock());
     if json {
-        if let Some(structured) = data.structured {
-            println!("{}", serde_json::to_string_pretty(&structured)?);
+        if json_file.exists() {
+            write_json(&json_file, words, &mut out)?;
         } else {
             eprintln!("No structured data available.");
         }
-    } else if let Some(text) = data.text {
-        println!("{}", text);
+    } else if text_file.exists() {
+        io::copy(&mut File::open(&text_file)?, &mut out)?;
+        writeln!(out)?;
     } else {
         eprintln!("No text content found.");
     }
+    out.flush()?;
+
+    Ok(())
+}
 
+/// Pretty-print transcript.json as it is parsed, leaving out the words unless `words`
+fn write_json(json_file: &Path, words: bool, out: &mut impl Write) -> Result<()> {
+    let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(File::open(json_file)?));
+    deserializer.deserialize_map(TranscriptWriter { out, words })?;
+    deserializer.end()?;
     Ok(())
 }
+
+/// Writes each field of a transcript object as soon as it is read, in file order
+struct TranscriptWriter<'a, W> {
+    out: &'a mut W,
+    words: bool,
+}
+
+impl<'de, W: Write> Visitor<'de> for TranscriptWriter<'_, W> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a transcript object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<(), A::Error> {
+        let mut first = true;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "words" && !self.words {
+                map.next_value::<IgnoredAny>()?;
+                continue;
+            }
+
+            let separator = if first { "{\n" } else { ",\n" };
+            first = false;
+            write!(self.out, "{}  {}: ", separator, serde_json::to_string(&key).map_err(de::Error::custom)?)
+                .map_err(de::Error::custom)?;
+
+            match key.as_str() {
+                "words" => map.next_value_seed(ArrayWriter::<W, Word>::new(self.out))?,
+                "utterances" => map.next_value_seed(ArrayWriter::<W, Utterance>::new(self.out))?,
+                "chapters" => map.next_value_seed(ArrayWriter::<W, Chapter>::new(self.out))?,
+                "highlights" => map.next_value_seed(ArrayWriter::<W, Highlight>::new(self.out))?,
+                "entities" => map.next_value_seed(ArrayWriter::<W, Entity>::new(self.out))?,
+                _ => {
+                    let value: serde_json::Value = map.next_value()?;
+                    write_indented(self.out, &value, "  ").map_err(de::Error::custom)?;
+                }
+            }
+        }
+
+        let closing = if first { "{}\n" } else { "\n}\n" };
+        self.out.write_all(closing.as_bytes()).map_err(de::Error::custom)
+    }
+}
+
+/// Writes a JSON array one element at a time, parsed as `T` to keep its field order
+struct ArrayWriter<'a, W, T> {
+    out: &'a mut W,
+    element: PhantomData<T>,
+}
+
+impl<'a, W, T> ArrayWriter<'a, W, T> {
+    fn new(out: &'a mut W) -> Self {
+        Self { out, element: PhantomData }
+    }
+}
+
+impl<'de, W: Write, T: DeserializeOwned + Serialize> DeserializeSeed<'de> for ArrayWriter<'_, W, T> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, W: Write, T: DeserializeOwned + Serialize> Visitor<'de> for ArrayWriter<'_, W, T> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<(), A::Error> {
+        let mut empty = true;
+        while let Some(element) = seq.next_element::<T>()? {
+            let separator = if empty { "[\n    " } else { ",\n    " };
+            empty = false;
+            self.out.write_all(separator.as_bytes()).map_err(de::Error::custom)?;
+            write_indented(self.out, &element, "    ").map_err(de::Error::custom)?;
+        }
+
+        let closing = if empty { "[]" } else { "\n  ]" };
+        self.out.write_all(closing.as_bytes()).map_err(de::Error::custom)
+    }
+}
+
+/// Pretty-print a value nested at `indent` (JSON strings never contain raw newlines,
+/// so indenting every line is safe)
+fn write_indented(out: &mut impl Write, value: &impl Serialize, indent: &str) -> io::Result<()> {
+    let pretty = serde_json::to_string_pretty(value)?;
+    out.write_all(pretty.replace('\n', &format!("\n{}", indent)).as_bytes())
+}
@@ -0,0 +1,25 @@
+use crate::database::{list_all_transcripts, TranscriptFilter};
+use crate::error::Result;
+use crate::format;
+
+/// Print the most recently transcribed videos, newest first
+pub fn run(limit: i32) -> Result<()> {
+    let transcripts = list_all_transcripts(None, None, None, &TranscriptFilter::default(), limit)?;
+
+    if transcripts.is_empty() {
+        println!("No transcripts indexed yet (run `yt-cli reindex` if there are some on disk).");
+        return Ok(());
+    }
+
+    for t in transcripts {
+        let when = t.transcribed_at.as_deref().map(format::timestamp).unwrap_or_default();
+        let mut line = format!("{}  {}: {}", when, t.channel, t.title);
+        if let Some(duration) = t.duration {
+            line.push_str(&format!(" ({})", format::duration(duration)));
+        }
+        println!("{}", line);
+        println!("  Path: {}", t.path);
+    }
+
+    Ok(())
+}
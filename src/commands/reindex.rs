@@ -2,14 +2,24 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::config::{ensure_directories, transcripts_dir};
-use crate::database::{add_transcript, TranscriptMetadata};
-use crate::error::Result;
-use crate::transcriber::TranscriptData;
+use crate::commands::history;
+use crate::config::{ensure_directories, ensure_writable, transcripts_dir};
+use crate::database::{
+    add_transcript, index_attachment, list_all_transcripts, pending_index_paths, IndexOutcome, TranscriptFilter,
+    TranscriptMetadata,
+};
+use crate::error::{Error, Result};
+use crate::storage::{list_attachments, list_transcripts};
+use crate::transcriber::{chapter_headlines, split_sentences, TranscriptData, TranscriptSource};
 
-pub fn run() -> Result<()> {
+pub fn run(pending: bool) -> Result<()> {
+    ensure_writable("reindex")?;
     ensure_directories()?;
 
+    if pending {
+        return run_pending();
+    }
+
     let transcripts_path = transcripts_dir();
     if !transcripts_path.exists() {
         println!("No transcripts directory found.");
@@ -21,10 +31,56 @@ pub fn run() -> Result<()> {
     reindex_recursive(&transcripts_path, &mut count, true)?;
 
     println!("\nReindexed {} transcript(s).", count);
+    history::record("reindex", None, Some(&format!("{} transcript(s)", count)), true);
 
     Ok(())
 }
 
+/// Finish indexing transcripts whose search rows failed to be written
+fn run_pending() -> Result<()> {
+    let (completed, failed) = index_pending(true)?;
+    if completed == 0 && failed == 0 {
+        println!("No transcripts waiting to be indexed.");
+        return Ok(());
+    }
+
+    if failed > 0 {
+        println!("\nIndexed {} pending transcript(s), {} still pending.", completed, failed);
+    } else {
+        println!("\nIndexed {} pending transcript(s).", completed);
+    }
+    history::record("reindex", None, Some(&format!("{} pending transcript(s)", completed)), failed == 0);
+    Ok(())
+}
+
+/// Retry indexing every transcript flagged as pending, returning how many were completed
+/// and how many still failed
+pub fn index_pending(verbose: bool) -> Result<(usize, usize)> {
+    let mut completed = 0;
+    let mut failed = 0;
+    for path in pending_index_paths()? {
+        let path = Path::new(&path);
+        let result = if path.join("transcript.json").exists() {
+            index_video_dir(path)
+        } else {
+            Err(Error::FileNotFound(format!("No transcript.json at {}", path.display())))
+        };
+        match result {
+            Ok(()) => {
+                completed += 1;
+                if verbose {
+                    println!("Indexed: {}", path.file_name().unwrap_or_default().to_string_lossy());
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!("Error indexing {}: {}", path.display(), e);
+            }
+        }
+    }
+    Ok((completed, failed))
+}
+
 fn reindex_recursive(path: &Path, count: &mut i32, verbose: bool) -> Result<()> {
     if !path.is_dir() {
         return Ok(());
@@ -80,10 +136,10 @@ fn find_video_recursive(path: &Path, video_id: &str) -> Option<PathBuf> {
     // Recurse into subdirectories
     if let Ok(entries) = fs::read_dir(path) {
         for entry in entries.flatten() {
-            if entry.path().is_dir() {
-                if let Some(found) = find_video_recursive(&entry.path(), video_id) {
-                    return Some(found);
-                }
+            if entry.path().is_dir()
+                && let Some(found) = find_video_recursive(&entry.path(), video_id)
+            {
+                return Some(found);
             }
         }
     }
@@ -91,6 +147,33 @@ fn find_video_recursive(path: &Path, video_id: &str) -> Option<PathBuf> {
     None
 }
 
+/// Index transcripts only this library had after its database was replaced by another's
+/// (`archive import --force`, `sync-remote pull --force`): their files are still in the
+/// transcripts directory, but the new database doesn't know them
+pub fn index_local_only() -> Result<()> {
+    let known: HashSet<String> = list_all_transcripts(None, None, None, &TranscriptFilter::default(), -1)?
+        .into_iter()
+        .map(|record| record.path)
+        .collect();
+    let local_only: Vec<String> = list_transcripts(None, None, None, &TranscriptFilter::default())?
+        .into_iter()
+        .map(|info| info.path)
+        .filter(|path| !known.contains(path))
+        .collect();
+    if local_only.is_empty() {
+        return Ok(());
+    }
+
+    println!("Indexing {} transcript(s) only this library had:", local_only.len());
+    for path in &local_only {
+        match index_video_dir(Path::new(path)) {
+            Ok(()) => println!("  {}", path),
+            Err(e) => eprintln!("Warning: could not index {}: {} (`yt-cli reindex` retries)", path, e),
+        }
+    }
+    Ok(())
+}
+
 /// Index a single video directory into the database
 pub fn index_video_dir(video_dir: &Path) -> Result<()> {
     let transcript_json = video_dir.join("transcript.json");
@@ -168,8 +251,9 @@ pub fn index_video_dir(video_dir: &Path) -> Result<()> {
     let thumbnail = metadata.get("thumbnail").and_then(|v| v.as_str()).map(String::from);
     let view_count = metadata.get("view_count").and_then(|v| v.as_i64());
     let like_count = metadata.get("like_count").and_then(|v| v.as_i64());
+    let transcribed_by = metadata.get("transcribed_by").and_then(|v| v.as_str()).map(String::from);
 
-    add_transcript(&TranscriptMetadata {
+    let outcome = add_transcript(&TranscriptMetadata {
         video_id: &video_id,
         url: &url,
         title: &title,
@@ -188,7 +272,26 @@ pub fn index_video_dir(video_dir: &Path) -> Result<()> {
         word_count,
         confidence: transcript_data.confidence,
         transcript_text: text,
+        chapters: &chapter_headlines(&transcript_data),
+        sentences: &split_sentences(&transcript_data),
+        highlights: &transcript_data.highlights,
+        entities: &transcript_data.entities,
+        source: TranscriptSource::of(&transcript_data).id(),
+        transcribed_by: transcribed_by.as_deref(),
+        language: transcript_data.language.as_deref(),
+        missing_tail: transcript_data.missing_tail(duration),
+        transcribed_now: false,
     })?;
 
-    Ok(())
+    // Extracted attachment text is kept beside each attachment
+    for attachment in list_attachments(video_dir) {
+        if let Some(text_file) = &attachment.text {
+            index_attachment(&video_dir.to_string_lossy(), &attachment.name, &fs::read_to_string(text_file)?)?;
+        }
+    }
+
+    match outcome {
+        IndexOutcome::Complete => Ok(()),
+        IndexOutcome::Pending(e) => Err(e),
+    }
 }
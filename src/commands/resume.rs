@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use clap::ValueEnum;
+
+use crate::commands::history;
+use crate::commands::transcribe::{print_summary, save_and_index, track_usage, Slot};
+use crate::config::{ensure_directories, ensure_writable};
+use crate::database::{add_inflight, list_inflight, remove_inflight, InflightTranscription};
+use crate::downloader::VideoMetadata;
+use crate::error::{print_error, Error, Result};
+use crate::format;
+use crate::transcriber::{AssemblyAI, AudioSource, Backend, Submission, TranscriptData, Transcriber};
+
+/// Transcribe `source`, recording the job before polling so `yt-cli resume` can finish it
+/// if this process dies, instead of uploading (and paying) again
+///
+/// `slot` (a batch's download/upload slot) is released once the audio is submitted, so the
+/// next video can download while this one is transcribed.
+pub async fn transcribe_resumable(
+    transcriber: &Transcriber,
+    source: AudioSource<'_>,
+    url: &str,
+    platform: &str,
+    metadata: &VideoMetadata,
+    slot: &mut Slot,
+) -> Result<TranscriptData> {
+    let transcript_id = match transcriber.submit(source).await? {
+        Submission::Done(data) => return Ok(data),
+        Submission::Pending(transcript_id) => transcript_id,
+    };
+    slot.take();
+
+    let audio_path = match source {
+        AudioSource::File(path) => Some(path.to_string_lossy()),
+        AudioSource::Url(_) => None,
+    };
+    if let Err(e) = add_inflight(
+        &transcript_id,
+        transcriber.backend().id(),
+        url,
+        platform,
+        &serde_json::to_string(metadata)?,
+        audio_path.as_deref(),
+    ) {
+        eprintln!("Warning: could not record job {} for resuming: {}", transcript_id, e);
+    }
+
+    wait_for(transcriber, &transcript_id).await
+}
+
+/// Wait for a job, forgetting it if it failed
+///
+/// A finished job stays recorded until [`forget_job`] is called once its transcript is saved,
+/// so a failed save can still be resumed without paying again.
+async fn wait_for(transcriber: &Transcriber, transcript_id: &str) -> Result<TranscriptData> {
+    let result = transcriber.wait(transcript_id).await;
+    if matches!(result, Err(ref e) if !matches!(e, Error::Http(_))) {
+        forget_job(transcript_id);
+    }
+    result
+}
+
+/// Stop tracking a job for `resume`: its transcript is saved, or it was superseded
+pub fn forget_job(transcript_id: &str) {
+    if let Err(e) = remove_inflight(transcript_id) {
+        eprintln!("Warning: could not update job {}: {}", transcript_id, e);
+    }
+}
+
+/// Reattach to jobs left behind by interrupted runs and save their transcripts
+pub async fn run() -> Result<()> {
+    ensure_writable("resume")?;
+
+    let jobs = list_inflight()?;
+    if jobs.is_empty() {
+        println!("No interrupted transcriptions to resume.");
+        return Ok(());
+    }
+    ensure_directories()?;
+
+    let mut failures = 0;
+    for job in &jobs {
+        if let Err(e) = resume_one(job).await {
+            print_error(&e);
+            history::record("resume", Some(&job.url), Some(&e.to_string()), false);
+            failures += 1;
+        }
+    }
+
+    println!("\nResumed {} of {} transcription(s).", jobs.len() - failures, jobs.len());
+    if failures > 0 {
+        return Err(Error::Transcription(format!(
+            "{} of {} transcription(s) could not be resumed",
+            failures,
+            jobs.len()
+        )));
+    }
+
+    Ok(())
+}
+
+async fn resume_one(job: &InflightTranscription) -> Result<()> {
+    if job.cancel_pending {
+        return finish_cancel(job).await;
+    }
+
+    let metadata: VideoMetadata = serde_json::from_str(&job.metadata)?;
+    eprintln!(
+        "\nResuming: {} (job {}, started {})",
+        metadata.title,
+        job.transcript_id,
+        format::timestamp(&job.started_at)
+    );
+
+    let backend = Backend::from_str(&job.backend, true)
+        .map_err(|_| Error::Config(format!("Unknown backend '{}'", job.backend)))?;
+    let transcriber = Transcriber::new(backend)?;
+
+    let mut transcript_data = wait_for(&transcriber, &job.transcript_id).await?;
+    eprintln!("Transcription complete!");
+    track_usage(&transcriber, &metadata, &transcript_data);
+
+    // The downloaded audio is kept with the transcript if it's still around
+    let audio_file = job.audio_path.as_deref().map(Path::new).filter(|path| path.exists());
+    let storage_path =
+        save_and_index(&job.url, &job.platform, &metadata, &mut transcript_data, audio_file)?;
+    print_summary(&storage_path, &metadata, &transcript_data);
+
+    Ok(())
+}
+
+/// Retry deleting a job `jobs cancel` couldn't delete from AssemblyAI, instead of saving it
+async fn finish_cancel(job: &InflightTranscription) -> Result<()> {
+    eprintln!("\nDeleting cancelled job {} ({})", job.transcript_id, job.url);
+    AssemblyAI::new()?.delete_transcript(&job.transcript_id).await?;
+    remove_inflight(&job.transcript_id)?;
+    eprintln!("Deleted it from AssemblyAI.");
+    Ok(())
+}
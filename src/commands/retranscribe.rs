@@ -0,0 +1,140 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commands::history;
+use crate::commands::resume::transcribe_resumable;
+use crate::commands::transcribe::{check_cost, print_summary, save_and_index, speakers_expected, track_usage};
+use crate::config::{ensure_directories, ensure_writable, settings, NotifyEvent};
+use crate::database::{transcripts_for_video, TranscriptRecord};
+use crate::downloader::{download_audio, VideoMetadata};
+use crate::error::{Error, Result};
+use crate::notify::notify;
+use crate::storage::{set_aside_speaker_names, OLD_SPEAKERS_FILE};
+use crate::transcriber::{AudioSource, Backend, TranscriptSource, Transcriber};
+
+/// Name of the audio file kept in a transcript directory
+const AUDIO_FILE: &str = "audio.mp3";
+
+/// Transcribe a stored video again with `backend` and the current settings (speaker counts,
+/// word boost, redaction, `[spelling]`), replacing its transcript and search index entries
+///
+/// The stored audio is used when there is some, else the video is downloaded again.
+/// Nothing is replaced until the new transcript is back, so a failed run leaves the old
+/// one as it was.
+pub async fn run(video_id: &str, backend: Backend) -> Result<()> {
+    ensure_writable("retranscribe")?;
+
+    let result = retranscribe(video_id, backend).await;
+    let detail = match &result {
+        Ok(()) => backend.id().to_string(),
+        Err(e) => e.to_string(),
+    };
+    if result.is_ok() {
+        // The previous transcript is replaced
+        history::record_irreversible("retranscribe", Some(video_id), Some(&detail));
+    } else {
+        history::record("retranscribe", Some(video_id), Some(&detail), false);
+    }
+    if result.is_err() {
+        notify(NotifyEvent::Failed, "Retranscription failed", &format!("{}: {}", video_id, detail), None);
+    }
+    result
+}
+
+async fn retranscribe(video_id: &str, backend: Backend) -> Result<()> {
+    let record = stored_transcript(video_id)?;
+    let url = record
+        .url
+        .clone()
+        .filter(|url| !url.is_empty())
+        .ok_or_else(|| Error::Download(format!("No source URL stored for {}", video_id)))?;
+    let dir = PathBuf::from(&record.path);
+    ensure_directories()?;
+
+    let stored_audio = dir.join(AUDIO_FILE);
+    let stored_metadata = fs::read_to_string(dir.join("metadata.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<VideoMetadata>(&content).ok());
+    // Downloaded audio is moved into the transcript directory once saved; stored audio
+    // stays where it is
+    let (audio_file, downloaded, metadata) = match stored_metadata {
+        Some(metadata) if stored_audio.is_file() => {
+            eprintln!("Using stored audio: {}", stored_audio.display());
+            (stored_audio, false, metadata)
+        }
+        _ => {
+            eprintln!("No stored audio, downloading: {}", url);
+            let (audio_file, metadata) = download_audio(&url)?;
+            (audio_file, true, metadata)
+        }
+    };
+
+    let result = transcribe(&url, &record, backend, &audio_file, downloaded, &metadata).await;
+    if result.is_err() && downloaded {
+        let _ = fs::remove_file(&audio_file);
+    }
+    let storage_path = result?;
+
+    if storage_path != dir && dir.exists() && record.source == TranscriptSource::Api.id() {
+        eprintln!(
+            "Note: saved to {} under the current directory naming; the old directory {} is left as it was.",
+            storage_path.display(),
+            dir.display()
+        );
+    } else {
+        // Diarization starts over, so the old names may belong to other labels now
+        let names = set_aside_speaker_names(&storage_path)?;
+        if !names.is_empty() {
+            let names: Vec<String> = names.iter().map(|(label, name)| format!("{} = {}", label, name)).collect();
+            eprintln!(
+                "Note: the new transcript has fresh speaker labels, so the names from `yt-cli speakers set` ({}) \
+                 were moved to {}. Check `yt-cli speakers list {}` and set them again.",
+                names.join(", "),
+                OLD_SPEAKERS_FILE,
+                video_id
+            );
+        }
+    }
+    Ok(())
+}
+
+/// The transcript to redo: the API one if the video has one, else its captions
+fn stored_transcript(video_id: &str) -> Result<TranscriptRecord> {
+    let mut records = transcripts_for_video(video_id)?;
+    let api = records.iter().position(|r| r.source == TranscriptSource::Api.id());
+    match api {
+        Some(i) => Ok(records.swap_remove(i)),
+        None => records.pop().ok_or_else(|| Error::FileNotFound(format!("No transcript for video {}", video_id))),
+    }
+}
+
+async fn transcribe(
+    url: &str,
+    record: &TranscriptRecord,
+    backend: Backend,
+    audio_file: &Path,
+    downloaded: bool,
+    metadata: &VideoMetadata,
+) -> Result<PathBuf> {
+    let mut transcriber = Transcriber::new(backend)?;
+    check_cost(&transcriber, &metadata.title, metadata.duration).await?;
+    transcriber.set_speakers(speakers_expected(metadata));
+    transcriber.set_word_boost(settings().boost_words(&metadata.channel, metadata.uploader_id.as_deref()));
+
+    eprintln!("\nTranscribing {} again with {}...", metadata.title, transcriber.name());
+    let source = AudioSource::File(audio_file);
+    let mut transcript_data =
+        transcribe_resumable(&transcriber, source, url, &record.platform, metadata, &mut None).await?;
+    eprintln!("Transcription complete!");
+    track_usage(&transcriber, metadata, &transcript_data);
+
+    let storage_path = save_and_index(
+        url,
+        &record.platform,
+        metadata,
+        &mut transcript_data,
+        downloaded.then_some(audio_file),
+    )?;
+    print_summary(&storage_path, metadata, &transcript_data);
+    Ok(storage_path)
+}
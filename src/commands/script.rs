@@ -0,0 +1,89 @@
+use crate::error::{Error, Result};
+
+/// The commands in a `yt-cli batch` script (or stdin for `-`), as line numbers and the words
+/// of each line
+///
+/// Blank lines and lines starting with `#` are skipped, as is a leading `yt-cli`. Words are
+/// split as a shell would: on whitespace, with '...' and "..." quoting and `\` escapes.
+pub fn read(path: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let content = if path == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(path).map_err(|e| Error::FileNotFound(format!("{}: {}", path, e)))?
+    };
+
+    let mut commands = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut words = split_words(line)
+            .ok_or_else(|| Error::Config(format!("{} line {}: unclosed quote", path, i + 1)))?;
+        if words.first().is_some_and(|word| word == "yt-cli") {
+            words.remove(0);
+        }
+        if !words.is_empty() {
+            commands.push((i + 1, words));
+        }
+    }
+    Ok(commands)
+}
+
+/// Shell-style words of a line; `None` if a quote isn't closed
+fn split_words(line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    // Set once a word has started, so "" is kept as an empty word
+    let mut in_word = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            c @ ('"' | '\\' | '$' | '`') => word.push(c),
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(c) = chars.next() {
+                    word.push(c);
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
@@ -1,28 +1,153 @@
-use crate::database::search_transcripts;
-use crate::error::Result;
+use std::collections::HashMap;
 
-pub fn run(query: &str, limit: i32) -> Result<()> {
-    let results = search_transcripts(query, limit)?;
+use crate::config::settings;
+use crate::database::{
+    embedded_transcripts, has_sentence_index, search_attachments, search_sentences, search_transcripts,
+    SearchResult, TranscriptFilter,
+};
+use crate::embeddings;
+use crate::error::{Error, Result};
+use crate::format;
+use crate::platform;
+use crate::transcriber::{format_timestamp, speaker_label, TranscriptSource};
 
-    if results.is_empty() {
+/// Keyword matches considered for hybrid ranking, beyond the requested limit
+const HYBRID_CANDIDATES: i32 = 200;
+/// Matching moments listed per transcript
+const HITS_PER_RESULT: i32 = 3;
+
+pub fn run(query: &str, limit: i32, filter: &TranscriptFilter) -> Result<()> {
+    let results = search_transcripts(query, limit, filter)?;
+    let attachments = search_attachments(query, limit)?;
+
+    if results.is_empty() && attachments.is_empty() {
         println!("No results found for: {}", query);
         return Ok(());
     }
 
-    println!("Found {} result(s) for '{}':\n", results.len(), query);
+    if !results.is_empty() {
+        println!("Found {} result(s) for '{}':\n", results.len(), query);
+    }
 
     for r in results {
-        let duration = r.duration.unwrap_or(0);
-        let mins = duration / 60;
-        let secs = duration % 60;
+        print_result(&r, query)?;
+        println!();
+    }
 
-        println!("- {}: {} ({}m {}s)", r.channel, r.title, mins, secs);
-        println!("  Path: {}", r.path);
-        if let Some(snippet) = r.snippet {
-            println!("  Match: {}", snippet);
+    if !attachments.is_empty() {
+        println!("Found in {} attachment(s):\n", attachments.len());
+        for hit in attachments {
+            println!("- {}: {} ({})", hit.channel, hit.title, hit.name);
+            println!("  Path: {}", hit.path);
+            println!("  Match: {}", hit.snippet);
+            println!();
         }
+    }
+
+    if !has_sentence_index()? {
+        eprintln!("Run `yt-cli reindex` to see where in each video the matches are.");
+    }
+
+    Ok(())
+}
+
+/// Rank by a blend of keyword (FTS5) and meaning (embedding) similarity
+///
+/// Both scores are scaled to 0-1 before mixing: `weight` is the share of the vector score,
+/// so 0 ranks by keywords only and 1 by meaning only.
+pub async fn run_hybrid(query: &str, limit: i32, filter: &TranscriptFilter, weight: Option<f64>) -> Result<()> {
+    let weight = weight.unwrap_or(settings().search.hybrid_weight);
+    if !(0.0..=1.0).contains(&weight) {
+        return Err(Error::Config(format!("Hybrid weight must be between 0 and 1, got {}", weight)));
+    }
+
+    let documents = embedded_transcripts(&settings().embeddings.model, filter)?;
+    if documents.is_empty() {
+        return Err(Error::Config(
+            "No transcripts are embedded with the configured model yet (run `yt-cli embed` first)".to_string(),
+        ));
+    }
+
+    // Keyword scores relative to the best match; a query that isn't valid FTS5 syntax
+    // (e.g. a stray quote) still gets semantic results
+    let keyword_hits = match search_transcripts(query, HYBRID_CANDIDATES, filter) {
+        Ok(hits) => hits,
+        Err(e) => {
+            eprintln!("Keyword search skipped: {}", e);
+            Vec::new()
+        }
+    };
+    let best_rank = keyword_hits.iter().map(|r| r.rank).fold(0.0, f64::min);
+    let mut keyword_scores: HashMap<i64, (f64, SearchResult)> = keyword_hits
+        .into_iter()
+        .map(|r| {
+            let score = if best_rank < 0.0 { r.rank / best_rank } else { 0.0 };
+            (r.id, (score, r))
+        })
+        .collect();
+
+    let query_vector = embeddings::embed(&[query.to_string()]).await?.remove(0);
+    let similarities: Vec<f64> = documents
+        .iter()
+        .map(|(_, vector)| embeddings::cosine(&query_vector, vector))
+        .collect();
+    let low = similarities.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = similarities.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let mut ranked: Vec<(f64, SearchResult)> = Vec::new();
+    for ((document, _), similarity) in documents.into_iter().zip(similarities) {
+        let vector_score = if high > low { (similarity - low) / (high - low) } else { 1.0 };
+        let (keyword_score, result) = keyword_scores
+            .remove(&document.id)
+            .unwrap_or((0.0, document));
+        ranked.push(((1.0 - weight) * keyword_score + weight * vector_score, result));
+    }
+    // Keyword matches that were never embedded still count on their keyword score
+    for (keyword_score, result) in keyword_scores.into_values() {
+        ranked.push(((1.0 - weight) * keyword_score, result));
+    }
+
+    ranked.sort_by(|a, b| b.0.total_cmp(&a.0));
+    ranked.truncate(limit.max(0) as usize);
+
+    println!("Top {} result(s) for '{}' (vector weight {:.2}):\n", ranked.len(), query, weight);
+    for (score, r) in ranked {
+        print_result(&r, query)?;
+        println!("  Score: {:.3}", score);
         println!();
     }
 
     Ok(())
 }
+
+/// A result with the sentences that match `query`: timestamp, speaker and a link to that
+/// point in the video; matches only in the title or description fall back to the snippet
+fn print_result(r: &SearchResult, query: &str) -> Result<()> {
+    println!("- {}: {} ({})", r.channel, r.title, format::duration(r.duration.unwrap_or(0)));
+    println!("  Path: {}", r.path);
+
+    if r.source == TranscriptSource::Captions.id() {
+        println!("  Source: captions");
+    }
+
+    let hits = search_sentences(query, r.id, HITS_PER_RESULT)?;
+    if hits.is_empty() {
+        if let Some(snippet) = &r.snippet {
+            println!("  Match: {}", snippet);
+        }
+        return Ok(());
+    }
+    for hit in hits {
+        match &hit.speaker {
+            Some(speaker) => {
+                println!("  [{}] {}: {}", format_timestamp(hit.start_ms), speaker_label(speaker), hit.text)
+            }
+            None => println!("  [{}] {}", format_timestamp(hit.start_ms), hit.text),
+        }
+        if let Some(url) = &r.url {
+            println!("    {}", platform::timestamp_url(url, hit.start_ms / 1000));
+        }
+    }
+
+    Ok(())
+}
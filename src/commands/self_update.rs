@@ -0,0 +1,166 @@
+use std::path::Path;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::error::{Error, Result};
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/DigiBugCat/yt-cli/releases/latest";
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    assets: Vec<Asset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Asset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl Release {
+    fn asset(&self, name: &str) -> Option<&Asset> {
+        self.assets.iter().find(|a| a.name == name)
+    }
+}
+
+pub async fn run(check_only: bool, force: bool) -> Result<()> {
+    let client = Client::builder()
+        .user_agent(concat!("yt-cli/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(300))
+        .build()?;
+
+    let current = env!("CARGO_PKG_VERSION");
+    let release: Release = get(&client, LATEST_RELEASE_URL).await?.json().await?;
+    let latest = release.tag_name.trim_start_matches('v');
+
+    println!("Current version: {}", current);
+    println!("Latest release:  {}", latest);
+
+    if !is_newer(latest, current) && !force {
+        println!("Already up to date.");
+        return Ok(());
+    }
+    if check_only {
+        println!("Run `yt-cli self-update` to install it.");
+        return Ok(());
+    }
+
+    let name = asset_name();
+    let binary = release.asset(&name).ok_or_else(|| {
+        Error::Update(format!("release {} has no binary for this platform ({})", latest, name))
+    })?;
+    let expected = expected_checksum(&client, &release, &name).await?;
+
+    println!("Downloading {}...", binary.name);
+    let bytes = get(&client, &binary.browser_download_url).await?.bytes().await?;
+
+    let actual = hex(&Sha256::digest(&bytes));
+    if !actual.eq_ignore_ascii_case(&expected) {
+        return Err(Error::Update(format!(
+            "checksum mismatch for {} (expected {}, got {})",
+            name, expected, actual
+        )));
+    }
+    println!("Checksum verified.");
+
+    let exe = std::env::current_exe()?;
+    replace_executable(&exe, &bytes)?;
+    println!("Updated {} to {}.", exe.display(), latest);
+
+    Ok(())
+}
+
+async fn get(client: &Client, url: &str) -> Result<reqwest::Response> {
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(Error::Update(format!("GET {} returned {}", url, response.status())));
+    }
+    Ok(response)
+}
+
+/// Release asset name for this build, e.g. `yt-cli-linux-x86_64` or `yt-cli-windows-x86_64.exe`
+fn asset_name() -> String {
+    format!(
+        "yt-cli-{}-{}{}",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::env::consts::EXE_SUFFIX
+    )
+}
+
+/// SHA-256 from `<asset>.sha256`, or the asset's line in `SHA256SUMS`
+async fn expected_checksum(client: &Client, release: &Release, name: &str) -> Result<String> {
+    if let Some(asset) = release.asset(&format!("{}.sha256", name)) {
+        let content = get(client, &asset.browser_download_url).await?.text().await?;
+        if let Some(hash) = content.split_whitespace().next() {
+            return Ok(hash.to_string());
+        }
+    }
+
+    if let Some(asset) = release.asset("SHA256SUMS") {
+        let content = get(client, &asset.browser_download_url).await?.text().await?;
+        let hash = content.lines().find_map(|line| {
+            let (hash, file) = line.split_once(char::is_whitespace)?;
+            (file.trim().trim_start_matches('*') == name).then(|| hash.to_string())
+        });
+        if let Some(hash) = hash {
+            return Ok(hash);
+        }
+    }
+
+    Err(Error::Update(format!("release has no SHA-256 checksum for {}; refusing to install", name)))
+}
+
+/// Compare dotted numeric versions ("0.10.1" > "0.9.3"); pre-release suffixes are ignored
+fn is_newer(latest: &str, current: &str) -> bool {
+    let parse = |version: &str| -> Vec<u64> {
+        version
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    };
+    parse(latest) > parse(current)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Write the new binary beside the running one, then swap it into place
+fn replace_executable(exe: &Path, bytes: &[u8]) -> Result<()> {
+    let staged = exe.with_extension("new");
+    std::fs::write(&staged, bytes)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    // Windows can't overwrite a running executable, but it can rename it
+    let old = exe.with_extension("old");
+    if cfg!(windows) {
+        let _ = std::fs::remove_file(&old);
+        if let Err(e) = std::fs::rename(exe, &old) {
+            let _ = std::fs::remove_file(&staged);
+            return Err(e.into());
+        }
+    }
+
+    std::fs::rename(&staged, exe).map_err(|e| {
+        let _ = std::fs::remove_file(&staged);
+        // Put the running binary back, so a failed update doesn't leave no executable at all
+        if cfg!(windows) {
+            let _ = std::fs::rename(&old, exe);
+        }
+        Error::Update(format!("could not replace {}: {}", exe.display(), e))
+    })
+}
@@ -0,0 +1,137 @@
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+
+use crate::commands::init::verify_key;
+use crate::config::{
+    config_dir, config_file_path, database_key, default_config_dir, env_file_path, read_only,
+};
+use crate::error::{Error, Result};
+
+/// Whether to offer the setup wizard: nothing configured yet and someone at the terminal
+pub fn needed() -> bool {
+    let key_in_env = ["ASSEMBLYAI_API_KEY", "DEEPGRAM_API_KEY"]
+        .iter()
+        .any(|name| std::env::var(name).is_ok_and(|v| !v.is_empty()));
+
+    !config_file_path().exists()
+        && !env_file_path().exists()
+        && !key_in_env
+        && database_key().is_none()
+        && !read_only()
+        && io::stdin().is_terminal()
+}
+
+/// Ask whether to run the wizard, then run it; returns whether a config was written
+///
+/// Must run before anything resolves `data_dir()`, which is fixed on first use.
+pub async fn offer() -> Result<bool> {
+    println!("No yt-cli configuration found at {}.", config_dir().display());
+    if !confirm("Run the setup wizard now?", true)? {
+        println!("Skipping setup (run `yt-cli init` to configure an API key later).\n");
+        return Ok(false);
+    }
+
+    run().await?;
+    Ok(true)
+}
+
+/// Interactive first-run setup: data directory, backend, API key and cookies mode
+pub async fn run() -> Result<()> {
+    let config_dir = config_dir();
+
+    // YT_TRANSCRIBE_DATA_DIR already picks the directory, and wins over config.toml
+    let data_dir = if std::env::var_os("YT_TRANSCRIBE_DATA_DIR").is_some() {
+        None
+    } else {
+        let answer = prompt("Data directory", &default_config_dir().to_string_lossy())?;
+        Some(expand_home(&answer)).filter(|dir| *dir != config_dir)
+    };
+
+    let backend = loop {
+        match prompt("Transcription backend (assemblyai/deepgram)", "assemblyai")?
+            .to_lowercase()
+            .as_str()
+        {
+            "assemblyai" => break "assemblyai",
+            "deepgram" => break "deepgram",
+            other => println!("Unknown backend '{}'.", other),
+        }
+    };
+
+    let (key_name, service) = if backend == "deepgram" {
+        ("DEEPGRAM_API_KEY", "Deepgram")
+    } else {
+        ("ASSEMBLYAI_API_KEY", "AssemblyAI")
+    };
+    let api_key = prompt(&format!("{} API key", service), "")?;
+    if api_key.is_empty() {
+        return Err(Error::ApiKeyMissing);
+    }
+    if backend == "assemblyai" {
+        verify_key(&api_key).await?;
+    }
+
+    let cookies = confirm(
+        "Send Firefox cookies with every yt-dlp request (needed for members-only videos)?",
+        true,
+    )?;
+
+    let mut config = String::from("# Written by the yt-cli setup wizard\n");
+    if let Some(dir) = &data_dir {
+        let value = toml::Value::String(dir.to_string_lossy().to_string());
+        config.push_str(&format!("data_dir = {}\n", value));
+    }
+    config.push_str(&format!("backend = \"{}\"\n", backend));
+    config.push_str(&format!("cookies = \"{}\"\n", if cookies { "firefox" } else { "none" }));
+
+    std::fs::create_dir_all(&config_dir)?;
+    if let Some(dir) = &data_dir {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let config_file = config_file_path();
+    std::fs::write(&config_file, config)?;
+    let env_file = env_file_path();
+    std::fs::write(&env_file, format!("{}={}\n", key_name, api_key))?;
+
+    println!("\nConfig saved to {}", config_file.display());
+    println!("API key saved to {}", env_file.display());
+    if let Some(dir) = &data_dir {
+        println!("Data directory: {}", dir.display());
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Read one line, returning `default` when the answer is empty
+fn prompt(question: &str, default: &str) -> Result<String> {
+    if default.is_empty() {
+        print!("{}: ", question);
+    } else {
+        print!("{} [{}]: ", question, default);
+    }
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let answer = input.trim();
+    Ok(if answer.is_empty() { default } else { answer }.to_string())
+}
+
+fn confirm(question: &str, default: bool) -> Result<bool> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    let answer = prompt(&format!("{} [{}]", question, hint), "")?;
+    Ok(match answer.to_lowercase().as_str() {
+        "" => default,
+        answer => answer.starts_with('y'),
+    })
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches('/')),
+        _ => PathBuf::from(path),
+    }
+}
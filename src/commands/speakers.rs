@@ -0,0 +1,130 @@
+use std::path::{Path, PathBuf};
+
+use crate::commands::history;
+use crate::commands::read::resolve_path;
+use crate::commands::undo::UndoAction;
+use crate::commands::reindex::index_video_dir;
+use crate::config::ensure_writable;
+use crate::error::{Error, Result};
+use crate::storage::{
+    get_transcript, load_metadata, load_speaker_names, metadata_sections, save_speaker_names, save_transcript,
+};
+use crate::transcriber::{format_transcript_markdown_sections, TranscriptData};
+
+/// Name a speaker of one transcript, e.g. label "A" as "Jerome Powell"
+///
+/// The label is replaced in transcript.json and transcript.md and the transcript is
+/// reindexed, so `read`, exports and search show the name. The original labels are kept
+/// in speakers.json: a speaker can be renamed again, and setting it to its own label
+/// removes the name.
+pub fn set(path_or_id: &str, label: &str, name: &str) -> Result<()> {
+    ensure_writable("name speakers")?;
+
+    let (label, name) = (label.trim(), name.trim());
+    if name.is_empty() {
+        return Err(Error::Config(format!(
+            "Give a name (or `{}` to go back to the label)",
+            label
+        )));
+    }
+
+    let path = PathBuf::from(resolve_path(path_or_id)?);
+    let previous = rename(&path, label, name)?;
+
+    if name == label {
+        println!("Speaker {} has no name any more.", label);
+    } else {
+        println!("Speaker {} is now {}.", label, name);
+    }
+    history::record_undoable(
+        "speakers",
+        Some(path_or_id),
+        Some(&format!("{} = {}", label, name)),
+        &UndoAction::RenameSpeaker {
+            path: path.to_string_lossy().into_owned(),
+            label: label.to_string(),
+            previous,
+        },
+    );
+    Ok(())
+}
+
+/// Show speaker `label` of the transcript at `path` as `name` (its label to remove the name)
+/// and reindex it; returns what the speaker was shown as before
+pub fn rename(path: &Path, label: &str, name: &str) -> Result<String> {
+    let mut data = transcript_data(path)?;
+    let mut names = load_speaker_names(path);
+
+    let current = names.get(label).cloned().unwrap_or_else(|| label.to_string());
+    let speakers = speakers_in_order(&data);
+    if !speakers.contains(&current) {
+        return Err(Error::Config(format!(
+            "No speaker {} in {} (speakers: {})",
+            label,
+            path.display(),
+            speakers.join(", ")
+        )));
+    }
+    if name != current && speakers.iter().any(|s| s == name) {
+        return Err(Error::Config(format!("{} already names another speaker in this transcript", name)));
+    }
+
+    for utterance in data.utterances.iter_mut().filter(|u| u.speaker == current) {
+        utterance.speaker = name.to_string();
+    }
+    for word in data.words.iter_mut().filter(|w| w.speaker.as_deref() == Some(current.as_str())) {
+        word.speaker = Some(name.to_string());
+    }
+    if name == label {
+        names.remove(label);
+    } else {
+        names.insert(label.to_string(), name.to_string());
+    }
+
+    save_transcript(path, &format_transcript_markdown_sections(&data, &metadata_sections(&load_metadata(path))), &data)?;
+    save_speaker_names(path, &names)?;
+    index_video_dir(path)?;
+    Ok(current)
+}
+
+/// Print a transcript's speakers in order of appearance, with their names
+pub fn list(path_or_id: &str) -> Result<()> {
+    let path = PathBuf::from(resolve_path(path_or_id)?);
+    let data = transcript_data(&path)?;
+    let names = load_speaker_names(&path);
+
+    let speakers = speakers_in_order(&data);
+    if speakers.is_empty() {
+        println!("No speakers in this transcript.");
+        return Ok(());
+    }
+
+    println!("{:<8} {:<30} {:>10}", "Label", "Name", "Utterances");
+    for speaker in speakers {
+        let label = names
+            .iter()
+            .find(|(_, name)| **name == speaker)
+            .map(|(label, _)| label.as_str())
+            .unwrap_or(&speaker);
+        let name = if label == speaker { "-" } else { speaker.as_str() };
+        let utterances = data.utterances.iter().filter(|u| u.speaker == speaker).count();
+        println!("{:<8} {:<30} {:>10}", label, name, utterances);
+    }
+    Ok(())
+}
+
+fn transcript_data(path: &Path) -> Result<TranscriptData> {
+    get_transcript(&path.to_string_lossy())?
+        .structured
+        .ok_or_else(|| Error::FileNotFound(format!("No transcript.json at {}", path.display())))
+}
+
+fn speakers_in_order(data: &TranscriptData) -> Vec<String> {
+    let mut speakers: Vec<String> = Vec::new();
+    for utterance in &data.utterances {
+        if !speakers.contains(&utterance.speaker) {
+            speakers.push(utterance.speaker.clone());
+        }
+    }
+    speakers
+}
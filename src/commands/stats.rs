@@ -1,6 +1,7 @@
 use crate::config::data_dir;
 use crate::database::get_stats;
 use crate::error::Result;
+use crate::format;
 
 pub fn run() -> Result<()> {
     let stats = get_stats()?;
@@ -11,17 +12,20 @@ pub fn run() -> Result<()> {
         return Ok(());
     }
 
-    let total_duration = stats.total_duration.unwrap_or(0);
-    let hours = total_duration / 3600;
-    let mins = (total_duration % 3600) / 60;
-
     println!("Transcript Database Statistics");
     println!("==============================");
-    println!("Total transcripts: {}", stats.total_transcripts);
-    println!("Unique channels:   {}", stats.unique_channels);
-    println!("Unique platforms:  {}", stats.unique_platforms);
-    println!("Total duration:    {}h {}m", hours, mins);
-    println!("Total words:       {}", stats.total_words.unwrap_or(0));
+    println!("Total transcripts: {}", format::number(stats.total_transcripts));
+    println!("Unique channels:   {}", format::number(stats.unique_channels));
+    println!("Unique platforms:  {}", format::number(stats.unique_platforms));
+    println!("Total duration:    {}", format::duration(stats.total_duration.unwrap_or(0)));
+    println!("Total words:       {}", format::number(stats.total_words.unwrap_or(0)));
+
+    if !stats.transcribers.is_empty() {
+        println!("\nTranscribed by:");
+        for (user, count) in &stats.transcribers {
+            println!("  {:<16} {}", user, format::number(*count));
+        }
+    }
     println!("\nData directory: {}", data_dir().display());
 
     Ok(())
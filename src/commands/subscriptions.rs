@@ -0,0 +1,190 @@
+use crate::commands::channel::resolve as resolve_channel;
+use crate::commands::channel_transcribe::DurationFilter;
+use crate::commands::history;
+use crate::commands::transcribe::run_many;
+use crate::commands::undo::UndoAction;
+use crate::config::ensure_writable;
+use crate::database::{
+    add_subscription, get_transcript_by_id, list_subscriptions, mark_subscription_synced,
+    remove_subscription, Subscription,
+};
+use crate::downloader::{fetch_channel_videos, fetch_playlist_entries, PlaylistEntry};
+use crate::error::{print_error, Error, Result};
+use crate::format;
+use crate::platform::YouTube;
+use crate::transcriber::Backend;
+
+/// Subscribe to a channel; only uploads after now are synced unless `backfill` is set
+pub fn subscribe(channel: &str, name: Option<&str>, backfill: bool, first: bool) -> Result<()> {
+    ensure_writable("subscribe")?;
+
+    let channel = &resolve_channel(channel, first)?;
+    let url = YouTube::channel_videos_url(channel);
+    eprintln!("Checking channel...");
+    let latest = fetch_playlist_entries(&url, 1)?;
+    let newest = latest.first();
+    if newest.is_none() && !backfill {
+        return Err(Error::Download(format!("No videos found for channel: {}", channel)));
+    }
+
+    let name = name
+        .map(String::from)
+        .or_else(|| newest.and_then(|v| v.channel.clone()))
+        .unwrap_or_else(|| channel.to_string());
+    let marker = if backfill { None } else { newest.map(|v| v.id.as_str()) };
+
+    if !add_subscription(&url, Some(&name), marker)? {
+        println!("Already subscribed to {}.", name);
+        return Ok(());
+    }
+
+    println!("Subscribed to {} ({}).", name, url);
+    if backfill {
+        println!("The next `yt-cli sync` transcribes its latest videos.");
+    } else {
+        println!("The next `yt-cli sync` transcribes uploads from now on.");
+    }
+    history::record("subscribe", Some(&name), Some(&url), true);
+
+    Ok(())
+}
+
+pub fn unsubscribe(channel: &str) -> Result<()> {
+    ensure_writable("unsubscribe")?;
+
+    let url = YouTube::channel_videos_url(channel);
+    let subscription = list_subscriptions()?
+        .into_iter()
+        .find(|s| s.url == url || s.name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(channel)))
+        .ok_or_else(|| Error::Config(format!("Not subscribed to {}", channel)))?;
+
+    remove_subscription(&subscription.url, subscription.name.as_deref().unwrap_or(channel))?;
+    println!("Unsubscribed from {}.", channel);
+    history::record_undoable(
+        "unsubscribe",
+        Some(channel),
+        None,
+        &UndoAction::Resubscribe {
+            url: subscription.url,
+            name: subscription.name,
+            last_video_id: subscription.last_video_id,
+        },
+    );
+
+    Ok(())
+}
+
+pub fn list() -> Result<()> {
+    let subscriptions = list_subscriptions()?;
+
+    if subscriptions.is_empty() {
+        println!("No subscriptions. Add one with `yt-cli subscribe <channel>`.");
+        return Ok(());
+    }
+
+    println!("{} subscription(s):\n", subscriptions.len());
+    for s in &subscriptions {
+        println!("- {}", s.name.as_deref().unwrap_or(&s.url));
+        println!("  URL: {}", s.url);
+        let last_sync = s.last_synced_at.as_deref().map(format::timestamp);
+        println!("  Last sync: {}", last_sync.as_deref().unwrap_or("never"));
+    }
+
+    Ok(())
+}
+
+/// Settings for a sync run
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    /// Latest videos checked per channel
+    pub limit: usize,
+    pub durations: DurationFilter,
+    pub backend: Backend,
+    /// Import captions in this language instead of transcribing
+    pub captions_lang: Option<String>,
+    /// Videos downloading and uploading at once
+    pub jobs: usize,
+}
+
+/// Transcribe new uploads of every subscribed channel
+pub async fn sync(options: &SyncOptions) -> Result<()> {
+    ensure_writable("sync")?;
+
+    let subscriptions = list_subscriptions()?;
+    if subscriptions.is_empty() {
+        println!("No subscriptions. Add one with `yt-cli subscribe <channel>`.");
+        return Ok(());
+    }
+
+    let mut failed = Vec::new();
+    for subscription in &subscriptions {
+        let name = subscription.name.as_deref().unwrap_or(&subscription.url);
+        println!("\n== {} ==", name);
+
+        let result = sync_one(subscription, options).await;
+        let outcome = match &result {
+            Ok(0) => "no new videos".to_string(),
+            Ok(count) => format!("{} new video(s) transcribed", count),
+            Err(e) => e.to_string(),
+        };
+        history::record("sync", Some(name), Some(&outcome), result.is_ok());
+
+        match result {
+            Ok(_) => println!("{}: {}", name, outcome),
+            Err(e) => {
+                eprintln!("{} failed:", name);
+                print_error(&e);
+                failed.push(name);
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(Error::Transcription(format!(
+            "sync failed for {} of {} channel(s): {}",
+            failed.len(),
+            subscriptions.len(),
+            failed.join(", ")
+        )));
+    }
+
+    Ok(())
+}
+
+/// Sync one channel, returning how many videos were transcribed
+///
+/// The marker only advances when every new video succeeded, so failures are retried next time.
+async fn sync_one(subscription: &Subscription, options: &SyncOptions) -> Result<usize> {
+    let videos = fetch_channel_videos(&subscription.url, options.limit)?;
+    let newest = videos.first().map(|v| v.id.clone());
+
+    let mut pending: Vec<&PlaylistEntry> = Vec::new();
+    for video in new_uploads(&videos, subscription.last_video_id.as_deref()) {
+        if get_transcript_by_id(&video.id)?.is_some() {
+            continue;
+        }
+        if !options.durations.matches(video.duration) {
+            println!("  Skipped (duration): {}", video.title);
+            continue;
+        }
+        pending.push(video);
+    }
+
+    if !pending.is_empty() {
+        // Oldest first, so the archive fills in upload order
+        let urls: Vec<&str> = pending.iter().rev().map(|v| v.url.as_str()).collect();
+        run_many(&urls, options.backend, options.captions_lang.as_deref(), options.jobs).await?;
+    }
+
+    mark_subscription_synced(subscription.id, newest.as_deref())?;
+    Ok(pending.len())
+}
+
+/// Videos listed before the last-seen marker (listings are newest first)
+fn new_uploads<'a>(videos: &'a [PlaylistEntry], last_video_id: Option<&str>) -> &'a [PlaylistEntry] {
+    match last_video_id.and_then(|last| videos.iter().position(|v| v.id == last)) {
+        Some(index) => &videos[..index],
+        // Marker missing (first sync after --backfill, or more than `limit` new uploads)
+        None => videos,
+    }
+}
@@ -0,0 +1,426 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::history;
+use crate::commands::reindex::index_local_only;
+use crate::config::{data_dir, database_path, ensure_writable, settings, transcripts_dir, SyncConfig};
+use crate::database::{backup_database, get_stats, keep_replaced_database, library_fingerprint, replace_database};
+use crate::error::{Error, Result};
+use crate::format;
+
+/// Describes the library on the remote; written last by a push, so it only ever names a
+/// complete one
+const MANIFEST_FILE: &str = "sync.json";
+/// Name of the database copy on the remote
+const DATABASE_FILE: &str = "transcripts.db";
+/// Name of the transcripts directory on the remote
+const TRANSCRIPTS_DIR: &str = "transcripts";
+/// What this machine last pushed or pulled, in the data directory
+const STATE_FILE: &str = "sync-state.json";
+/// Layout version written into new manifests
+const SYNC_VERSION: u32 = 1;
+
+/// The library last pushed to the remote
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    /// Changes on every push
+    revision: String,
+    /// Data directory of the machine that pushed it, which stored paths start with
+    data_dir: String,
+    host: String,
+    /// Unix time of the push
+    pushed_at: u64,
+}
+
+/// Where this machine's library stood at its last push or pull
+#[derive(Serialize, Deserialize)]
+struct SyncState {
+    /// Remote revision pushed or pulled
+    revision: String,
+    /// `library_fingerprint` right after it
+    fingerprint: String,
+}
+
+/// Where the shared library is kept
+trait Remote {
+    /// Remote location for status output
+    fn describe(&self) -> String;
+
+    /// Contents of a small file, `None` if it doesn't exist
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>>;
+
+    fn upload(&self, source: &Path, key: &str) -> Result<()>;
+
+    fn download(&self, key: &str, dest: &Path) -> Result<()>;
+
+    /// Copy the files under a local directory up, leaving remote files it lacks alone
+    fn push_dir(&self, source: &Path, key: &str) -> Result<()>;
+
+    /// Copy the files under a remote directory down, leaving local files it lacks alone
+    fn pull_dir(&self, key: &str, dest: &Path) -> Result<()>;
+}
+
+/// A bucket (and prefix) in S3 or an S3-compatible store, through the `aws` CLI
+struct S3Remote {
+    bucket: String,
+    prefix: Option<String>,
+    endpoint: Option<String>,
+    profile: Option<String>,
+}
+
+impl S3Remote {
+    fn uri(&self, key: &str) -> String {
+        match self.prefix.as_deref().map(|p| p.trim_matches('/')) {
+            Some(prefix) if !prefix.is_empty() => format!("s3://{}/{}/{}", self.bucket, prefix, key),
+            _ => format!("s3://{}/{}", self.bucket, key),
+        }
+    }
+
+    fn command(&self, action: &str) -> Command {
+        let mut cmd = Command::new("aws");
+        if let Some(endpoint) = &self.endpoint {
+            cmd.args(["--endpoint-url", endpoint]);
+        }
+        if let Some(profile) = &self.profile {
+            cmd.args(["--profile", profile]);
+        }
+        cmd.args(["s3", action, "--only-show-errors"]);
+        cmd
+    }
+}
+
+impl Remote for S3Remote {
+    fn describe(&self) -> String {
+        self.uri("")
+    }
+
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut cmd = self.command("cp");
+        cmd.args([&self.uri(key), "-"]);
+        read_output(cmd, "aws")
+    }
+
+    fn upload(&self, source: &Path, key: &str) -> Result<()> {
+        let mut cmd = self.command("cp");
+        cmd.arg(source).arg(self.uri(key));
+        run(cmd, "aws")
+    }
+
+    fn download(&self, key: &str, dest: &Path) -> Result<()> {
+        let mut cmd = self.command("cp");
+        cmd.arg(self.uri(key)).arg(dest);
+        run(cmd, "aws")
+    }
+
+    fn push_dir(&self, source: &Path, key: &str) -> Result<()> {
+        let mut cmd = self.command("sync");
+        cmd.args(["--exclude", "*.tmp"]).arg(source).arg(self.uri(key));
+        run(cmd, "aws")
+    }
+
+    fn pull_dir(&self, key: &str, dest: &Path) -> Result<()> {
+        let mut cmd = self.command("sync");
+        cmd.arg(self.uri(key)).arg(dest);
+        run(cmd, "aws")
+    }
+}
+
+/// Any rclone remote (Google Drive, Dropbox, SFTP, S3, ...)
+struct RcloneRemote {
+    remote: String,
+}
+
+impl RcloneRemote {
+    fn path(&self, key: &str) -> String {
+        format!("{}/{}", self.remote.trim_end_matches('/'), key)
+    }
+}
+
+impl Remote for RcloneRemote {
+    fn describe(&self) -> String {
+        self.remote.clone()
+    }
+
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>> {
+        let mut cmd = Command::new("rclone");
+        cmd.arg("cat").arg(self.path(key));
+        read_output(cmd, "rclone")
+    }
+
+    fn upload(&self, source: &Path, key: &str) -> Result<()> {
+        let mut cmd = Command::new("rclone");
+        cmd.arg("copyto").arg(source).arg(self.path(key));
+        run(cmd, "rclone")
+    }
+
+    fn download(&self, key: &str, dest: &Path) -> Result<()> {
+        let mut cmd = Command::new("rclone");
+        cmd.arg("copyto").arg(self.path(key)).arg(dest);
+        run(cmd, "rclone")
+    }
+
+    fn push_dir(&self, source: &Path, key: &str) -> Result<()> {
+        let mut cmd = Command::new("rclone");
+        cmd.args(["copy", "--exclude", "*.tmp"]).arg(source).arg(self.path(key));
+        run(cmd, "rclone")
+    }
+
+    fn pull_dir(&self, key: &str, dest: &Path) -> Result<()> {
+        let mut cmd = Command::new("rclone");
+        cmd.arg("copy").arg(self.path(key)).arg(dest);
+        run(cmd, "rclone")
+    }
+}
+
+/// The remote configured by the `[sync]` table
+fn remote() -> Result<Box<dyn Remote>> {
+    match settings().sync.clone() {
+        Some(SyncConfig::S3 { bucket, prefix, endpoint, profile }) => {
+            Ok(Box::new(S3Remote { bucket, prefix, endpoint, profile }))
+        }
+        Some(SyncConfig::Rclone { remote }) => Ok(Box::new(RcloneRemote { remote })),
+        None => Err(Error::Config(
+            "No remote configured; add a [sync] table to config.toml (backend = \"s3\" or \"rclone\")".to_string(),
+        )),
+    }
+}
+
+/// Upload the library, its database and transcript files, to the `[sync]` remote
+///
+/// Refuses when someone else pushed since this machine last pushed or pulled, since that
+/// would drop their changes, unless `force` is set.
+pub fn push(force: bool) -> Result<()> {
+    let remote = remote()?;
+    let manifest = read_manifest(remote.as_ref())?;
+    let state = read_state();
+    let fingerprint = library_fingerprint()?;
+
+    if let Some(manifest) = &manifest {
+        let synced = state.as_ref().is_some_and(|state| state.revision == manifest.revision);
+        if !synced && !force {
+            return Err(Error::SyncConflict(format!(
+                "{} was pushed from {} {} ago, which this machine hasn't pulled; pull it first, or push \
+                 with --force to replace it",
+                remote.describe(),
+                manifest.host,
+                age(manifest.pushed_at)
+            )));
+        }
+        if synced && state.as_ref().is_some_and(|state| state.fingerprint == fingerprint) && !force {
+            println!("Already up to date: nothing changed since the last sync.");
+            return Ok(());
+        }
+    }
+
+    let transcripts = transcripts_dir();
+    if transcripts.is_dir() {
+        eprintln!("Uploading {}...", transcripts.display());
+        remote.push_dir(&transcripts, TRANSCRIPTS_DIR)?;
+    }
+
+    eprintln!("Uploading the database...");
+    let snapshot = data_dir().join(format!(".sync-push-{}.db", uuid::Uuid::new_v4()));
+    let uploaded = backup_database(&snapshot).and_then(|()| remote.upload(&snapshot, DATABASE_FILE));
+    let _ = fs::remove_file(&snapshot);
+    uploaded?;
+
+    let manifest = Manifest {
+        version: SYNC_VERSION,
+        revision: uuid::Uuid::new_v4().to_string(),
+        data_dir: data_dir().to_string_lossy().to_string(),
+        host: host_name(),
+        pushed_at: now(),
+    };
+    let manifest_file = data_dir().join(format!(".sync-manifest-{}.json", uuid::Uuid::new_v4()));
+    let uploaded = fs::write(&manifest_file, serde_json::to_string_pretty(&manifest)?)
+        .map_err(Error::from)
+        .and_then(|()| remote.upload(&manifest_file, MANIFEST_FILE));
+    let _ = fs::remove_file(&manifest_file);
+    uploaded?;
+
+    write_state(&SyncState { revision: manifest.revision, fingerprint })?;
+    println!("Pushed the library to {}.", remote.describe());
+    history::record("sync-remote", Some("push"), Some(&remote.describe()), true);
+    Ok(())
+}
+
+/// Download the library last pushed to the `[sync]` remote, replacing the local database
+/// and adding its transcript files
+///
+/// Refuses when the local library changed since its last push or pull and the remote
+/// changed too, since pulling would drop the local changes, unless `force` is set.
+pub fn pull(force: bool) -> Result<()> {
+    ensure_writable("pull the library")?;
+    let remote = remote()?;
+    let manifest = read_manifest(remote.as_ref())?
+        .ok_or_else(|| Error::FileNotFound(format!("Nothing pushed to {} yet", remote.describe())))?;
+    if manifest.version > SYNC_VERSION {
+        return Err(Error::Config(format!(
+            "{} was pushed by a newer yt-cli (sync version {}); update with `yt-cli self-update`",
+            remote.describe(),
+            manifest.version
+        )));
+    }
+    let state = read_state();
+    let local_changed = match &state {
+        Some(state) => state.fingerprint != library_fingerprint()?,
+        None => database_path().exists() && get_stats()?.total_transcripts > 0,
+    };
+
+    if state.as_ref().is_some_and(|state| state.revision == manifest.revision) && !force {
+        println!("Already up to date with {}.", remote.describe());
+        if local_changed {
+            println!("This library has changes of its own; `yt-cli sync-remote push` uploads them.");
+        }
+        return Ok(());
+    }
+    if local_changed && !force {
+        let since = if state.is_some() { "since it was last synced" } else { "and was never synced" };
+        return Err(Error::SyncConflict(format!(
+            "this library changed {} and {} holds another (pushed from {} {} ago); pulling would replace \
+             the local database. Push with --force to keep this one, or pull with --force to take the remote's",
+            since,
+            remote.describe(),
+            manifest.host,
+            age(manifest.pushed_at)
+        )));
+    }
+
+    eprintln!("Downloading transcripts...");
+    remote.pull_dir(TRANSCRIPTS_DIR, &transcripts_dir())?;
+
+    eprintln!("Downloading the database...");
+    let download = data_dir().join(format!(".sync-pull-{}.db", uuid::Uuid::new_v4()));
+    if let Err(e) = remote.download(DATABASE_FILE, &download) {
+        let _ = fs::remove_file(&download);
+        return Err(e);
+    }
+    match keep_replaced_database("pull") {
+        Ok(Some(copy)) => println!("Saved the current database to {}.", copy.display()),
+        Ok(None) => {}
+        Err(e) => {
+            let _ = fs::remove_file(&download);
+            return Err(e);
+        }
+    }
+    replace_database(&download, &manifest.data_dir)?;
+    if local_changed {
+        index_local_only()?;
+    }
+
+    write_state(&SyncState { revision: manifest.revision, fingerprint: library_fingerprint()? })?;
+    println!(
+        "Pulled the library from {} (pushed from {} {} ago).",
+        remote.describe(),
+        manifest.host,
+        age(manifest.pushed_at)
+    );
+    history::record_irreversible("sync-remote", Some("pull"), Some(&remote.describe()));
+    Ok(())
+}
+
+/// Show what was last pushed to the remote and whether either side changed since this
+/// machine last synced
+pub fn status() -> Result<()> {
+    let remote = remote()?;
+    let manifest = read_manifest(remote.as_ref())?;
+    let state = read_state();
+
+    println!("Remote: {}", remote.describe());
+    let Some(manifest) = manifest else {
+        println!("Nothing pushed yet; `yt-cli sync-remote push` uploads this library.");
+        return Ok(());
+    };
+    println!("Last push: from {}, {} ago", manifest.host, age(manifest.pushed_at));
+
+    let Some(state) = state else {
+        println!("This machine hasn't synced yet; `yt-cli sync-remote pull` downloads the library.");
+        return Ok(());
+    };
+    let remote_changed = state.revision != manifest.revision;
+    let local_changed = state.fingerprint != library_fingerprint()?;
+    println!(
+        "{}",
+        match (local_changed, remote_changed) {
+            (false, false) => "In sync.",
+            (true, false) => "This library has changes to push.",
+            (false, true) => "The remote has changes to pull.",
+            (true, true) => "Both changed since the last sync; push or pull with --force to keep one of them.",
+        }
+    );
+    Ok(())
+}
+
+fn read_manifest(remote: &dyn Remote) -> Result<Option<Manifest>> {
+    let Some(content) = remote.read(MANIFEST_FILE)? else {
+        return Ok(None);
+    };
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(|e| Error::Storage(format!("Unreadable {} on {}: {}", MANIFEST_FILE, remote.describe(), e)))
+}
+
+fn read_state() -> Option<SyncState> {
+    let content = fs::read_to_string(data_dir().join(STATE_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write_state(state: &SyncState) -> Result<()> {
+    fs::write(data_dir().join(STATE_FILE), serde_json::to_string_pretty(state)?)?;
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default()
+}
+
+/// How long ago a Unix time was, e.g. "2h 5m 0s"
+fn age(unix_time: u64) -> String {
+    format::duration(now().saturating_sub(unix_time) as i64)
+}
+
+fn host_name() -> String {
+    Command::new("hostname")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "an unnamed host".to_string())
+}
+
+fn run(mut cmd: Command, program: &str) -> Result<()> {
+    let output = output(&mut cmd, program)?;
+    if !output.status.success() {
+        return Err(Error::Storage(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(())
+}
+
+/// A command's stdout, `None` if it failed because the file doesn't exist
+fn read_output(mut cmd: Command, program: &str) -> Result<Option<Vec<u8>>> {
+    let output = output(&mut cmd, program)?;
+    if output.status.success() {
+        return Ok(Some(output.stdout));
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let lower = stderr.to_lowercase();
+    if lower.contains("not found") || lower.contains("404") || lower.contains("does not exist") {
+        return Ok(None);
+    }
+    Err(Error::Storage(stderr))
+}
+
+fn output(cmd: &mut Command, program: &str) -> Result<Output> {
+    cmd.stdin(Stdio::null()).output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            Error::Storage(format!("`{}` not found in PATH (required by sync-remote)", program))
+        } else {
+            Error::Io(e)
+        }
+    })
+}
@@ -0,0 +1,47 @@
+use std::path;
+
+use crate::commands::history;
+use crate::commands::read::resolve_path;
+use crate::commands::undo::UndoAction;
+use crate::database::{add_tags, get_tags, remove_tags};
+use crate::error::Result;
+
+/// Add tags to a transcript (e.g. `public`, for `yt-cli publish`), or remove them, then
+/// print the tags it has
+pub fn run(path_or_id: &str, tags: &[String], remove: bool) -> Result<()> {
+    let storage_path = path::absolute(resolve_path(path_or_id)?)?.to_string_lossy().to_string();
+    let tags: Vec<String> = tags
+        .iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    if !tags.is_empty() {
+        if remove {
+            let removed: Vec<String> =
+                get_tags(&storage_path)?.into_iter().filter(|t| tags.contains(t)).collect();
+            println!("Removed {} tag(s).", remove_tags(&storage_path, &tags)?);
+            if removed.is_empty() {
+                history::record("tag", Some(path_or_id), Some(&tags.join(", ")), true);
+            } else {
+                history::record_undoable(
+                    "tag",
+                    Some(path_or_id),
+                    Some(&tags.join(", ")),
+                    &UndoAction::Retag { path: storage_path.clone(), tags: removed },
+                );
+            }
+        } else {
+            println!("Added {} tag(s).", add_tags(&storage_path, &tags)?);
+            history::record("tag", Some(path_or_id), Some(&tags.join(", ")), true);
+        }
+    }
+
+    let current = get_tags(&storage_path)?;
+    if current.is_empty() {
+        println!("No tags.");
+    } else {
+        println!("Tags: {}", current.join(", "));
+    }
+    Ok(())
+}
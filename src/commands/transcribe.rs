@@ -1,56 +1,551 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-use crate::config::{ensure_directories, validate_config};
-use crate::database::{add_transcript, TranscriptMetadata};
-use crate::downloader::download_audio;
-use crate::error::Result;
-use crate::storage::{create_storage_path, get_platform_from_url, move_audio_file, save_metadata, save_transcript};
-use crate::transcriber::{format_transcript_markdown, AssemblyAI};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinSet;
 
-pub async fn run(url: &str) -> Result<()> {
-    validate_config()?;
+use crate::commands::history;
+use crate::commands::resume::{forget_job, transcribe_resumable};
+use crate::config::{ensure_directories, ensure_writable, settings, user_name, LanguageMismatch, NotifyEvent};
+use crate::database::{add_transcript, record_usage, IndexOutcome, TranscriptMetadata};
+use crate::downloader::{download_audio, download_captions, resolve_audio_url, VideoMetadata};
+use crate::episode;
+use crate::error::{print_error, Error, Result};
+use crate::ffmpeg;
+use crate::format;
+use crate::hooks;
+use crate::notify::notify;
+use crate::platform;
+use crate::storage::{
+    create_storage_path, move_audio_file, save_metadata, save_transcript, CAPTIONS_DIR_SUFFIX,
+};
+use crate::transcriber::{
+    chapter_headlines, format_transcript_markdown_sections, parse_captions, split_sentences, AudioSource,
+    Backend, SpellingMap, TranscriptData, TranscriptSource, Transcriber, CAPTIONS_ID_PREFIX,
+};
+
+/// A batch's permission to download and upload, held until the audio is submitted
+pub type Slot = Option<OwnedSemaphorePermit>;
+
+/// Transcribe a URL with `backend`, or import its captions in `captions_lang` instead
+pub async fn run(url: &str, backend: Backend, captions_lang: Option<&str>) -> Result<()> {
+    run_in_slot(url, backend, captions_lang, None).await
+}
+
+/// [`run`], releasing `slot` once the audio is with the transcription service
+pub async fn run_in_slot(url: &str, backend: Backend, captions_lang: Option<&str>, slot: Slot) -> Result<()> {
+    run_url(url, backend, captions_lang, slot, true).await
+}
+
+/// [`run_in_slot`] for a URL `queue add` already rewrote, so `[[rewrite]]` rules and the
+/// `pre_download` hook aren't applied to it a second time
+pub async fn run_queued(url: &str, backend: Backend, captions_lang: Option<&str>, slot: Slot) -> Result<()> {
+    run_url(url, backend, captions_lang, slot, false).await
+}
+
+async fn run_url(
+    url: &str,
+    backend: Backend,
+    captions_lang: Option<&str>,
+    mut slot: Slot,
+    rewrite: bool,
+) -> Result<()> {
+    ensure_writable("transcribe")?;
+
+    let result = transcribe_one(url, backend, captions_lang, &mut slot, rewrite).await;
+    if let Err(e) = &result {
+        history::record("transcribe", Some(url), Some(&e.to_string()), false);
+        notify(NotifyEvent::Failed, "Transcription failed", &e.to_string(), Some(url));
+    }
+    result
+}
+
+async fn transcribe_one(
+    url: &str,
+    backend: Backend,
+    captions_lang: Option<&str>,
+    slot: &mut Slot,
+    rewrite: bool,
+) -> Result<()> {
+    let url = url.to_string();
+    let url = if rewrite { blocking(move || hooks::rewrite_url(&url)).await? } else { url };
+    // The same video shared as youtu.be, /shorts or with tracking parameters is stored once
+    let url = platform::normalize_url(&url);
+    let url = url.as_str();
+
+    match captions_lang {
+        Some(_) if !settings().transcription.redact_pii.is_empty() => Err(Error::Config(
+            "Captions can't be redacted; transcribe without --use-captions to redact PII".to_string(),
+        )),
+        Some(_) if settings().transcription.filter_profanity => Err(Error::Config(
+            "Captions can't be profanity-filtered; transcribe without --use-captions to filter them".to_string(),
+        )),
+        Some(lang) => {
+            let (url, lang) = (url.to_string(), lang.to_string());
+            blocking(move || run_captions(&url, &lang)).await
+        }
+        None => transcribe_audio(url, backend, slot).await,
+    }
+}
+
+async fn transcribe_audio(url: &str, backend: Backend, slot: &mut Slot) -> Result<()> {
+    let mut transcriber = Transcriber::new(backend)?;
     ensure_directories()?;
 
+    let platform = platform::platform_name(url);
+
+    if let Some((metadata, mut transcript_data)) = transcribe_remote(&mut transcriber, url, slot).await? {
+        let storage_path = save_and_index(url, &platform, &metadata, &mut transcript_data, None)?;
+        print_summary(&storage_path, &metadata, &transcript_data);
+        return Ok(());
+    }
+
     eprintln!("Downloading: {}", url);
-    let (audio_file, metadata) = download_audio(url)?;
+    let (audio_file, metadata) = {
+        let url = url.to_string();
+        blocking(move || download_audio(&url)).await?
+    };
     eprintln!("Downloaded: {}", metadata.title);
+    print_source_details(&metadata);
+    let duration = metadata.duration.or_else(|| ffmpeg::duration(&audio_file).ok());
+    if let Err(e) = check_cost(&transcriber, &metadata.title, duration).await {
+        let _ = std::fs::remove_file(&audio_file);
+        return Err(e);
+    }
+    transcriber.set_speakers(speakers_expected(&metadata));
+    transcriber.set_word_boost(settings().boost_words(&metadata.channel, metadata.uploader_id.as_deref()));
+
+    eprintln!("\nTranscribing with {}...", transcriber.name());
+    let source = AudioSource::File(&audio_file);
+    let mut transcript_data =
+        transcribe_resumable(&transcriber, source, url, &platform, &metadata, slot).await?;
+    eprintln!("Transcription complete!");
+    track_usage(&transcriber, &metadata, &transcript_data);
+
+    if let Some(language) = retranscribe_language(&transcriber, &metadata) {
+        transcriber.set_language(Some(language));
+        match transcribe_resumable(&transcriber, source, url, &platform, &metadata, &mut None).await {
+            Ok(retranscribed) => {
+                eprintln!("Transcription complete!");
+                track_usage(&transcriber, &metadata, &retranscribed);
+                forget_job(&transcript_data.id);
+                transcript_data = retranscribed;
+            }
+            Err(e) => eprintln!("Retranscription failed ({}), keeping the first transcript", e),
+        }
+    }
+
+    let storage_path =
+        save_and_index(url, &platform, &metadata, &mut transcript_data, Some(&audio_file))?;
+    print_summary(&storage_path, &metadata, &transcript_data);
+
+    Ok(())
+}
+
+/// Transcribe each URL listed in a file (or stdin for `-`), continuing past failures
+///
+/// Blank lines and lines starting with `#` are skipped. Fails at the end if any URL failed.
+pub async fn run_batch(
+    path: &str,
+    backend: Backend,
+    captions_lang: Option<&str>,
+    jobs: usize,
+) -> Result<()> {
+    ensure_writable("transcribe")?;
+
+    let urls = read_url_list(path)?;
+    if urls.is_empty() {
+        println!("No URLs found in {}.", if path == "-" { "stdin" } else { path });
+        return Ok(());
+    }
+    let urls: Vec<&str> = urls.iter().map(String::as_str).collect();
+
+    let result = run_many(&urls, backend, captions_lang, jobs).await;
+    let outcome = match &result {
+        Ok(()) => format!("{} URL(s) transcribed", urls.len()),
+        Err(e) => e.to_string(),
+    };
+    history::record("batch", Some(path), Some(&outcome), result.is_ok());
+    result
+}
+
+/// URLs listed one per line in a file (or stdin for `-`), skipping blanks and `#` comments
+pub fn read_url_list(path: &str) -> Result<Vec<String>> {
+    let content = if path == "-" {
+        std::io::read_to_string(std::io::stdin())?
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|e| Error::FileNotFound(format!("{}: {}", path, e)))?
+    };
+
+    // Skip links to a video listed earlier in another form
+    let mut seen = HashSet::new();
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter(|line| seen.insert(platform::normalize_url(line)))
+        .map(String::from)
+        .collect())
+}
+
+/// Transcribe URLs, reporting failures in a summary instead of stopping
+///
+/// Up to `jobs` videos download and upload at once. A video gives up its slot as soon as the
+/// service has its audio, so the next one downloads while earlier ones are transcribed.
+/// Downloads also share the yt-dlp limits (`--max-concurrent-fetches`, `--fetch-delay`).
+pub async fn run_many(
+    urls: &[&str],
+    backend: Backend,
+    captions_lang: Option<&str>,
+    jobs: usize,
+) -> Result<()> {
+    let slots = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut tasks = JoinSet::new();
+    // Which video each task transcribes, to report one that panicked
+    let mut task_urls = HashMap::new();
+    for (i, url) in urls.iter().enumerate() {
+        // Videos start in list order, each once a slot is free
+        let slot = slots.clone().acquire_owned().await.ok();
+        let (url, total) = (url.to_string(), urls.len());
+        let captions_lang = captions_lang.map(String::from);
+        let task_url = url.clone();
+        let task = tasks.spawn(async move {
+            eprintln!("\n[{}/{}] {}", i + 1, total, url);
+            let result = run_in_slot(&url, backend, captions_lang.as_deref(), slot).await;
+            if let Err(e) = &result {
+                eprintln!("Failed: {}", url);
+                print_error(e);
+            }
+            (i, url, result)
+        });
+        task_urls.insert(task.id(), (i, task_url));
+    }
+
+    // A panicking video fails on its own rather than taking the rest of the batch with it
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(result) => results.push(result),
+            Err(e) => {
+                let Some((i, url)) = task_urls.remove(&e.id()) else {
+                    continue;
+                };
+                let error = Error::Transcription(format!("job panicked: {}", e));
+                eprintln!("Failed: {}", url);
+                print_error(&error);
+                results.push((i, url, Err(error)));
+            }
+        }
+    }
+    results.sort_by_key(|(i, _, _)| *i);
+    let failures: Vec<(String, Error)> =
+        results.into_iter().filter_map(|(_, url, result)| Some((url, result.err()?))).collect();
+
+    println!("\nBatch complete: {} succeeded, {} failed.", urls.len() - failures.len(), failures.len());
+    for (url, e) in &failures {
+        println!("  FAILED {}: {}", url, e);
+    }
+
+    if !failures.is_empty() {
+        return Err(Error::Transcription(format!(
+            "{} of {} URLs failed",
+            failures.len(),
+            urls.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Run blocking work (yt-dlp, file moves) off the async workers so concurrent jobs keep going
+async fn blocking<T: Send + 'static>(f: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| Error::Transcription(format!("Worker task failed: {}", e)))?
+}
+
+/// Store the video's existing captions as its transcript, without a paid transcription
+fn run_captions(url: &str, lang: &str) -> Result<()> {
+    ensure_directories()?;
+
+    let platform = platform::platform_name(url);
+
+    eprintln!("Fetching captions: {}", url);
+    let (captions_file, metadata) = download_captions(url, lang)?;
+    let captions_file = captions_file.ok_or_else(|| {
+        Error::Transcription(format!(
+            "No '{}' captions available for {} (run without --use-captions to transcribe the audio)",
+            lang, url
+        ))
+    })?;
+    eprintln!("Found: {}", metadata.title);
+    print_source_details(&metadata);
+
+    let transcript_data = parse_captions(
+        &captions_file,
+        &format!("{}{}", CAPTIONS_ID_PREFIX, metadata.id),
+        metadata.duration,
+    );
+    let _ = std::fs::remove_file(&captions_file);
+    let mut transcript_data = TranscriptData {
+        language: Some(lang.to_string()),
+        ..transcript_data?
+    };
+    eprintln!("Converted captions ({} words).", word_count(&transcript_data));
+
+    let storage_path = save_and_index(url, &platform, &metadata, &mut transcript_data, None)?;
+    print_summary(&storage_path, &metadata, &transcript_data);
+
+    Ok(())
+}
+
+/// Let the transcription service fetch the audio itself when the source exposes a public file URL
+///
+/// Only tried for podcasts and unrecognised sites: video platforms serve signed,
+/// IP-bound stream URLs that the service can't fetch. Returns `None` to fall back to
+/// downloading and uploading the audio. Fails only if the estimated cost is refused.
+async fn transcribe_remote(
+    transcriber: &mut Transcriber,
+    url: &str,
+    slot: &mut Slot,
+) -> Result<Option<(VideoMetadata, TranscriptData)>> {
+    if platform::detect(url).is_some_and(|p| p.name() != "podcast") {
+        return Ok(None);
+    }
+
+    let resolved = {
+        let url = url.to_string();
+        blocking(move || resolve_audio_url(&url)).await
+    };
+    let (audio_url, metadata) = match resolved {
+        Ok(Some(resolved)) => resolved,
+        Ok(None) => return Ok(None),
+        Err(e) => {
+            eprintln!("Could not resolve a direct audio URL ({}), downloading instead", e);
+            return Ok(None);
+        }
+    };
+
+    eprintln!("Found: {}", metadata.title);
+    print_source_details(&metadata);
+    check_cost(transcriber, &metadata.title, metadata.duration).await?;
+    transcriber.set_speakers(speakers_expected(&metadata));
+    transcriber.set_word_boost(settings().boost_words(&metadata.channel, metadata.uploader_id.as_deref()));
+
+    let platform = platform::platform_name(url);
+    let source = AudioSource::Url(&audio_url);
+    eprintln!("\nTranscribing with {} (direct URL)...", transcriber.name());
+    match transcribe_resumable(transcriber, source, url, &platform, &metadata, slot).await {
+        Ok(mut transcript_data) => {
+            eprintln!("Transcription complete!");
+            track_usage(transcriber, &metadata, &transcript_data);
+
+            if let Some(language) = retranscribe_language(transcriber, &metadata) {
+                transcriber.set_language(Some(language));
+                match transcribe_resumable(transcriber, source, url, &platform, &metadata, &mut None)
+                    .await
+                {
+                    Ok(retranscribed) => {
+                        eprintln!("Transcription complete!");
+                        track_usage(transcriber, &metadata, &retranscribed);
+                        forget_job(&transcript_data.id);
+                        transcript_data = retranscribed;
+                    }
+                    Err(e) => {
+                        eprintln!("Retranscription failed ({}), keeping the first transcript", e)
+                    }
+                }
+            }
+            Ok(Some((metadata, transcript_data)))
+        }
+        Err(e) => {
+            eprintln!("Direct URL transcription failed ({}), downloading instead", e);
+            Ok(None)
+        }
+    }
+}
+
+/// Held while a question is on the terminal, so concurrent jobs (`--jobs`, `sync`,
+/// `queue run`) ask one at a time and each answer goes to the video it names
+static PROMPT: Mutex<()> = Mutex::new(());
+
+/// Print what transcribing `seconds` of audio of `title` should cost, and refuse when that
+/// is above `max_cost` unless confirmed at the terminal (or `--yes`)
+pub async fn check_cost(transcriber: &Transcriber, title: &str, seconds: Option<i64>) -> Result<()> {
+    let Some(seconds) = seconds else {
+        eprintln!("Estimated cost: unknown (the recording's length isn't known)");
+        return Ok(());
+    };
+    let rate = transcriber.price_per_hour();
+    let cost = seconds as f64 / 3600.0 * rate;
+    eprintln!(
+        "Estimated cost: ${:.2} ({} at ${:.2}/hour, list price)",
+        cost,
+        format::duration(seconds),
+        rate
+    );
+
+    let Some(max_cost) = settings().max_cost.filter(|max| cost > *max) else {
+        return Ok(());
+    };
+    let over_budget = format!("Estimated cost ${:.2} is above the ${:.2} limit", cost, max_cost);
+    if settings().assume_yes {
+        eprintln!("That is above the ${:.2} limit; going ahead (--yes).", max_cost);
+        notify(NotifyEvent::BudgetExceeded, "Over budget", &format!("{}; went ahead (--yes)", over_budget), None);
+        return Ok(());
+    }
+    if io::stdin().is_terminal() && io::stderr().is_terminal() {
+        let question = format!("{}: {}. Transcribe anyway? [y/N] ", title, over_budget);
+        if blocking(move || confirm(&question)).await? {
+            return Ok(());
+        }
+    } else {
+        // Nobody was asked, so whoever is running it unattended is told
+        notify(NotifyEvent::BudgetExceeded, "Over budget", &format!("{}; not transcribed", over_budget), None);
+    }
+    Err(Error::Transcription(format!(
+        "estimated cost ${:.2} is above the ${:.2} limit (raise --max-cost, or --yes to go ahead)",
+        cost, max_cost
+    )))
+}
+
+/// Ask a yes/no question at the terminal, waiting for any other job's question to be answered
+fn confirm(question: &str) -> Result<bool> {
+    let _prompt = PROMPT.lock().unwrap_or_else(|e| e.into_inner());
+    eprint!("{}", question);
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_lowercase().starts_with('y'))
+}
+
+/// Speaker count hint for a video: `--speakers`, else its channel's `speakers` setting
+pub fn speakers_expected(metadata: &VideoMetadata) -> Option<u32> {
+    settings().speakers_expected(&metadata.channel, metadata.uploader_id.as_deref())
+}
+
+/// Compare the platform's language for the video with the one it was transcribed in
+///
+/// Warns on a mismatch and returns the video's language when `language_mismatch` asks
+/// for a retranscription.
+fn retranscribe_language(transcriber: &Transcriber, metadata: &VideoMetadata) -> Option<String> {
+    // The service already picked the language it heard
+    if transcriber.detects_language() {
+        return None;
+    }
+    let detected = primary_language(metadata.language.as_deref()?);
+    // Both services fall back to English when no language is given
+    let requested = primary_language(transcriber.language().unwrap_or("en"));
+    if detected.is_empty() || detected == requested {
+        return None;
+    }
+
+    eprintln!(
+        "\nWarning: the video's language is '{}' but it was transcribed as '{}'.",
+        detected, requested
+    );
+    if settings().language_mismatch == LanguageMismatch::Retranscribe {
+        eprintln!("Retranscribing in '{}' with {}...", detected, transcriber.name());
+        return Some(detected);
+    }
+    eprintln!(
+        "The transcript may be garbled; rerun with --language {} (or set language_mismatch = \"retranscribe\" in config.toml).",
+        detected
+    );
+    None
+}
+
+/// "es-419" / "en_us" -> "es" / "en"
+fn primary_language(code: &str) -> String {
+    code.split(['-', '_']).next().unwrap_or_default().trim().to_lowercase()
+}
+
+/// Record billed audio seconds for `yt-cli account`; failures only warn
+pub fn track_usage(transcriber: &Transcriber, metadata: &VideoMetadata, transcript_data: &TranscriptData) {
+    let seconds = transcript_data.audio_duration.or(metadata.duration).unwrap_or(0);
+    let cost = seconds as f64 / 3600.0 * transcriber.price_per_hour();
+    if let Err(e) = record_usage(transcriber.name(), &metadata.id, seconds, cost) {
+        eprintln!("Warning: could not record usage: {}", e);
+    }
+}
+
+fn print_source_details(metadata: &VideoMetadata) {
     eprintln!("Channel: {}", metadata.channel);
+    if let Some(series) = &metadata.series {
+        eprintln!("Show: {}", series);
+    }
+    if let Some(episode_number) = metadata.episode_number {
+        eprintln!("Episode: {}", episode_number);
+    }
     if let Some(duration) = metadata.duration {
         eprintln!("Duration: {}s", duration);
     }
+}
 
-    eprintln!("\nTranscribing with AssemblyAI...");
-    let assemblyai = AssemblyAI::new()?;
-    let transcript_data = assemblyai.transcribe(&audio_file).await?;
-    eprintln!("Transcription complete!");
+/// Save transcript files (moving the audio in, if any) and index them in the database
+///
+/// Corrections from `[spelling]` in config.toml are applied to `transcript_data` first. The job
+/// is kept for `resume` until this succeeds.
+pub fn save_and_index(
+    url: &str,
+    platform: &str,
+    metadata: &VideoMetadata,
+    transcript_data: &mut TranscriptData,
+    audio_file: Option<&Path>,
+) -> Result<PathBuf> {
+    let spelling = SpellingMap::new(&settings().spelling)?;
+    if !spelling.is_empty() {
+        spelling.apply(transcript_data);
+    }
 
-    // Create storage path using video ID
-    let platform = get_platform_from_url(url);
-    let storage_path = create_storage_path(&platform, &metadata.channel, &metadata.id)?;
+    let mut metadata = VideoMetadata {
+        transcribed_by: user_name().map(String::from),
+        ..metadata.clone()
+    };
+    episode::fill_in(&mut metadata);
+    let metadata = &metadata;
 
-    // Move audio and save files
-    move_audio_file(&audio_file, &storage_path)?;
-    let markdown = format_transcript_markdown(&transcript_data);
-    save_transcript(&storage_path, &markdown, &transcript_data)?;
-    save_metadata(&storage_path, &metadata)?;
+    // Create storage path using video ID; captions get their own directory, so importing
+    // them never replaces a transcript from the API
+    let source = TranscriptSource::of(transcript_data);
+    let dir_name = match source {
+        TranscriptSource::Captions => format!("{}{}", metadata.id, CAPTIONS_DIR_SUFFIX),
+        TranscriptSource::Api => metadata.id.clone(),
+    };
+    let storage_path = create_storage_path(platform, metadata, &dir_name)?;
 
-    // Index in database with full metadata
-    let speaker_count = transcript_data
-        .utterances
-        .iter()
-        .map(|u| &u.speaker)
-        .collect::<HashSet<_>>()
-        .len() as i32;
-    let word_count = transcript_data.text.split_whitespace().count() as i32;
+    // Move audio in and save files; with `keep_audio = false` the audio is only dropped once
+    // the transcript is saved, so a failed save can still be retried from it
+    let keep_audio = settings().keeps_audio(platform);
+    if let Some(audio_file) = audio_file
+        && keep_audio
+    {
+        move_audio_file(audio_file, &storage_path)?;
+    }
+    let missing_tail = transcript_data.missing_tail(metadata.duration);
+    if let Some(missing) = missing_tail {
+        eprintln!(
+            "Warning: the transcript stops {} before the end of the audio; the download or transcription \
+             may have been cut short. Flagged in `yt-cli list --incomplete`; `yt-cli retranscribe {}` redoes it.",
+            format::duration(missing),
+            metadata.id
+        );
+    }
+    let markdown = format_transcript_markdown_sections(transcript_data, &metadata.sections());
+    save_transcript(&storage_path, &markdown, transcript_data)?;
+    save_metadata(&storage_path, metadata)?;
 
-    add_transcript(&TranscriptMetadata {
+    // Index in database with full metadata
+    let outcome = add_transcript(&TranscriptMetadata {
         video_id: &metadata.id,
         url,
         title: &metadata.title,
         channel: &metadata.channel,
         channel_handle: metadata.uploader_id.as_deref(),
         channel_id: None, // TODO: capture actual channel ID from yt-dlp
-        platform: &platform,
+        platform,
         duration: metadata.duration,
         upload_date: metadata.upload_date.as_deref(),
         description: metadata.description.as_deref(),
@@ -58,18 +553,65 @@ pub async fn run(url: &str) -> Result<()> {
         view_count: metadata.view_count,
         like_count: metadata.like_count,
         path: &storage_path.to_string_lossy(),
-        speaker_count,
-        word_count,
+        speaker_count: speaker_count(transcript_data),
+        word_count: word_count(transcript_data),
         confidence: transcript_data.confidence,
         transcript_text: &transcript_data.text,
+        chapters: &chapter_headlines(transcript_data),
+        sentences: &split_sentences(transcript_data),
+        highlights: &transcript_data.highlights,
+        entities: &transcript_data.entities,
+        source: source.id(),
+        transcribed_by: metadata.transcribed_by.as_deref(),
+        language: transcript_data.language.as_deref(),
+        missing_tail,
+        transcribed_now: true,
     })?;
-    eprintln!("Indexed in database.");
+    match outcome {
+        IndexOutcome::Complete => eprintln!("Indexed in database."),
+        IndexOutcome::Pending(e) => eprintln!(
+            "Warning: saved, but not searchable yet ({}). Run `yt-cli reindex --pending` to finish indexing.",
+            e
+        ),
+    }
+    if let Some(audio_file) = audio_file
+        && !keep_audio
+        && let Err(e) = std::fs::remove_file(audio_file)
+    {
+        eprintln!("Warning: could not remove {}: {}", audio_file.display(), e);
+    }
+    forget_job(&transcript_data.id);
+    history::record("transcribe", Some(&metadata.id), Some(&metadata.title), true);
+    hooks::post_transcribe(&storage_path, url, platform, metadata);
+    notify(
+        NotifyEvent::Completed,
+        &format!("Transcribed: {}", metadata.title),
+        &format!("{} ({})", metadata.channel, storage_path.display()),
+        Some(url),
+    );
 
-    // Output result
-    let duration = transcript_data.audio_duration.unwrap_or(0);
-    let mins = duration / 60;
-    let secs = duration % 60;
+    Ok(storage_path)
+}
 
+fn speaker_count(transcript_data: &TranscriptData) -> i32 {
+    transcript_data
+        .utterances
+        .iter()
+        .map(|u| &u.speaker)
+        .collect::<HashSet<_>>()
+        .len() as i32
+}
+
+fn word_count(transcript_data: &TranscriptData) -> i32 {
+    transcript_data.text.split_whitespace().count() as i32
+}
+
+/// Print the result block shown after a successful transcription
+pub fn print_summary(storage_path: &Path, metadata: &VideoMetadata, transcript_data: &TranscriptData) {
+    // Cut at a character, not a byte, so text in any script can be previewed
+    let text = &transcript_data.text;
+    let cut = text.char_indices().nth(500).map(|(i, _)| i);
+    let preview = cut.map_or(text.as_str(), |i| &text[..i]);
     println!(
         r#"
 Transcription complete!
@@ -78,7 +620,8 @@ Path: {}
 Video ID: {}
 Title: {}
 Channel: {}
-Duration: {}m {}s
+Duration: {}
+Language: {}
 Words: {}
 Speakers: {}
 
@@ -88,13 +631,11 @@ Preview (first 500 chars):
         metadata.id,
         metadata.title,
         metadata.channel,
-        mins,
-        secs,
-        word_count,
-        speaker_count,
-        &transcript_data.text[..transcript_data.text.len().min(500)],
-        if transcript_data.text.len() > 500 { "..." } else { "" }
+        format::duration(transcript_data.audio_duration.unwrap_or(0)),
+        transcript_data.language.as_deref().unwrap_or("unknown"),
+        format::number(word_count(transcript_data) as i64),
+        speaker_count(transcript_data),
+        preview,
+        if cut.is_some() { "..." } else { "" }
     );
-
-    Ok(())
 }
@@ -0,0 +1,110 @@
+use crate::commands::history;
+use crate::commands::resume::transcribe_resumable;
+use crate::commands::transcribe::{check_cost, print_summary, save_and_index, track_usage};
+use crate::config::{ensure_directories, ensure_writable, settings, NotifyEvent};
+use crate::downloader::VideoMetadata;
+use crate::error::{Error, Result};
+use crate::ffmpeg;
+use crate::notify::notify;
+use crate::platform;
+use crate::storage::sanitize_filename;
+use crate::transcriber::{AudioSource, Backend, Transcriber};
+
+/// Platform name used for audio transcribed straight from a URL
+const DIRECT_PLATFORM: &str = "direct";
+
+pub async fn run(
+    audio_url: &str,
+    title: Option<&str>,
+    channel: Option<&str>,
+    backend: Backend,
+) -> Result<()> {
+    ensure_writable("transcribe")?;
+
+    let result = transcribe(audio_url, title, channel, backend).await;
+    if let Err(e) = &result {
+        history::record("transcribe-url", Some(audio_url), Some(&e.to_string()), false);
+        notify(NotifyEvent::Failed, "Transcription failed", &e.to_string(), Some(audio_url));
+    }
+    result
+}
+
+async fn transcribe(
+    audio_url: &str,
+    title: Option<&str>,
+    channel: Option<&str>,
+    backend: Backend,
+) -> Result<()> {
+    let mut transcriber = Transcriber::new(backend)?;
+    ensure_directories()?;
+
+    if !audio_url.starts_with("http://") && !audio_url.starts_with("https://") {
+        return Err(Error::Config(format!("Not an http(s) URL: {}", audio_url)));
+    }
+
+    let metadata = direct_metadata(audio_url, title, channel);
+    eprintln!("Title: {}", metadata.title);
+    eprintln!("Channel: {}", metadata.channel);
+    transcriber.set_speakers(settings().speakers_expected(&metadata.channel, None));
+    transcriber.set_word_boost(settings().boost_words(&metadata.channel, None));
+    check_cost(&transcriber, &metadata.title, ffmpeg::duration(audio_url).ok()).await?;
+
+    // The service fetches the audio itself, so nothing is downloaded or uploaded locally
+    eprintln!("\nTranscribing with {} (direct URL)...", transcriber.name());
+    let source = AudioSource::Url(audio_url);
+    let mut transcript_data =
+        transcribe_resumable(&transcriber, source, audio_url, DIRECT_PLATFORM, &metadata, &mut None)
+            .await?;
+    eprintln!("Transcription complete!");
+    track_usage(&transcriber, &metadata, &transcript_data);
+
+    let storage_path =
+        save_and_index(audio_url, DIRECT_PLATFORM, &metadata, &mut transcript_data, None)?;
+    print_summary(&storage_path, &metadata, &transcript_data);
+
+    Ok(())
+}
+
+/// Build metadata for a bare audio URL: file name as title, host as channel
+fn direct_metadata(audio_url: &str, title: Option<&str>, channel: Option<&str>) -> VideoMetadata {
+    let file_name = audio_url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').find(|s| !s.is_empty()))
+        .unwrap_or("audio");
+    let stem = file_name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(file_name);
+
+    // File names like "episode.mp3" repeat across feeds, so suffix a hash of the URL
+    let id = format!("{}-{:08x}", sanitize_filename(stem, 40), fnv1a(audio_url));
+
+    VideoMetadata {
+        id,
+        title: title.unwrap_or(stem).to_string(),
+        channel: channel.map(String::from).unwrap_or_else(|| platform::domain(audio_url)),
+        uploader: None,
+        uploader_id: None,
+        duration: None,
+        upload_date: None,
+        description: None,
+        view_count: None,
+        like_count: None,
+        thumbnail: None,
+        url: audio_url.to_string(),
+        webpage_url: None,
+        extractor: None,
+        chapters: Vec::new(),
+        series: None,
+        episode: None,
+        episode_number: None,
+        season_number: None,
+        transcribed_by: None,
+        language: None,
+    }
+}
+
+/// 32-bit FNV-1a hash (stable across builds, unlike `DefaultHasher`)
+fn fnv1a(input: &str) -> u32 {
+    input.bytes().fold(0x811c9dc5u32, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    })
+}
@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commands::history;
+use crate::commands::reindex::index_video_dir;
+use crate::commands::speakers;
+use crate::config::ensure_writable;
+use crate::database::{add_subscription, add_tags, last_undoable_activity, mark_activity_undone};
+use crate::error::{Error, Result};
+use crate::format;
+
+/// How to reverse a destructive operation, stored as JSON in the activity log
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum UndoAction {
+    /// Restore a removed channel subscription, including its sync marker
+    Resubscribe {
+        url: String,
+        name: Option<String>,
+        last_video_id: Option<String>,
+    },
+    /// Put a deleted video's transcript directories back and index them again
+    Restore {
+        video_id: String,
+        directories: Vec<RestoreDir>,
+    },
+    /// Give a speaker back the name it had before `speakers set` (its label for none)
+    RenameSpeaker {
+        path: String,
+        label: String,
+        previous: String,
+    },
+    /// Add back tags removed by `tag --remove`
+    Retag { path: String, tags: Vec<String> },
+    /// An operation that can't be reversed (e.g. `prune-audio`), so `undo` stops at it
+    /// rather than reaching past it to an older one
+    Irreversible,
+}
+
+/// A transcript directory removed by `yt-cli delete`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RestoreDir {
+    /// Where it was in the library
+    pub path: String,
+    /// Where it is in the trash (`None` when `--keep-files` left it in place)
+    pub trashed: Option<String>,
+    pub tags: Vec<String>,
+}
+
+/// Reverse the most recent destructive operation that hasn't been undone yet
+pub fn run() -> Result<()> {
+    ensure_writable("undo")?;
+
+    let Some((entry, json)) = last_undoable_activity()? else {
+        println!("Nothing to undo.");
+        return Ok(());
+    };
+
+    let action: UndoAction = serde_json::from_str(&json).map_err(|e| {
+        Error::Config(format!("Cannot undo '{}' (unrecognized undo data: {})", entry.action, e))
+    })?;
+
+    let description = match &action {
+        UndoAction::Resubscribe { url, name, last_video_id } => {
+            let name = name.as_deref().unwrap_or(url);
+            if !add_subscription(url, Some(name), last_video_id.as_deref())? {
+                return Err(Error::Config(format!(
+                    "Cannot undo '{}': already subscribed to {} again",
+                    entry.action, name
+                )));
+            }
+            format!("resubscribed to {}", name)
+        }
+        UndoAction::Restore { video_id, directories } => {
+            restore(&entry.action, directories)?;
+            format!("restored {}", video_id)
+        }
+        UndoAction::RenameSpeaker { path, label, previous } => {
+            speakers::rename(Path::new(path), label, previous)?;
+            if previous == label {
+                format!("speaker {} has no name again", label)
+            } else {
+                format!("speaker {} is {} again", label, previous)
+            }
+        }
+        UndoAction::Retag { path, tags } => {
+            add_tags(path, tags)?;
+            format!("tagged {} again", tags.join(", "))
+        }
+        UndoAction::Irreversible => {
+            return Err(Error::Config(format!(
+                "Cannot undo '{}' from {}: it can't be reversed, and older operations aren't undone past it",
+                entry.action,
+                format::timestamp(&entry.created_at)
+            )));
+        }
+    };
+
+    mark_activity_undone(entry.id)?;
+    println!("Undid {} from {}: {}.", entry.action, format::timestamp(&entry.created_at), description);
+    history::record("undo", Some(&entry.action), Some(&description), true);
+
+    Ok(())
+}
+
+/// Move trashed directories back to where they were, then index them and reapply their tags
+fn restore(action: &str, directories: &[RestoreDir]) -> Result<()> {
+    for dir in directories {
+        let path = Path::new(&dir.path);
+        match &dir.trashed {
+            Some(trashed) if path.exists() => {
+                return Err(Error::Config(format!(
+                    "Cannot undo '{}': {} exists again (the deleted copy is still in {})",
+                    action,
+                    path.display(),
+                    trashed
+                )));
+            }
+            Some(trashed) if !Path::new(trashed).exists() => {
+                return Err(Error::FileNotFound(format!(
+                    "Cannot undo '{}': {} is no longer in the trash",
+                    action, trashed
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    for dir in directories {
+        let path = Path::new(&dir.path);
+        if let Some(trashed) = &dir.trashed {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(trashed, path)?;
+            // The video's trash entry, once empty
+            if let Some(entry) = Path::new(trashed).parent() {
+                let _ = fs::remove_dir(entry);
+            }
+        }
+        if path.join("transcript.json").exists() {
+            index_video_dir(path)?;
+            add_tags(&dir.path, &dir.tags)?;
+        }
+    }
+    Ok(())
+}
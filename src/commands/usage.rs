@@ -0,0 +1,91 @@
+use crate::database::{usage_by_month, usage_entries, UsageSummary};
+use crate::error::{Error, Result};
+use crate::format;
+
+/// Report audio transcribed and its estimated cost per month and backend
+///
+/// With `month` (`YYYY-MM`), only that month is summarized and each transcription in it
+/// is listed. Months are calendar months in UTC; costs are estimates at the list price
+/// when each transcription was recorded (older rows have none).
+pub fn run(month: Option<&str>) -> Result<()> {
+    if let Some(month) = month {
+        validate_month(month)?;
+    }
+
+    let summaries = usage_by_month(month)?;
+    if summaries.is_empty() {
+        match month {
+            Some(month) => println!("Nothing transcribed in {}.", month),
+            None => println!("Nothing transcribed yet."),
+        }
+        return Ok(());
+    }
+
+    println!("{:<8} {:<11} {:>11} {:>12} {:>10}", "Month", "Backend", "Transcripts", "Audio", "Est. cost");
+    for summary in &summaries {
+        print_row(&summary.month, &summary.backend, summary);
+    }
+
+    if summaries.len() > 1 {
+        let total = UsageSummary {
+            month: String::new(),
+            backend: String::new(),
+            transcripts: summaries.iter().map(|s| s.transcripts).sum(),
+            seconds: summaries.iter().map(|s| s.seconds).sum(),
+            cost: summaries.iter().filter_map(|s| s.cost).reduce(|a, b| a + b),
+        };
+        print_row("Total", "", &total);
+    }
+
+    let Some(month) = month else {
+        return Ok(());
+    };
+
+    println!("\nTranscriptions");
+    println!("==============");
+    for entry in usage_entries(month)? {
+        let video = entry.title.or(entry.video_id).unwrap_or_else(|| "(unknown video)".to_string());
+        println!(
+            "{}  {:<11} {:>12} {:>10}  {}",
+            format::timestamp(&entry.recorded_at),
+            entry.backend,
+            format::duration(entry.seconds),
+            format_cost(entry.cost),
+            video
+        );
+    }
+
+    Ok(())
+}
+
+fn print_row(month: &str, backend: &str, summary: &UsageSummary) {
+    println!(
+        "{:<8} {:<11} {:>11} {:>12} {:>10}",
+        month,
+        backend,
+        summary.transcripts,
+        format::duration(summary.seconds),
+        format_cost(summary.cost)
+    );
+}
+
+fn format_cost(cost: Option<f64>) -> String {
+    cost.map(|c| format!("${:.2}", c)).unwrap_or_else(|| "-".to_string())
+}
+
+/// `--month` must be `YYYY-MM`, the form the usage table is grouped by
+fn validate_month(month: &str) -> Result<()> {
+    let valid = match month.split_once('-') {
+        Some((year, mm)) => {
+            year.len() == 4
+                && year.chars().all(|c| c.is_ascii_digit())
+                && mm.len() == 2
+                && mm.parse::<u32>().is_ok_and(|m| (1..=12).contains(&m))
+        }
+        None => false,
+    };
+    if !valid {
+        return Err(Error::Config(format!("Invalid month '{}': expected YYYY-MM, e.g. 2025-01", month)));
+    }
+    Ok(())
+}
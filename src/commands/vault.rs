@@ -0,0 +1,244 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::transcripts_dir;
+use crate::database::{list_all_transcripts, TranscriptFilter, TranscriptRecord};
+use crate::error::{Error, Result};
+use crate::storage::get_transcript;
+use crate::transcriber::{format_timestamp, format_transcript_markdown, html_lang, is_rtl, mark_direction};
+
+/// Records which note each transcript was written to, for `--sync`
+const MANIFEST_FILE: &str = ".yt-cli-vault.json";
+/// Longest note name, leaving room for a video ID suffix within filesystem limits
+const MAX_NAME_CHARS: usize = 120;
+
+/// Notes written by earlier exports, keyed by transcript directory (relative to the library)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    notes: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    /// Note path relative to the vault
+    note: String,
+    /// Newest modification time of the transcript's files when the note was written
+    modified: u64,
+}
+
+/// Write one markdown note per transcript into an Obsidian vault
+///
+/// With `sync`, transcripts whose files haven't changed since the last export are skipped,
+/// so edits made to their notes in Obsidian are kept.
+pub fn run(dir: &str, platform: Option<&str>, channel: Option<&str>, sync: bool) -> Result<()> {
+    // A negative LIMIT is no limit in SQLite
+    let mut transcripts = list_all_transcripts(platform, channel, None, &TranscriptFilter::default(), -1)?;
+    if transcripts.is_empty() {
+        return Err(Error::FileNotFound(
+            "No indexed transcripts matched the selection (run `yt-cli reindex` first?)".to_string(),
+        ));
+    }
+    // Oldest first, so the older of two same-titled videos keeps the plain name
+    transcripts.sort_by(|a, b| a.upload_date.cmp(&b.upload_date).then(a.path.cmp(&b.path)));
+
+    let vault = PathBuf::from(dir);
+    fs::create_dir_all(&vault)?;
+    let manifest_path = vault.join(MANIFEST_FILE);
+    let mut manifest: Manifest = fs::read_to_string(&manifest_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+
+    // Note names in use (lowercased for case-insensitive filesystems) and their transcripts
+    let mut taken: HashMap<String, String> = manifest
+        .notes
+        .iter()
+        .map(|(key, entry)| (entry.note.to_lowercase(), key.clone()))
+        .collect();
+
+    let (mut written, mut unchanged) = (0, 0);
+    for t in &transcripts {
+        let key = manifest_key(&t.path);
+        let note = note_path(t, &key, &taken);
+        taken.insert(note.to_lowercase(), key.clone());
+
+        let modified = last_modified(Path::new(&t.path));
+        let previous = manifest.notes.get(&key);
+        if sync
+            && previous.is_some_and(|p| p.note == note && p.modified >= modified)
+            && vault.join(&note).exists()
+        {
+            unchanged += 1;
+            continue;
+        }
+
+        let content = match render_note(t) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Skipping {}: {}", t.path, e);
+                continue;
+            }
+        };
+
+        let note_file = vault.join(&note);
+        if let Some(parent) = note_file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&note_file, content)?;
+
+        // The title changed since the last export: drop the note under the old name
+        if let Some(previous) = previous.filter(|p| p.note != note) {
+            let _ = fs::remove_file(vault.join(&previous.note));
+        }
+
+        manifest.notes.insert(key, ManifestEntry { note, modified });
+        written += 1;
+    }
+
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+
+    if sync {
+        println!("Wrote {} note(s) to {} ({} unchanged).", written, vault.display(), unchanged);
+    } else {
+        println!("Wrote {} note(s) to {}.", written, vault.display());
+    }
+
+    Ok(())
+}
+
+/// `Channel/Title.md`, or `Channel/Title (VIDEO_ID).md` when another transcript has the name
+fn note_path(t: &TranscriptRecord, key: &str, taken: &HashMap<String, String>) -> String {
+    let folder = note_name(&t.channel).unwrap_or_else(|| "Unknown".to_string());
+    let title = note_name(&t.title).unwrap_or_else(|| t.video_id.clone());
+
+    let plain = format!("{}/{}.md", folder, title);
+    match taken.get(&plain.to_lowercase()) {
+        Some(owner) if owner != key => format!("{}/{} ({}).md", folder, title, t.video_id),
+        _ => plain,
+    }
+}
+
+/// A file name Obsidian can link to: no characters that break `[[wiki links]]` or paths
+fn note_name(title: &str) -> Option<String> {
+    let cleaned: String = title
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | '#' | '^' | '|' | '\\' | '/' | ':' | '*' | '?' | '"' | '<' | '>' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect();
+    let name = cleaned
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_start_matches('.')
+        .chars()
+        .take(MAX_NAME_CHARS)
+        .collect::<String>()
+        .trim()
+        .to_string();
+
+    (!name.is_empty()).then_some(name)
+}
+
+/// A transcript as markdown with YAML frontmatter (title, channel, date, ...)
+pub fn render_note(t: &TranscriptRecord) -> Result<String> {
+    let content = get_transcript(&t.path)?;
+    let language = content.structured.as_ref().and_then(|data| data.language.clone());
+    let body = match (content.text, content.structured) {
+        (Some(text), _) => text,
+        (None, Some(data)) => format_transcript_markdown(&data),
+        (None, None) => String::new(),
+    };
+
+    let mut note = String::from("---\n");
+    let _ = writeln!(note, "title: {}", yaml_string(&t.title));
+    let _ = writeln!(note, "channel: {}", yaml_string(&t.channel));
+    if let Some(url) = &t.url {
+        let _ = writeln!(note, "url: {}", yaml_string(url));
+    }
+    if let Some(date) = t.upload_date.as_deref().and_then(iso_date) {
+        let _ = writeln!(note, "date: {}", date);
+    }
+    if let Some(duration) = t.duration {
+        let _ = writeln!(note, "duration: {}", yaml_string(&format_timestamp(duration * 1000)));
+    }
+    let _ = writeln!(note, "video_id: {}", yaml_string(&t.video_id));
+    let _ = writeln!(note, "platform: {}", yaml_string(&t.platform));
+    if let Some(language) = &language {
+        let _ = writeln!(note, "lang: {}", yaml_string(&html_lang(language)));
+        if is_rtl(language) {
+            note.push_str("dir: rtl\n");
+        }
+    }
+    note.push_str("tags:\n");
+    for tag in tags(t) {
+        let _ = writeln!(note, "  - {}", tag);
+    }
+    note.push_str("---\n\n");
+
+    let mut content = format!("# {}\n\n", t.title);
+    if let Some(url) = &t.url {
+        let _ = writeln!(content, "[Watch on {}]({})\n", t.platform, url);
+    }
+    content.push_str(body.trim());
+    note.push_str(&mark_direction(&content, language.as_deref()));
+    note.push('\n');
+
+    Ok(note)
+}
+
+/// `yt-cli`, the platform and `channel/<slug>` (Obsidian tags can't contain spaces)
+fn tags(t: &TranscriptRecord) -> Vec<String> {
+    let mut tags = vec!["yt-cli".to_string(), slug(&t.platform)];
+    let channel = slug(&t.channel);
+    if !channel.is_empty() {
+        tags.push(format!("channel/{}", channel));
+    }
+    tags
+}
+
+fn slug(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// JSON strings are valid double-quoted YAML scalars, escapes included
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "\"\"".to_string())
+}
+
+/// yt-dlp's YYYYMMDD as YYYY-MM-DD, which Obsidian reads as a date property
+fn iso_date(date: &str) -> Option<String> {
+    (date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()))
+        .then(|| format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..]))
+}
+
+/// A transcript directory relative to the library
+pub fn manifest_key(path: &str) -> String {
+    let path = Path::new(path);
+    path.strip_prefix(transcripts_dir())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Newest modification time (seconds) of the files in a transcript directory
+pub fn last_modified(dir: &Path) -> u64 {
+    ["transcript.md", "transcript.json", "metadata.json"]
+        .iter()
+        .filter_map(|name| fs::metadata(dir.join(name)).and_then(|m| m.modified()).ok())
+        .filter_map(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .max()
+        .unwrap_or(0)
+}
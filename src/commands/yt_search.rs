@@ -1,5 +1,6 @@
 use crate::downloader::{search_youtube, PlaylistEntry};
 use crate::error::Result;
+use crate::format;
 
 pub fn run(query: &str, limit: usize) -> Result<()> {
     eprintln!("Searching YouTube for: {}", query);
@@ -31,33 +32,16 @@ fn print_search_result(index: usize, video: &PlaylistEntry) {
         .map(|c| format!(" - {}", c))
         .unwrap_or_default();
 
-    let duration_str = video
-        .duration
-        .map(|d| {
-            let mins = d / 60;
-            let secs = d % 60;
-            format!(" ({}:{:02})", mins, secs)
-        })
-        .unwrap_or_default();
+    let duration_str = video.duration.map(|d| format!(" ({})", format::clock(d))).unwrap_or_default();
 
     println!("{}. {}{}{}", index, video.title, channel_str, duration_str);
 
     // View count
     if let Some(views) = video.view_count {
-        println!("   {}", format_view_count(views));
+        println!("   {} views", format::compact_number(views));
     }
 
     // URL
     println!("   {}", video.url);
     println!();
 }
-
-fn format_view_count(views: i64) -> String {
-    if views >= 1_000_000 {
-        format!("{:.1}M views", views as f64 / 1_000_000.0)
-    } else if views >= 1_000 {
-        format!("{:.1}K views", views as f64 / 1_000.0)
-    } else {
-        format!("{} views", views)
-    }
-}
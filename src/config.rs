@@ -1,20 +1,39 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::OnceLock;
+use std::time::Duration;
+
+use clap::ValueEnum;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, Result};
+use crate::transcriber::Backend;
+
+/// Get the directory holding config.toml and .env (YT_TRANSCRIBE_DATA_DIR, else ~/.yt-transcribe/)
+pub fn config_dir() -> PathBuf {
+    std::env::var("YT_TRANSCRIBE_DATA_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| default_config_dir())
+}
+
+/// The default config directory (~/.yt-transcribe/)
+pub fn default_config_dir() -> PathBuf {
+    dirs::home_dir()
+        .expect("Could not determine home directory")
+        .join(".yt-transcribe")
+}
 
 static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
 
-/// Get the base data directory (~/.yt-transcribe/)
+/// Get the base data directory (the config directory unless `data_dir` moves it in config.toml)
 pub fn data_dir() -> &'static PathBuf {
     DATA_DIR.get_or_init(|| {
-        std::env::var("YT_TRANSCRIBE_DATA_DIR")
-            .map(PathBuf::from)
-            .unwrap_or_else(|_| {
-                dirs::home_dir()
-                    .expect("Could not determine home directory")
-                    .join(".yt-transcribe")
-            })
+        if std::env::var_os("YT_TRANSCRIBE_DATA_DIR").is_some() {
+            return config_dir();
+        }
+        settings().data_dir.clone().unwrap_or_else(config_dir)
     })
 }
 
@@ -28,6 +47,11 @@ pub fn downloads_dir() -> PathBuf {
     data_dir().join(".downloads")
 }
 
+/// Get the trash directory, holding deleted transcripts until `yt-cli undo` restores them
+pub fn trash_dir() -> PathBuf {
+    data_dir().join(".trash")
+}
+
 /// Get the database path
 pub fn database_path() -> PathBuf {
     data_dir().join("transcripts.db")
@@ -35,7 +59,606 @@ pub fn database_path() -> PathBuf {
 
 /// Get the .env file path
 pub fn env_file_path() -> PathBuf {
-    data_dir().join(".env")
+    config_dir().join(".env")
+}
+
+/// Get the config.toml path
+pub fn config_file_path() -> PathBuf {
+    config_dir().join("config.toml")
+}
+
+/// Settings read from config.toml in the data directory
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// Where the database, transcripts and downloads live (ignored when YT_TRANSCRIBE_DATA_DIR is set)
+    pub data_dir: Option<PathBuf>,
+    /// Transcription service used when `--backend` isn't given
+    pub backend: Backend,
+    /// Whether yt-dlp gets browser cookies up front
+    pub cookies: CookiesMode,
+    /// Spoken language code sent to the transcription service (e.g. "es"); unset means English
+    pub language: Option<String>,
+    /// Have the transcription service detect each recording's language (overrides `language`)
+    pub detect_language: bool,
+    /// What to do when a video's language differs from the transcription language
+    pub language_mismatch: LanguageMismatch,
+    /// Estimated cost in USD above which a recording is only transcribed once confirmed
+    /// (`--max-cost`)
+    pub max_cost: Option<f64>,
+    /// Extra arguments for every yt-dlp run, after yt-cli's own (e.g. `["-f", "bestaudio/best"]`;
+    /// `--ytdlp-args` adds more)
+    pub ytdlp_args: Vec<String>,
+    /// Whether a recording's audio.mp3 is stored with its transcript (default true;
+    /// `--no-keep-audio`)
+    pub keep_audio: Option<bool>,
+    /// Hours after which `yt-cli clean` removes leftover downloads (default 24; `--older-than`)
+    pub clean_after_hours: Option<u64>,
+    /// Punctuation, casing and filler word options (`[transcription]` table)
+    pub transcription: TranscriptionConfig,
+    pub storage: StorageConfig,
+    /// Where `yt-cli sync-remote` pushes and pulls the library (`[sync]` table)
+    pub sync: Option<SyncConfig>,
+    pub user: UserConfig,
+    /// Spelling corrections applied to new transcripts (`[spelling]` table, wrong = right)
+    pub spelling: HashMap<String, String>,
+    /// Per-channel settings (`[channels."<name or @handle>"]` tables)
+    pub channels: HashMap<String, ChannelConfig>,
+    /// Per-platform storage settings (`[platforms.<name>]` tables, e.g. `[platforms.twitch]`)
+    pub platforms: HashMap<String, PlatformConfig>,
+    /// Commands run before downloading and after each new transcript (`[hooks]` table)
+    pub hooks: HooksConfig,
+    /// URL rewrite rules applied, in order, before anything is downloaded (`[[rewrite]]` tables)
+    pub rewrite: Vec<RewriteRule>,
+    /// Where finished, failed and over-budget jobs are announced (`[[notify]]` tables)
+    pub notify: Vec<NotifierConfig>,
+    /// How long channel and search listings are reused (`[cache]` table)
+    pub cache: CacheConfig,
+    /// Language model answering `yt-cli ask` (`[ask]` table)
+    pub ask: AskConfig,
+    /// Embedding model behind `yt-cli embed` and `search --hybrid` (`[embeddings]` table)
+    pub embeddings: EmbeddingsConfig,
+    /// Search ranking (`[search]` table)
+    pub search: SearchConfig,
+    /// Time zone of times shown from the database, e.g. in `history` (`--utc`/`--local`)
+    pub timezone: TimeZone,
+    /// Speaker count from `--speakers`, overriding the per-channel one (command line only)
+    #[serde(skip)]
+    pub speakers: Option<u32>,
+    /// Words from `--boost-words`, added to the per-channel ones (command line only)
+    #[serde(skip)]
+    pub boost_words: Vec<String>,
+    /// Transcribe recordings above `max_cost` without asking (`--yes`, command line only)
+    #[serde(skip)]
+    pub assume_yes: bool,
+    /// Don't store audio, whatever config.toml says (`--no-keep-audio`, command line only)
+    #[serde(skip)]
+    pub discard_audio: bool,
+}
+
+/// When yt-dlp is given Firefox cookies (`cookies` in config.toml)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CookiesMode {
+    /// On every request, so members-only and age-restricted videos work
+    #[default]
+    Firefox,
+    /// Only when retrying a throttled request
+    #[serde(rename = "none", alias = "off")]
+    Off,
+}
+
+/// How transcript text is formatted and annotated by the service
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TranscriptionConfig {
+    /// Add punctuation
+    pub punctuate: bool,
+    /// Casing and number formatting ("twenty five" -> "25")
+    pub format_text: bool,
+    /// Keep filler words ("um", "uh") for verbatim transcripts
+    pub disfluencies: bool,
+    /// Mask profanity in the transcript text, e.g. "s***"
+    pub filter_profanity: bool,
+    /// AssemblyAI model tier; unset uses AssemblyAI's default (`--model`)
+    pub speech_model: Option<SpeechModel>,
+    /// Transcribe each audio channel separately, one speaker per channel, instead of
+    /// diarizing (AssemblyAI `multichannel`, e.g. for dual-channel Zoom recordings)
+    pub multichannel: bool,
+    /// Detect chapters with headlines and summaries (AssemblyAI `auto_chapters`)
+    pub chapters: bool,
+    /// Detect key phrases and when they were said (AssemblyAI `auto_highlights`)
+    pub highlights: bool,
+    /// Detect people, organizations and places mentioned (AssemblyAI `entity_detection`)
+    pub entities: bool,
+    /// Kinds of personal information redacted by the service before the transcript is
+    /// returned, e.g. `person_name`, `phone_number` (AssemblyAI `redact_pii_policies`)
+    pub redact_pii: Vec<String>,
+    /// What redacted text is replaced with
+    pub redact_pii_sub: PiiSubstitution,
+}
+
+/// AssemblyAI speech model (`speech_model`), trading accuracy for price
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum SpeechModel {
+    /// Most accurate, at the full price
+    Best,
+    /// Much cheaper and less accurate, for bulk backfills
+    Nano,
+    /// AssemblyAI's current general model
+    Universal,
+    /// English-only model that takes prompts
+    #[serde(rename = "slam-1")]
+    #[value(name = "slam-1")]
+    Slam1,
+}
+
+/// Replacement for redacted personal information (AssemblyAI `redact_pii_sub`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiSubstitution {
+    /// `####`, one `#` per character
+    #[default]
+    Hash,
+    /// The kind of information, e.g. `[PERSON_NAME]`
+    EntityName,
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            punctuate: true,
+            format_text: true,
+            disfluencies: false,
+            filter_profanity: false,
+            speech_model: None,
+            multichannel: false,
+            chapters: false,
+            highlights: false,
+            entities: false,
+            redact_pii: Vec::new(),
+            redact_pii_sub: PiiSubstitution::Hash,
+        }
+    }
+}
+
+/// OpenAI-compatible chat completions endpoint used by `yt-cli ask`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AskConfig {
+    /// Chat completions URL (default: AssemblyAI's LLM gateway, billed to the AssemblyAI key)
+    pub endpoint: String,
+    pub model: String,
+    /// Environment variable holding the key for another service, sent as a Bearer token
+    pub api_key_env: Option<String>,
+}
+
+impl Default for AskConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: "https://llm-gateway.assemblyai.com/v1/chat/completions".to_string(),
+            model: "claude-sonnet-4-5-20250929".to_string(),
+            api_key_env: None,
+        }
+    }
+}
+
+/// OpenAI-compatible embeddings endpoint used for semantic search
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmbeddingsConfig {
+    pub endpoint: String,
+    /// Stored with each vector; changing it means running `yt-cli embed` again
+    pub model: String,
+    /// Environment variable holding the key, sent as a Bearer token
+    pub api_key_env: String,
+}
+
+impl Default for EmbeddingsConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: "https://api.openai.com/v1/embeddings".to_string(),
+            model: "text-embedding-3-small".to_string(),
+            api_key_env: "OPENAI_API_KEY".to_string(),
+        }
+    }
+}
+
+/// How `search --hybrid` ranks results
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SearchConfig {
+    /// Share of the score from vector similarity (0.0 = keywords only, 1.0 = meaning only)
+    pub hybrid_weight: f64,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self { hybrid_weight: 0.5 }
+    }
+}
+
+/// Caching of yt-dlp listings in the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// Seconds a channel or search listing is reused before asking the platform again (0 disables)
+    pub listings_ttl: u64,
+    /// Seconds a video's metadata lookup is reused (0 disables)
+    pub metadata_ttl: u64,
+    /// Ignore cached listings and metadata for this run, still refreshing them (`--no-cache`)
+    #[serde(skip)]
+    pub bypass: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            listings_ttl: 600,
+            metadata_ttl: 3600,
+            bypass: false,
+        }
+    }
+}
+
+/// Reaction to a video whose language differs from the one it was transcribed in
+/// (`language_mismatch` in config.toml)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LanguageMismatch {
+    /// Keep the transcript and print a warning
+    #[default]
+    Warn,
+    /// Transcribe again in the video's language (billed twice)
+    Retranscribe,
+}
+
+/// How stored timestamps are displayed (`timezone` in config.toml)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeZone {
+    /// The system's time zone
+    #[default]
+    Local,
+    /// As stored
+    Utc,
+}
+
+/// Settings for one channel, matched by channel name or @handle
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChannelConfig {
+    /// Speaker count hint for diarization (e.g. 2 for a host and one guest)
+    pub speakers: Option<u32>,
+    /// Names and jargon the service should favour, e.g. tickers or product names
+    /// (AssemblyAI `word_boost`)
+    pub boost_words: Vec<String>,
+    /// Regexes finding episode numbers in titles, with an `episode` group and optionally a
+    /// `series` group, tried before the built-in ones (e.g. `'^MR(?P<episode>\d+)'`)
+    pub episode_patterns: Vec<String>,
+    /// `episode_patterns` compiled on first use
+    #[serde(skip)]
+    episode_regexes: OnceLock<Vec<Regex>>,
+}
+
+impl ChannelConfig {
+    /// The channel's episode patterns, compiled once; invalid ones are skipped with a warning
+    /// (`Settings::load` already refuses a config.toml with any)
+    fn episode_regexes(&self) -> &[Regex] {
+        self.episode_regexes.get_or_init(|| {
+            self.episode_patterns
+                .iter()
+                .filter_map(|pattern| match compile_episode_pattern(pattern) {
+                    Ok(regex) => Some(regex),
+                    Err(e) => {
+                        eprintln!("Warning: {}", e);
+                        None
+                    }
+                })
+                .collect()
+        })
+    }
+}
+
+/// Compile an `episode_patterns` entry, which must have an `episode` group
+fn compile_episode_pattern(pattern: &str) -> Result<Regex> {
+    let regex = Regex::new(pattern)
+        .map_err(|e| Error::Config(format!("Invalid episode pattern '{}': {}", pattern, e)))?;
+    if !regex.capture_names().any(|name| name == Some("episode")) {
+        return Err(Error::Config(format!(
+            "Episode pattern '{}' has no (?P<episode>...) group",
+            pattern
+        )));
+    }
+    Ok(regex)
+}
+
+/// Storage settings for one platform (`[platforms.<name>]`, the name as in the transcripts
+/// directory: youtube, twitch, podcast, ...)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlatformConfig {
+    /// What the directory between the platform and the video is named after
+    pub directory: DirectoryNaming,
+    /// Store audio.mp3 with this platform's transcripts (overrides the top-level `keep_audio`)
+    pub keep_audio: Option<bool>,
+    /// Age after which `yt-cli prune-audio` deletes this platform's audio, e.g. "30d"
+    pub prune_audio_after: Option<String>,
+}
+
+/// Directory a platform's transcripts are grouped in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DirectoryNaming {
+    /// The channel's display name
+    #[default]
+    Channel,
+    /// The channel's login / handle (e.g. a Twitch streamer's login), else its name
+    Handle,
+    /// The show's title (podcast series), else the channel name
+    Show,
+}
+
+/// User commands run at points in the pipeline (`[hooks]` table)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Shell command given each URL before it's downloaded; prints the URL to use instead
+    pub pre_download: Option<String>,
+    /// Shell commands run, in order, after a transcript is saved and indexed
+    pub post_transcribe: Vec<String>,
+}
+
+/// One place notifications go (a `[[notify]]` table), selected by `kind`
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotifierConfig {
+    #[serde(flatten)]
+    pub kind: NotifierKind,
+    /// Events sent to this notifier; all of them when empty
+    #[serde(default)]
+    pub events: Vec<NotifyEvent>,
+}
+
+/// Kinds of notifier and their settings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum NotifierKind {
+    /// A desktop notification, via `notify-send` (Linux) or `osascript` (macOS)
+    Desktop,
+    /// A Slack incoming webhook
+    Slack {
+        url: Option<String>,
+        /// Environment variable holding the webhook URL, to keep it out of config.toml
+        url_env: Option<String>,
+    },
+    /// A Discord channel webhook
+    Discord { url: Option<String>, url_env: Option<String> },
+    /// An email, handed to `sendmail` (or another command reading a message on stdin)
+    Email {
+        to: String,
+        from: Option<String>,
+        /// Command run through `sh -c` with the message on stdin (default `sendmail -t`)
+        command: Option<String>,
+    },
+    /// The notification as JSON, POSTed to any URL
+    Webhook { url: Option<String>, url_env: Option<String> },
+}
+
+/// What a notification is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum NotifyEvent {
+    /// A transcript was saved
+    Completed,
+    /// A transcription failed
+    Failed,
+    /// A recording's estimated cost was above `max_cost`
+    BudgetExceeded,
+}
+
+impl NotifyEvent {
+    pub fn id(self) -> &'static str {
+        match self {
+            NotifyEvent::Completed => "completed",
+            NotifyEvent::Failed => "failed",
+            NotifyEvent::BudgetExceeded => "budget_exceeded",
+        }
+    }
+}
+
+/// Regex rewrite of source URLs (e.g. a mirror to its canonical site)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RewriteRule {
+    /// Regular expression matched against the URL
+    pub pattern: String,
+    /// Replacement, with `$1` / `$name` for capture groups
+    pub replace: String,
+    /// `pattern` compiled on first use
+    #[serde(skip)]
+    regex: OnceLock<Option<Regex>>,
+}
+
+impl RewriteRule {
+    /// The rule's pattern, compiled once; `None` (with a warning) when it is invalid
+    /// (`Settings::load` already refuses a config.toml with one)
+    pub fn regex(&self) -> Option<&Regex> {
+        self.regex
+            .get_or_init(|| match compile_rewrite_pattern(&self.pattern) {
+                Ok(regex) => Some(regex),
+                Err(e) => {
+                    eprintln!("Warning: {}", e);
+                    None
+                }
+            })
+            .as_ref()
+    }
+}
+
+fn compile_rewrite_pattern(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|e| Error::Config(format!("Invalid rewrite pattern '{}': {}", pattern, e)))
+}
+
+/// Who is running yt-cli (`[user]` table), for libraries shared by several people
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UserConfig {
+    /// Recorded as `transcribed_by` on every new transcript
+    pub name: Option<String>,
+}
+
+/// Where transcript files are written (`[storage]` table, selected by `backend`)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase", deny_unknown_fields)]
+pub enum StorageConfig {
+    /// Files live only under the local transcripts directory
+    #[default]
+    Local,
+    /// S3 or an S3-compatible store (MinIO, Garage, ...) via the `aws` CLI
+    S3 {
+        bucket: String,
+        /// Key prefix inside the bucket
+        prefix: Option<String>,
+        /// Custom endpoint for S3-compatible servers
+        endpoint: Option<String>,
+        /// Named `aws` CLI profile holding the credentials
+        profile: Option<String>,
+    },
+    /// A WebDAV share (Nextcloud, Synology, ...) via `curl`
+    WebDav {
+        /// Base URL of the collection transcripts are written under
+        url: String,
+        username: Option<String>,
+        password: Option<String>,
+    },
+}
+
+/// Shared copy of the whole library for `yt-cli sync-remote` (`[sync]` table, selected by
+/// `backend`)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase", deny_unknown_fields)]
+pub enum SyncConfig {
+    /// S3 or an S3-compatible store via the `aws` CLI
+    S3 {
+        bucket: String,
+        /// Key prefix inside the bucket
+        prefix: Option<String>,
+        /// Custom endpoint for S3-compatible servers
+        endpoint: Option<String>,
+        /// Named `aws` CLI profile holding the credentials
+        profile: Option<String>,
+    },
+    /// Any rclone remote
+    Rclone {
+        /// rclone path the library is kept under, e.g. "gdrive:yt-cli"
+        remote: String,
+    },
+}
+
+impl Settings {
+    /// Read config.toml, falling back to defaults when it doesn't exist
+    pub fn load() -> Result<Self> {
+        let path = config_file_path();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let settings: Self = toml::from_str(&content)
+            .map_err(|e| Error::Config(format!("Invalid {}: {}", path.display(), e)))?;
+        settings.validate().map_err(|e| match e {
+            Error::Config(message) => Error::Config(format!("Invalid {}: {}", path.display(), message)),
+            e => e,
+        })?;
+        Ok(settings)
+    }
+
+    /// Check settings that parse but can't be used, so they fail at startup rather than
+    /// midway through a (paid) transcription
+    fn validate(&self) -> Result<()> {
+        // An empty key would match at every word boundary
+        if self.spelling.keys().any(|wrong| wrong.trim().is_empty()) {
+            return Err(Error::Config("[spelling]: empty key; each key is the word or phrase to correct".to_string()));
+        }
+        for rule in &self.rewrite {
+            compile_rewrite_pattern(&rule.pattern)?;
+        }
+        for (channel, config) in &self.channels {
+            for pattern in &config.episode_patterns {
+                compile_episode_pattern(pattern)
+                    .map_err(|e| Error::Config(format!("[channels.\"{}\"]: {}", channel, e)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Expected number of speakers for a channel's videos, if configured
+    pub fn speakers_expected(&self, channel: &str, handle: Option<&str>) -> Option<u32> {
+        self.speakers
+            .or_else(|| self.channel_config(channel, handle).and_then(|config| config.speakers))
+    }
+
+    /// Whether to store the audio of new transcripts from a platform
+    pub fn keeps_audio(&self, platform: &str) -> bool {
+        let configured = self.platform_config(platform).and_then(|config| config.keep_audio);
+        !self.discard_audio && configured.or(self.keep_audio).unwrap_or(true)
+    }
+
+    /// The `[platforms.<name>]` table for a platform, if any
+    pub fn platform_config(&self, platform: &str) -> Option<&PlatformConfig> {
+        self.platforms
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(platform))
+            .map(|(_, config)| config)
+    }
+
+    /// Episode patterns configured for a channel's titles, compiled
+    pub fn episode_patterns(&self, channel: &str, handle: Option<&str>) -> &[Regex] {
+        self.channel_config(channel, handle)
+            .map(ChannelConfig::episode_regexes)
+            .unwrap_or_default()
+    }
+
+    /// Words to boost for a channel's videos: `--boost-words`, then the channel's `boost_words`
+    pub fn boost_words(&self, channel: &str, handle: Option<&str>) -> Vec<String> {
+        let channel_words = self.channel_config(channel, handle).map(|config| config.boost_words.as_slice());
+        let mut words: Vec<String> = Vec::new();
+        for word in self.boost_words.iter().chain(channel_words.unwrap_or_default()) {
+            let word = word.trim();
+            if !word.is_empty() && !words.iter().any(|w| w.eq_ignore_ascii_case(word)) {
+                words.push(word.to_string());
+            }
+        }
+        words
+    }
+
+    /// `[channels]` entry matching a channel name or @handle
+    fn channel_config(&self, channel: &str, handle: Option<&str>) -> Option<&ChannelConfig> {
+        self.channels
+            .iter()
+            .find(|(key, _)| {
+                key.eq_ignore_ascii_case(channel) || handle.is_some_and(|handle| key.eq_ignore_ascii_case(handle))
+            })
+            .map(|(_, config)| config)
+    }
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Set settings for this process (call once at startup so config errors surface early)
+pub fn set_settings(settings: Settings) {
+    let _ = SETTINGS.set(settings);
+}
+
+/// Get settings (read from config.toml unless set via `set_settings`; defaults if invalid)
+pub fn settings() -> &'static Settings {
+    SETTINGS.get_or_init(|| Settings::load().unwrap_or_default())
+}
+
+/// Name to attribute new transcripts to (`[user] name` in config.toml)
+pub fn user_name() -> Option<&'static str> {
+    settings().user.name.as_deref().map(str::trim).filter(|n| !n.is_empty())
 }
 
 /// Load environment variables from the data directory's .env file
@@ -49,9 +672,25 @@ pub fn load_env() {
     }
 }
 
-/// Get the AssemblyAI API key
+/// Get the AssemblyAI API key (environment first, then the encrypted database)
 pub fn assemblyai_api_key() -> Option<String> {
-    std::env::var("ASSEMBLYAI_API_KEY").ok()
+    std::env::var("ASSEMBLYAI_API_KEY").ok().or_else(|| {
+        database_key()?;
+        crate::database::get_secret(API_KEY_SECRET).ok().flatten()
+    })
+}
+
+/// Get the Deepgram API key
+pub fn deepgram_api_key() -> Option<String> {
+    std::env::var("DEEPGRAM_API_KEY").ok().filter(|k| !k.is_empty())
+}
+
+/// Name under which `init --encrypt` stores the AssemblyAI key in the database
+pub const API_KEY_SECRET: &str = "ASSEMBLYAI_API_KEY";
+
+/// Passphrase for an SQLCipher-encrypted database (requires the `sqlcipher` feature)
+pub fn database_key() -> Option<String> {
+    std::env::var("YT_TRANSCRIBE_DB_KEY").ok().filter(|k| !k.is_empty())
 }
 
 /// Validate that required configuration is present
@@ -62,6 +701,30 @@ pub fn validate_config() -> Result<()> {
     Ok(())
 }
 
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Enable read-only mode for this process (`--read-only`)
+pub fn set_read_only(read_only: bool) {
+    READ_ONLY.store(read_only, Ordering::Relaxed);
+}
+
+/// Whether writes are disabled, via `--read-only` or YT_TRANSCRIBE_READ_ONLY
+pub fn read_only() -> bool {
+    READ_ONLY.load(Ordering::Relaxed)
+        || matches!(
+            std::env::var("YT_TRANSCRIBE_READ_ONLY").map(|v| v.to_lowercase()).as_deref(),
+            Ok("1" | "true" | "yes" | "on")
+        )
+}
+
+/// Fail with `Error::ReadOnly` if writes are disabled
+pub fn ensure_writable(action: &str) -> Result<()> {
+    if read_only() {
+        return Err(Error::ReadOnly(action.to_string()));
+    }
+    Ok(())
+}
+
 /// Create necessary directories if they don't exist
 pub fn ensure_directories() -> Result<()> {
     std::fs::create_dir_all(data_dir())?;
@@ -70,11 +733,128 @@ pub fn ensure_directories() -> Result<()> {
     Ok(())
 }
 
+/// Pacing applied to yt-dlp invocations so archive jobs don't trigger throttling, and
+/// limits that stop hung ones
+#[derive(Debug, Clone)]
+pub struct Politeness {
+    /// Seconds yt-dlp sleeps between extraction requests (`--sleep-requests`)
+    pub sleep_requests: Option<f64>,
+    /// Seconds yt-dlp sleeps before each download (`--sleep-interval`)
+    pub sleep_interval: Option<f64>,
+    /// Upper bound for a randomized download sleep (`--max-sleep-interval`)
+    pub max_sleep_interval: Option<f64>,
+    /// Minimum seconds between starting consecutive yt-dlp processes
+    pub fetch_delay: Option<f64>,
+    /// Maximum number of yt-dlp processes running at once
+    pub max_concurrent_fetches: usize,
+    /// Minutes after which a yt-dlp process is killed, however busy (`None`: no limit)
+    pub ytdlp_timeout: Option<f64>,
+    /// Minutes a yt-dlp process may go without printing anything, progress included,
+    /// before it is killed as hung (`None`: no limit)
+    pub ytdlp_idle_timeout: Option<f64>,
+}
+
+impl Default for Politeness {
+    fn default() -> Self {
+        Self {
+            sleep_requests: None,
+            sleep_interval: None,
+            max_sleep_interval: None,
+            fetch_delay: None,
+            max_concurrent_fetches: 2,
+            ytdlp_timeout: None,
+            ytdlp_idle_timeout: Some(15.0),
+        }
+    }
+}
+
+impl Politeness {
+    /// Read pacing settings from YT_TRANSCRIBE_* environment variables
+    pub fn from_env() -> Self {
+        let env_f64 = |name: &str| std::env::var(name).ok().and_then(|v| v.parse::<f64>().ok());
+        let defaults = Self::default();
+
+        Self {
+            sleep_requests: env_f64("YT_TRANSCRIBE_SLEEP_REQUESTS"),
+            sleep_interval: env_f64("YT_TRANSCRIBE_SLEEP_INTERVAL"),
+            max_sleep_interval: env_f64("YT_TRANSCRIBE_MAX_SLEEP_INTERVAL"),
+            fetch_delay: env_f64("YT_TRANSCRIBE_FETCH_DELAY"),
+            max_concurrent_fetches: std::env::var("YT_TRANSCRIBE_MAX_CONCURRENT_FETCHES")
+                .ok()
+                .and_then(|v| v.parse::<usize>().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(defaults.max_concurrent_fetches),
+            // 0 turns a limit off
+            ytdlp_timeout: env_f64("YT_TRANSCRIBE_YTDLP_TIMEOUT").filter(|m| *m > 0.0),
+            ytdlp_idle_timeout: match env_f64("YT_TRANSCRIBE_YTDLP_IDLE_TIMEOUT") {
+                Some(minutes) => Some(minutes).filter(|m| *m > 0.0),
+                None => defaults.ytdlp_idle_timeout,
+            },
+        }
+    }
+
+    /// Reject a delay or timeout that isn't a usable length of time (infinite, or too long)
+    pub fn validate(&self) -> Result<()> {
+        let limits = [
+            ("--fetch-delay", "YT_TRANSCRIBE_FETCH_DELAY", self.fetch_delay, 1.0, "seconds"),
+            ("--ytdlp-timeout", "YT_TRANSCRIBE_YTDLP_TIMEOUT", self.ytdlp_timeout, 60.0, "minutes"),
+            ("--ytdlp-idle-timeout", "YT_TRANSCRIBE_YTDLP_IDLE_TIMEOUT", self.ytdlp_idle_timeout, 60.0, "minutes"),
+        ];
+        for (flag, env, value, unit_secs, unit) in limits {
+            if let Some(value) = value
+                && Duration::try_from_secs_f64(value.max(0.0) * unit_secs).is_err()
+            {
+                return Err(Error::Config(format!("{} ({}) must be a number of {}, got {:?}", flag, env, unit, value)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Extra yt-dlp arguments implementing the sleep settings
+    pub fn ytdlp_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(secs) = self.sleep_requests {
+            args.extend(["--sleep-requests".to_string(), secs.to_string()]);
+        }
+        if let Some(secs) = self.sleep_interval {
+            args.extend(["--sleep-interval".to_string(), secs.to_string()]);
+            if let Some(max) = self.max_sleep_interval.filter(|max| *max >= secs) {
+                args.extend(["--max-sleep-interval".to_string(), max.to_string()]);
+            }
+        }
+        args
+    }
+}
+
+static POLITENESS: OnceLock<Politeness> = OnceLock::new();
+
+/// Set pacing for this process (call once, before any yt-dlp invocation)
+pub fn set_politeness(politeness: Politeness) {
+    let _ = POLITENESS.set(politeness);
+}
+
+/// Get pacing settings (environment defaults unless overridden via `set_politeness`)
+pub fn politeness() -> &'static Politeness {
+    POLITENESS.get_or_init(Politeness::from_env)
+}
+
 /// Check if running in Docker mode (cookies mounted as volume)
 pub fn is_docker_mode() -> bool {
     std::env::var("FIREFOX_COOKIES_PATH").is_ok()
 }
 
+/// Whether yt-dlp gets browser cookies up front (disable with YT_TRANSCRIBE_COOKIES=none
+/// or `cookies = "none"` in config.toml)
+///
+/// Even when disabled, cookies are still used to retry requests that get throttled.
+pub fn cookies_enabled() -> bool {
+    match std::env::var("YT_TRANSCRIBE_COOKIES").map(|v| v.to_lowercase()).as_deref() {
+        Ok("none" | "off" | "false" | "0") => false,
+        Ok(_) => true,
+        Err(_) => settings().cookies == CookiesMode::Firefox,
+    }
+}
+
 /// Get yt-dlp arguments for Firefox cookies
 pub fn firefox_cookies_args() -> Vec<String> {
     if let Ok(cookies_path) = std::env::var("FIREFOX_COOKIES_PATH") {
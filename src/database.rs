@@ -1,8 +1,19 @@
-use rusqlite::{Connection, params};
+use std::collections::HashMap;
+use std::fs;
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rusqlite::types::ValueRef;
+use rusqlite::{Connection, OpenFlags, params};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use crate::config::{database_path, ensure_directories};
-use crate::error::Result;
+use crate::config::{data_dir, database_key, database_path, ensure_directories, ensure_writable, read_only};
+use crate::error::{Error, Result};
+use crate::transcriber::{Entity, Highlight, Sentence, TranscriptSource};
 
 /// Initialize database tables
 fn init_tables(conn: &Connection) -> Result<()> {
@@ -11,7 +22,7 @@ fn init_tables(conn: &Connection) -> Result<()> {
         -- Main transcripts table
         CREATE TABLE IF NOT EXISTS transcripts (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
-            video_id TEXT UNIQUE,
+            video_id TEXT NOT NULL,
             url TEXT,
             title TEXT,
             channel TEXT,
@@ -28,7 +39,16 @@ fn init_tables(conn: &Connection) -> Result<()> {
             path TEXT,
             speaker_count INTEGER,
             word_count INTEGER,
-            confidence REAL
+            confidence REAL,
+            transcribed_by TEXT,
+            -- 'captions' or 'api' (see TranscriptSource); one transcript of each per video
+            source TEXT NOT NULL DEFAULT 'api',
+            -- 1 while the search rows are missing (see `reindex --pending`)
+            index_pending INTEGER NOT NULL DEFAULT 0,
+            -- Language code the transcript is in, as reported or requested (e.g. "en_us", "es")
+            language TEXT,
+            -- Seconds at the end of the audio the transcript doesn't reach, when it looks cut short
+            missing_tail INTEGER
         );
 
         -- Full-text search table
@@ -36,7 +56,154 @@ fn init_tables(conn: &Connection) -> Result<()> {
             title,
             channel,
             description,
-            transcript_text
+            transcript_text,
+            chapters
+        );
+
+        -- One row per sentence, so search can show the sentence and moment a phrase was said
+        CREATE VIRTUAL TABLE IF NOT EXISTS sentences_fts USING fts5(
+            text,
+            speaker UNINDEXED,
+            transcript_id UNINDEXED,
+            start_ms UNINDEXED
+        );
+
+        -- Text extracted from files added with `yt-cli attach`, keyed by transcript directory
+        CREATE VIRTUAL TABLE IF NOT EXISTS attachments_fts USING fts5(
+            name,
+            text,
+            path UNINDEXED
+        );
+
+        -- Labels added with `yt-cli tag`, keyed by transcript directory so reindexing keeps them
+        CREATE TABLE IF NOT EXISTS tags (
+            path TEXT NOT NULL,
+            tag TEXT NOT NULL COLLATE NOCASE,
+            PRIMARY KEY (path, tag)
+        );
+
+        -- Key phrases (AssemblyAI auto_highlights), one row per moment a phrase was said
+        CREATE TABLE IF NOT EXISTS highlights (
+            transcript_id INTEGER NOT NULL,
+            text TEXT NOT NULL,
+            rank REAL NOT NULL,
+            start_ms INTEGER NOT NULL,
+            end_ms INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_highlights_transcript ON highlights(transcript_id);
+
+        -- Named entities (AssemblyAI entity_detection), each stored once; mentions link
+        -- them to the transcripts and moments they come up in
+        CREATE TABLE IF NOT EXISTS entities (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            entity_type TEXT NOT NULL,
+            name TEXT NOT NULL COLLATE NOCASE,
+            UNIQUE(entity_type, name)
+        );
+        CREATE TABLE IF NOT EXISTS entity_mentions (
+            transcript_id INTEGER NOT NULL,
+            entity_id INTEGER NOT NULL REFERENCES entities(id),
+            start_ms INTEGER NOT NULL,
+            end_ms INTEGER NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_entity_mentions_transcript ON entity_mentions(transcript_id);
+        CREATE INDEX IF NOT EXISTS idx_entity_mentions_entity ON entity_mentions(entity_id);
+
+        -- Audio seconds sent to paid transcription services, for spend tracking
+        CREATE TABLE IF NOT EXISTS usage (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            backend TEXT NOT NULL,
+            video_id TEXT,
+            seconds INTEGER NOT NULL,
+            -- Estimated USD at the list price when recorded; NULL for older rows
+            cost REAL,
+            recorded_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- High-level outcome of each write command, shown by `yt-cli history`
+        CREATE TABLE IF NOT EXISTS activity (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            action TEXT NOT NULL,
+            subject TEXT,
+            detail TEXT,
+            ok INTEGER NOT NULL DEFAULT 1,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            undo TEXT,
+            undone INTEGER NOT NULL DEFAULT 0
+        );
+
+        -- Channels kept up to date by `yt-cli sync`
+        CREATE TABLE IF NOT EXISTS subscriptions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            url TEXT UNIQUE NOT NULL,
+            name TEXT,
+            last_video_id TEXT,
+            last_synced_at TIMESTAMP,
+            added_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- Transcriptions queued by `yt-cli queue add`, worked off by `yt-cli queue run`
+        CREATE TABLE IF NOT EXISTS jobs (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            url TEXT NOT NULL,
+            backend TEXT NOT NULL,
+            captions_lang TEXT,
+            status TEXT NOT NULL DEFAULT 'pending',
+            attempts INTEGER NOT NULL DEFAULT 0,
+            error TEXT,
+            runner_pid INTEGER,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- Submitted transcription jobs not saved yet, finished by `yt-cli resume` after a crash
+        CREATE TABLE IF NOT EXISTS inflight (
+            transcript_id TEXT PRIMARY KEY,
+            backend TEXT NOT NULL,
+            url TEXT NOT NULL,
+            platform TEXT NOT NULL,
+            metadata TEXT NOT NULL,
+            audio_path TEXT,
+            -- 1 once `jobs cancel` couldn't delete it: `resume` retries the delete instead of saving
+            cancel_pending INTEGER NOT NULL DEFAULT 0,
+            started_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- yt-dlp channel and search listings, reused for `[cache] listings_ttl` seconds
+        CREATE TABLE IF NOT EXISTS listing_cache (
+            url TEXT NOT NULL,
+            limit_count INTEGER NOT NULL,
+            entries TEXT NOT NULL,
+            fetched_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (url, limit_count)
+        );
+
+        -- yt-dlp metadata lookups, reused for `[cache] metadata_ttl` seconds
+        CREATE TABLE IF NOT EXISTS metadata_cache (
+            url TEXT PRIMARY KEY,
+            metadata TEXT NOT NULL,
+            fetched_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- One embedding per transcript for semantic search, written by `yt-cli embed`
+        CREATE TABLE IF NOT EXISTS embeddings (
+            transcript_id INTEGER PRIMARY KEY,
+            model TEXT NOT NULL,
+            vector BLOB NOT NULL,
+            created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+        );
+
+        -- SHA-256 of each stored audio file, so identical audio is hard-linked, not stored twice
+        CREATE TABLE IF NOT EXISTS audio_files (
+            path TEXT PRIMARY KEY,
+            sha256 TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_audio_files_sha256 ON audio_files(sha256);
+
+        -- Credentials kept inside the (encrypted) database instead of .env
+        CREATE TABLE IF NOT EXISTS secrets (
+            name TEXT PRIMARY KEY,
+            value TEXT NOT NULL
         );
         "#,
     )?;
@@ -47,6 +214,39 @@ fn init_tables(conn: &Connection) -> Result<()> {
     // Migration: Add channel_handle column
     migrate_add_channel_handle(conn)?;
 
+    // Migration: Add transcribed_by column
+    migrate_add_transcribed_by(conn)?;
+
+    // Migration: Add undo columns to the activity log
+    migrate_add_activity_undo(conn)?;
+
+    // Migration: Utterance index replaced by sentences_fts (filled by reindex)
+    conn.execute("DROP TABLE IF EXISTS utterances_fts", [])?;
+
+    // Migration: One transcript per video and source instead of per video
+    migrate_add_transcript_source(conn)?;
+
+    // Migration: Chapter headlines are searchable again
+    migrate_add_fts_chapters(conn)?;
+
+    // Migration: Track transcripts whose search rows failed to be written
+    migrate_add_index_pending(conn)?;
+
+    // Migration: Store the language each transcript is in
+    migrate_add_language(conn)?;
+
+    // Migration: Estimated cost of each transcription
+    migrate_add_usage_cost(conn)?;
+
+    // Migration: Flag transcripts that stop well before the end of the audio
+    migrate_add_missing_tail(conn)?;
+
+    // Migration: Record which `queue run` process is working on a job
+    migrate_add_job_runner_pid(conn)?;
+
+    // Migration: Mark jobs cancelled but not yet deleted from AssemblyAI
+    migrate_add_inflight_cancel_pending(conn)?;
+
     Ok(())
 }
 
@@ -123,12 +323,1071 @@ fn migrate_add_channel_handle(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
-/// Get a database connection
-pub fn get_connection() -> Result<Connection> {
-    ensure_directories()?;
-    let conn = Connection::open(database_path())?;
-    init_tables(&conn)?;
-    Ok(conn)
+/// Migration to add transcribed_by column to existing databases
+fn migrate_add_transcribed_by(conn: &Connection) -> Result<()> {
+    let has_transcribed_by: bool = conn
+        .prepare("SELECT 1 FROM pragma_table_info('transcripts') WHERE name = 'transcribed_by'")?
+        .exists([])?;
+
+    if !has_transcribed_by {
+        conn.execute("ALTER TABLE transcripts ADD COLUMN transcribed_by TEXT", [])?;
+    }
+
+    Ok(())
+}
+
+fn migrate_add_index_pending(conn: &Connection) -> Result<()> {
+    let has_index_pending: bool = conn
+        .prepare("SELECT 1 FROM pragma_table_info('transcripts') WHERE name = 'index_pending'")?
+        .exists([])?;
+
+    if !has_index_pending {
+        conn.execute("ALTER TABLE transcripts ADD COLUMN index_pending INTEGER NOT NULL DEFAULT 0", [])?;
+    }
+
+    Ok(())
+}
+
+fn migrate_add_language(conn: &Connection) -> Result<()> {
+    let has_language: bool = conn
+        .prepare("SELECT 1 FROM pragma_table_info('transcripts') WHERE name = 'language'")?
+        .exists([])?;
+
+    if !has_language {
+        conn.execute("ALTER TABLE transcripts ADD COLUMN language TEXT", [])?;
+    }
+
+    Ok(())
+}
+
+fn migrate_add_usage_cost(conn: &Connection) -> Result<()> {
+    let has_cost: bool = conn
+        .prepare("SELECT 1 FROM pragma_table_info('usage') WHERE name = 'cost'")?
+        .exists([])?;
+
+    if !has_cost {
+        conn.execute("ALTER TABLE usage ADD COLUMN cost REAL", [])?;
+    }
+
+    Ok(())
+}
+
+fn migrate_add_missing_tail(conn: &Connection) -> Result<()> {
+    let has_missing_tail: bool = conn
+        .prepare("SELECT 1 FROM pragma_table_info('transcripts') WHERE name = 'missing_tail'")?
+        .exists([])?;
+
+    if !has_missing_tail {
+        conn.execute("ALTER TABLE transcripts ADD COLUMN missing_tail INTEGER", [])?;
+    }
+
+    Ok(())
+}
+
+fn migrate_add_job_runner_pid(conn: &Connection) -> Result<()> {
+    let has_runner_pid: bool = conn
+        .prepare("SELECT 1 FROM pragma_table_info('jobs') WHERE name = 'runner_pid'")?
+        .exists([])?;
+
+    if !has_runner_pid {
+        conn.execute("ALTER TABLE jobs ADD COLUMN runner_pid INTEGER", [])?;
+    }
+
+    Ok(())
+}
+
+fn migrate_add_inflight_cancel_pending(conn: &Connection) -> Result<()> {
+    let has_cancel_pending: bool = conn
+        .prepare("SELECT 1 FROM pragma_table_info('inflight') WHERE name = 'cancel_pending'")?
+        .exists([])?;
+
+    if !has_cancel_pending {
+        conn.execute("ALTER TABLE inflight ADD COLUMN cancel_pending INTEGER NOT NULL DEFAULT 0", [])?;
+    }
+
+    Ok(())
+}
+
+/// Migration letting a video have both a captions and an API transcript: adds `source` and
+/// replaces the UNIQUE constraint on video_id (which needs a table rebuild) with a
+/// unique (video_id, source) index
+fn migrate_add_transcript_source(conn: &Connection) -> Result<()> {
+    let has_source: bool = conn
+        .prepare("SELECT 1 FROM pragma_table_info('transcripts') WHERE name = 'source'")?
+        .exists([])?;
+
+    if !has_source {
+        // Existing rows are labelled by `yt-cli reindex`, which reads their transcript IDs
+        conn.execute_batch(
+            r#"
+            CREATE TABLE transcripts_new (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                video_id TEXT NOT NULL,
+                url TEXT,
+                title TEXT,
+                channel TEXT,
+                channel_handle TEXT,
+                channel_id TEXT,
+                platform TEXT,
+                duration INTEGER,
+                upload_date TEXT,
+                description TEXT,
+                thumbnail TEXT,
+                view_count INTEGER,
+                like_count INTEGER,
+                transcribed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                path TEXT,
+                speaker_count INTEGER,
+                word_count INTEGER,
+                confidence REAL,
+                transcribed_by TEXT,
+                source TEXT NOT NULL DEFAULT 'api'
+            );
+
+            INSERT INTO transcripts_new (id, video_id, url, title, channel, channel_handle, channel_id,
+                platform, duration, upload_date, description, thumbnail, view_count, like_count,
+                transcribed_at, path, speaker_count, word_count, confidence, transcribed_by)
+            SELECT id, video_id, url, title, channel, channel_handle, channel_id,
+                platform, duration, upload_date, description, thumbnail, view_count, like_count,
+                transcribed_at, path, speaker_count, word_count, confidence, transcribed_by
+            FROM transcripts
+            WHERE video_id IS NOT NULL;
+
+            DROP TABLE transcripts;
+            ALTER TABLE transcripts_new RENAME TO transcripts;
+            "#,
+        )?;
+    }
+
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_transcripts_video_source ON transcripts(video_id, source)",
+        [],
+    )?;
+
+    // Sentences were briefly keyed by video ID; the index is rebuilt by reindex
+    let sentences_by_video: bool = conn
+        .prepare("SELECT 1 FROM pragma_table_info('sentences_fts') WHERE name = 'video_id'")?
+        .exists([])?;
+    if sentences_by_video {
+        conn.execute_batch(
+            "DROP TABLE sentences_fts;
+             CREATE VIRTUAL TABLE sentences_fts USING fts5(
+                 text, speaker UNINDEXED, transcript_id UNINDEXED, start_ms UNINDEXED
+             );",
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Migration adding the chapters column to the search index; FTS5 tables can't be
+/// altered, so the rows are copied into a new table
+fn migrate_add_fts_chapters(conn: &Connection) -> Result<()> {
+    let has_chapters: bool = conn
+        .prepare("SELECT 1 FROM pragma_table_info('transcripts_fts') WHERE name = 'chapters'")?
+        .exists([])?;
+
+    if !has_chapters {
+        conn.execute_batch(
+            r#"
+            CREATE VIRTUAL TABLE transcripts_fts_new USING fts5(
+                title,
+                channel,
+                description,
+                transcript_text,
+                chapters
+            );
+
+            INSERT INTO transcripts_fts_new (rowid, title, channel, description, transcript_text, chapters)
+            SELECT rowid, title, channel, description, transcript_text, '' FROM transcripts_fts;
+
+            DROP TABLE transcripts_fts;
+            ALTER TABLE transcripts_fts_new RENAME TO transcripts_fts;
+            "#,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Migration to add undo/undone columns to existing activity tables
+fn migrate_add_activity_undo(conn: &Connection) -> Result<()> {
+    let has_undo: bool = conn
+        .prepare("SELECT 1 FROM pragma_table_info('activity') WHERE name = 'undo'")?
+        .exists([])?;
+
+    if !has_undo {
+        conn.execute_batch(
+            "ALTER TABLE activity ADD COLUMN undo TEXT;
+             ALTER TABLE activity ADD COLUMN undone INTEGER NOT NULL DEFAULT 0;",
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Open connections kept for reuse, so a process running many commands (`batch`, the
+/// daemon) doesn't open and migrate the database for every query
+static POOL: Mutex<Vec<Connection>> = Mutex::new(Vec::new());
+
+/// Bumped by [`close_connections`]; connections from an older generation aren't reused
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Whether this process has brought the database's tables up to date
+static MIGRATED: AtomicBool = AtomicBool::new(false);
+
+/// A database connection, returned to the pool when dropped
+pub struct PooledConnection {
+    conn: Option<Connection>,
+    generation: u64,
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection present until dropped")
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Connection {
+        self.conn.as_mut().expect("connection present until dropped")
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take()
+            && self.generation == GENERATION.load(Ordering::SeqCst)
+        {
+            POOL.lock().unwrap_or_else(|e| e.into_inner()).push(conn);
+        }
+    }
+}
+
+/// Get a database connection, reusing one opened earlier in this process when possible
+pub fn get_connection() -> Result<PooledConnection> {
+    let generation = GENERATION.load(Ordering::SeqCst);
+    let pooled = POOL.lock().unwrap_or_else(|e| e.into_inner()).pop();
+    let conn = match pooled {
+        Some(conn) => conn,
+        None => open_connection()?,
+    };
+    Ok(PooledConnection { conn: Some(conn), generation })
+}
+
+/// Close the pooled connections before the database file is replaced (`archive import`,
+/// `sync-remote pull`); the next [`get_connection`] opens and migrates the new file
+pub fn close_connections() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+    POOL.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    MIGRATED.store(false, Ordering::SeqCst);
+}
+
+/// Put `new_file`, another library's database (`archive import`, `sync-remote pull`), in
+/// place of this one, pointing its paths under `old_data_dir` at this data directory;
+/// returns how many stored paths were remapped
+pub fn replace_database(new_file: &Path, old_data_dir: &str) -> Result<usize> {
+    // Open connections would keep using the replaced file, and a write-ahead log left by it
+    // would be applied to the new one
+    close_connections();
+    for suffix in ["-wal", "-shm", "-journal"] {
+        let mut stale = database_path().into_os_string();
+        stale.push(suffix);
+        let _ = fs::remove_file(stale);
+    }
+    fs::rename(new_file, database_path())?;
+
+    let old_prefix = format!("{}/", old_data_dir.trim_end_matches('/'));
+    let new_prefix = format!("{}/", data_dir().to_string_lossy().trim_end_matches('/'));
+    if old_prefix == new_prefix {
+        return Ok(0);
+    }
+    remap_paths(&old_prefix, &new_prefix)
+}
+
+/// Copy this library's database next to it as `transcripts.db.before-<action>-<time>`
+/// ahead of [`replace_database`], so its subscriptions, jobs, tags and history aren't lost
+/// for good; `None` when there is no database yet
+pub fn keep_replaced_database(action: &str) -> Result<Option<PathBuf>> {
+    let path = database_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".before-{}-{}", action, stamp));
+    let copy = path.with_file_name(name);
+    backup_database(&copy)?;
+    Ok(Some(copy))
+}
+
+fn open_connection() -> Result<Connection> {
+    if read_only() {
+        // Shared libraries may be mounted read-only: no directory creation or migrations
+        let path = database_path();
+        if !path.exists() {
+            return Err(Error::FileNotFound(format!("No database at {}", path.display())));
+        }
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        unlock(&conn)?;
+        return Ok(conn);
+    }
+
+    ensure_directories()?;
+    let conn = Connection::open(database_path())?;
+    unlock(&conn)?;
+    if !MIGRATED.load(Ordering::SeqCst) {
+        init_tables(&conn)?;
+        MIGRATED.store(true, Ordering::SeqCst);
+    }
+    Ok(conn)
+}
+
+/// Copy the database to `dest` with SQLite's online backup API, which gives a consistent
+/// snapshot even while another process (the daemon, a queue run) is writing to it
+///
+/// The copy is encrypted with the same key as the database, if any, and checked with
+/// `PRAGMA quick_check` before returning.
+pub fn backup_database(dest: &Path) -> Result<()> {
+    let source = get_connection()?;
+    let mut copy = Connection::open(dest)?;
+    unlock(&copy)?;
+
+    // Copy in chunks, pausing between them so writers aren't held up; the backup restarts
+    // on its own if another connection changes the database midway
+    let backup = rusqlite::backup::Backup::new(&source, &mut copy)?;
+    backup.run_to_completion(1024, Duration::from_millis(10), None)?;
+    drop(backup);
+
+    let check: String = copy.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+    if check != "ok" {
+        let message = format!("backup at {} failed its integrity check: {}", dest.display(), check);
+        return Err(Error::Io(std::io::Error::other(message)));
+    }
+    Ok(())
+}
+
+/// Tables left out of [`library_fingerprint`]: caches and the activity log change without
+/// the library itself changing
+const UNFINGERPRINTED_TABLES: &[&str] = &["activity", "listing_cache", "metadata_cache"];
+
+/// Hex SHA-256 of the database's contents, which tells whether the library changed between
+/// two points in time (e.g. since the last `sync-remote`)
+pub fn library_fingerprint() -> Result<String> {
+    let mut conn = get_connection()?;
+    // One read transaction, so a writer can't change the tables midway
+    let tx = conn.transaction()?;
+    let tables: Vec<String> = tx
+        .prepare(
+            "SELECT name FROM sqlite_master
+             WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND sql NOT LIKE 'CREATE VIRTUAL%'
+             ORDER BY name",
+        )?
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let mut hasher = Sha256::new();
+    for table in tables.iter().filter(|t| !UNFINGERPRINTED_TABLES.contains(&t.as_str())) {
+        hasher.update(table.as_bytes());
+        let mut stmt = tx.prepare(&format!("SELECT * FROM \"{}\"", table))?;
+        let columns = stmt.column_count();
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            for i in 0..columns {
+                // Tagged and length-prefixed, so different rows can't hash the same
+                match row.get_ref(i)? {
+                    ValueRef::Null => hasher.update([0]),
+                    ValueRef::Integer(n) => {
+                        hasher.update([1]);
+                        hasher.update(n.to_le_bytes());
+                    }
+                    ValueRef::Real(f) => {
+                        hasher.update([2]);
+                        hasher.update(f.to_le_bytes());
+                    }
+                    ValueRef::Text(bytes) | ValueRef::Blob(bytes) => {
+                        hasher.update([3]);
+                        hasher.update((bytes.len() as u64).to_le_bytes());
+                        hasher.update(bytes);
+                    }
+                }
+            }
+        }
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Rewrite stored file paths starting with `old_prefix` to start with `new_prefix` instead,
+/// e.g. after the data directory moved to another machine; returns the rows changed
+pub fn remap_paths(old_prefix: &str, new_prefix: &str) -> Result<usize> {
+    ensure_writable("remap stored paths")?;
+    let mut conn = get_connection()?;
+    let tx = conn.transaction()?;
+
+    let mut changed = 0;
+    for (table, column) in [
+        ("transcripts", "path"),
+        ("tags", "path"),
+        ("attachments_fts", "path"),
+        ("audio_files", "path"),
+        ("inflight", "audio_path"),
+    ] {
+        changed += tx.execute(
+            &format!(
+                "UPDATE {t} SET {c} = ?2 || substr({c}, length(?1) + 1) WHERE substr({c}, 1, length(?1)) = ?1",
+                t = table,
+                c = column
+            ),
+            params![old_prefix, new_prefix],
+        )?;
+    }
+
+    tx.commit()?;
+    Ok(changed)
+}
+
+/// Whether this build can open SQLCipher-encrypted databases
+pub fn encryption_supported() -> bool {
+    cfg!(feature = "sqlcipher")
+}
+
+/// Apply the SQLCipher key, if one is configured
+#[cfg(feature = "sqlcipher")]
+fn unlock(conn: &Connection) -> Result<()> {
+    let Some(key) = database_key() else {
+        return Ok(());
+    };
+    conn.pragma_update(None, "key", &key)?;
+
+    // SQLCipher only checks the key on the first read
+    conn.query_row("SELECT count(*) FROM sqlite_master", [], |_| Ok(()))
+        .map_err(|_| {
+            Error::Config(format!(
+                "Could not decrypt {}: wrong YT_TRANSCRIBE_DB_KEY, or the database is unencrypted \
+                 (move it aside and run `yt-cli reindex` to rebuild it encrypted)",
+                database_path().display()
+            ))
+        })
+}
+
+#[cfg(not(feature = "sqlcipher"))]
+fn unlock(_conn: &Connection) -> Result<()> {
+    if database_key().is_some() {
+        return Err(Error::Config(
+            "YT_TRANSCRIBE_DB_KEY is set, but this build has no SQLCipher support \
+             (rebuild with `cargo install --features sqlcipher`)"
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Read a stored secret
+pub fn get_secret(name: &str) -> Result<Option<String>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare("SELECT value FROM secrets WHERE name = ?")?;
+    let mut rows = stmt.query(params![name])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(row.get(0)?)),
+        None => Ok(None),
+    }
+}
+
+/// Store a secret, replacing any previous value
+pub fn set_secret(name: &str, value: &str) -> Result<()> {
+    ensure_writable("store secrets")?;
+    let conn = get_connection()?;
+    conn.execute(
+        "INSERT INTO secrets (name, value) VALUES (?, ?)
+         ON CONFLICT(name) DO UPDATE SET value = excluded.value",
+        params![name, value],
+    )?;
+    Ok(())
+}
+
+/// Record audio seconds billed by a transcription service, with their estimated cost
+pub fn record_usage(backend: &str, video_id: &str, seconds: i64, cost: f64) -> Result<()> {
+    ensure_writable("record usage")?;
+    let conn = get_connection()?;
+    conn.execute(
+        "INSERT INTO usage (backend, video_id, seconds, cost) VALUES (?, ?, ?, ?)",
+        params![backend, video_id, seconds, cost],
+    )?;
+    Ok(())
+}
+
+/// Locally tracked usage for one backend
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsageSummary {
+    /// Calendar month (`YYYY-MM`, UTC) for `usage_by_month`, empty otherwise
+    pub month: String,
+    pub backend: String,
+    pub transcripts: i64,
+    pub seconds: i64,
+    /// Estimated USD, leaving out rows recorded before costs were (`None` if all were)
+    pub cost: Option<f64>,
+}
+
+/// Usage per backend since an SQLite date expression (e.g. `date('now', 'start of month')`)
+fn usage_since(conn: &Connection, since: &str) -> Result<Vec<UsageSummary>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT '', backend, COUNT(*), SUM(seconds), SUM(cost) FROM usage WHERE recorded_at >= {} \
+         GROUP BY backend ORDER BY backend",
+        since
+    ))?;
+
+    let results = stmt.query_map([], usage_summary)?.collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+fn usage_summary(row: &rusqlite::Row) -> rusqlite::Result<UsageSummary> {
+    Ok(UsageSummary {
+        month: row.get(0)?,
+        backend: row.get(1)?,
+        transcripts: row.get(2)?,
+        seconds: row.get(3)?,
+        cost: row.get(4)?,
+    })
+}
+
+/// Usage per calendar month (UTC) and backend, oldest month first; only `month`
+/// (`YYYY-MM`) when given
+pub fn usage_by_month(month: Option<&str>) -> Result<Vec<UsageSummary>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare(
+        "SELECT strftime('%Y-%m', recorded_at) AS month, backend, COUNT(*), SUM(seconds), SUM(cost)
+         FROM usage WHERE ?1 IS NULL OR strftime('%Y-%m', recorded_at) = ?1
+         GROUP BY month, backend ORDER BY month, backend",
+    )?;
+
+    let results = stmt
+        .query_map(params![month], usage_summary)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// One transcription recorded in the usage table
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsageEntry {
+    pub recorded_at: String,
+    pub backend: String,
+    pub video_id: Option<String>,
+    /// Title of the video's transcript, if it is still indexed
+    pub title: Option<String>,
+    pub seconds: i64,
+    pub cost: Option<f64>,
+}
+
+/// Every transcription recorded in `month` (`YYYY-MM`, UTC), oldest first
+pub fn usage_entries(month: &str) -> Result<Vec<UsageEntry>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare(
+        "SELECT u.recorded_at, u.backend, u.video_id,
+                (SELECT t.title FROM transcripts t WHERE t.video_id = u.video_id LIMIT 1),
+                u.seconds, u.cost
+         FROM usage u WHERE strftime('%Y-%m', u.recorded_at) = ? ORDER BY u.recorded_at, u.id",
+    )?;
+
+    let results = stmt
+        .query_map(params![month], |row| {
+            Ok(UsageEntry {
+                recorded_at: row.get(0)?,
+                backend: row.get(1)?,
+                video_id: row.get(2)?,
+                title: row.get(3)?,
+                seconds: row.get(4)?,
+                cost: row.get(5)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Usage this calendar month (UTC) and all time
+pub fn get_usage() -> Result<(Vec<UsageSummary>, Vec<UsageSummary>)> {
+    let conn = get_connection()?;
+    let month = usage_since(&conn, "date('now', 'start of month')")?;
+    let total = usage_since(&conn, "'0000-01-01'")?;
+    Ok((month, total))
+}
+
+/// Append an entry to the activity log
+///
+/// `undo` is a JSON description of how to reverse the operation, for `yt-cli undo`.
+pub fn log_activity(
+    action: &str,
+    subject: Option<&str>,
+    detail: Option<&str>,
+    ok: bool,
+    undo: Option<&str>,
+) -> Result<()> {
+    ensure_writable("log activity")?;
+    let conn = get_connection()?;
+    conn.execute(
+        "INSERT INTO activity (action, subject, detail, ok, undo) VALUES (?, ?, ?, ?, ?)",
+        params![action, subject, detail, ok, undo],
+    )?;
+    Ok(())
+}
+
+/// Most recent activity entry that can still be undone: `(entry, undo JSON)`
+pub fn last_undoable_activity() -> Result<Option<(ActivityRecord, String)>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare(
+        "SELECT id, action, subject, detail, ok, created_at, undo FROM activity
+         WHERE undo IS NOT NULL AND undone = 0 AND ok = 1
+         ORDER BY id DESC LIMIT 1",
+    )?;
+    let mut rows = stmt.query([])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some((
+            ActivityRecord {
+                id: row.get(0)?,
+                action: row.get(1)?,
+                subject: row.get(2)?,
+                detail: row.get(3)?,
+                ok: row.get(4)?,
+                created_at: row.get(5)?,
+            },
+            row.get(6)?,
+        ))),
+        None => Ok(None),
+    }
+}
+
+/// Mark an activity entry as undone so it isn't reversed twice
+pub fn mark_activity_undone(id: i64) -> Result<()> {
+    ensure_writable("update activity")?;
+    let conn = get_connection()?;
+    conn.execute("UPDATE activity SET undone = 1 WHERE id = ?", params![id])?;
+    Ok(())
+}
+
+/// Activity log entry
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ActivityRecord {
+    pub id: i64,
+    pub action: String,
+    pub subject: Option<String>,
+    pub detail: Option<String>,
+    pub ok: bool,
+    pub created_at: String,
+}
+
+/// Most recent activity entries, newest first
+pub fn recent_activity(limit: i32, action: Option<&str>, failed_only: bool) -> Result<Vec<ActivityRecord>> {
+    let conn = get_connection()?;
+
+    let mut query =
+        "SELECT id, action, subject, detail, ok, created_at FROM activity WHERE 1=1".to_string();
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(a) = action {
+        query.push_str(" AND action = ?");
+        params_vec.push(Box::new(a.to_string()));
+    }
+
+    if failed_only {
+        query.push_str(" AND ok = 0");
+    }
+
+    query.push_str(" ORDER BY id DESC LIMIT ?");
+    params_vec.push(Box::new(limit));
+
+    let mut stmt = conn.prepare(&query)?;
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+
+    let results = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            Ok(ActivityRecord {
+                id: row.get(0)?,
+                action: row.get(1)?,
+                subject: row.get(2)?,
+                detail: row.get(3)?,
+                ok: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Subscribed channel
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Subscription {
+    pub id: i64,
+    /// Channel videos-tab URL
+    pub url: String,
+    pub name: Option<String>,
+    /// Newest video seen by the last successful sync; uploads after it are new
+    pub last_video_id: Option<String>,
+    pub last_synced_at: Option<String>,
+}
+
+/// Subscribe to a channel; returns false if it was already subscribed
+pub fn add_subscription(url: &str, name: Option<&str>, last_video_id: Option<&str>) -> Result<bool> {
+    ensure_writable("subscribe")?;
+    let conn = get_connection()?;
+    let changes = conn.execute(
+        "INSERT OR IGNORE INTO subscriptions (url, name, last_video_id) VALUES (?, ?, ?)",
+        params![url, name, last_video_id],
+    )?;
+    Ok(changes > 0)
+}
+
+/// Remove a subscription matching the URL or the (case-insensitive) name
+pub fn remove_subscription(url: &str, name: &str) -> Result<bool> {
+    ensure_writable("unsubscribe")?;
+    let conn = get_connection()?;
+    let changes = conn.execute(
+        "DELETE FROM subscriptions WHERE url = ? OR name = ? COLLATE NOCASE",
+        params![url, name],
+    )?;
+    Ok(changes > 0)
+}
+
+/// All subscriptions, oldest first
+pub fn list_subscriptions() -> Result<Vec<Subscription>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare(
+        "SELECT id, url, name, last_video_id, last_synced_at FROM subscriptions ORDER BY id",
+    )?;
+
+    let results = stmt
+        .query_map([], |row| {
+            Ok(Subscription {
+                id: row.get(0)?,
+                url: row.get(1)?,
+                name: row.get(2)?,
+                last_video_id: row.get(3)?,
+                last_synced_at: row.get(4)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Record a successful sync, moving the new-upload marker to `last_video_id`
+pub fn mark_subscription_synced(id: i64, last_video_id: Option<&str>) -> Result<()> {
+    ensure_writable("update subscriptions")?;
+    let conn = get_connection()?;
+    conn.execute(
+        "UPDATE subscriptions SET last_synced_at = CURRENT_TIMESTAMP,
+             last_video_id = COALESCE(?, last_video_id)
+         WHERE id = ?",
+        params![last_video_id, id],
+    )?;
+    Ok(())
+}
+
+/// Queued transcription job
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Job {
+    pub id: i64,
+    pub url: String,
+    /// Backend name as accepted by `--backend`
+    pub backend: String,
+    /// Import captions in this language instead of transcribing
+    pub captions_lang: Option<String>,
+    /// `pending`, `running`, `done` or `failed`
+    pub status: String,
+    pub attempts: i32,
+    /// Error from the last failed attempt
+    pub error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+const JOB_COLUMNS: &str =
+    "id, url, backend, captions_lang, status, attempts, error, created_at, updated_at";
+
+fn row_to_job(row: &rusqlite::Row) -> rusqlite::Result<Job> {
+    Ok(Job {
+        id: row.get(0)?,
+        url: row.get(1)?,
+        backend: row.get(2)?,
+        captions_lang: row.get(3)?,
+        status: row.get(4)?,
+        attempts: row.get(5)?,
+        error: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+    })
+}
+
+/// Cached listing JSON for `url` and `limit`, if fetched within the last `max_age_secs` seconds
+pub fn get_cached_listing(url: &str, limit: usize, max_age_secs: u64) -> Result<Option<String>> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT entries FROM listing_cache
+         WHERE url = ? AND limit_count = ? AND fetched_at > datetime('now', ?)",
+    )?;
+    let mut rows = stmt.query(params![url, limit as i64, format!("-{} seconds", max_age_secs)])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(row.get(0)?)),
+        None => Ok(None),
+    }
+}
+
+/// Store a listing, dropping entries too old to be reused by any reasonable TTL
+pub fn cache_listing(url: &str, limit: usize, entries: &str) -> Result<()> {
+    ensure_writable("cache listings")?;
+    let conn = get_connection()?;
+    conn.execute(
+        "INSERT OR REPLACE INTO listing_cache (url, limit_count, entries, fetched_at)
+         VALUES (?, ?, ?, CURRENT_TIMESTAMP)",
+        params![url, limit as i64, entries],
+    )?;
+    conn.execute("DELETE FROM listing_cache WHERE fetched_at < datetime('now', '-1 day')", [])?;
+    Ok(())
+}
+
+/// Cached metadata JSON for `url`, if fetched within the last `max_age_secs` seconds
+pub fn get_cached_metadata(url: &str, max_age_secs: u64) -> Result<Option<String>> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT metadata FROM metadata_cache WHERE url = ? AND fetched_at > datetime('now', ?)",
+    )?;
+    let mut rows = stmt.query(params![url, format!("-{} seconds", max_age_secs)])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(row.get(0)?)),
+        None => Ok(None),
+    }
+}
+
+/// Store a metadata lookup, dropping entries older than a week
+pub fn cache_metadata(url: &str, metadata: &str) -> Result<()> {
+    ensure_writable("cache metadata")?;
+    let conn = get_connection()?;
+    conn.execute(
+        "INSERT OR REPLACE INTO metadata_cache (url, metadata, fetched_at) VALUES (?, ?, CURRENT_TIMESTAMP)",
+        params![url, metadata],
+    )?;
+    conn.execute("DELETE FROM metadata_cache WHERE fetched_at < datetime('now', '-7 days')", [])?;
+    Ok(())
+}
+
+/// Stored audio files with this SHA-256, oldest first
+pub fn audio_files_with_hash(sha256: &str) -> Result<Vec<String>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare("SELECT path FROM audio_files WHERE sha256 = ? ORDER BY rowid")?;
+    let paths = stmt
+        .query_map([sha256], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(paths)
+}
+
+/// Remember the hash of an audio file in storage
+pub fn record_audio_file(path: &str, sha256: &str) -> Result<()> {
+    let conn = get_connection()?;
+    conn.execute(
+        "INSERT OR REPLACE INTO audio_files (path, sha256) VALUES (?, ?)",
+        params![path, sha256],
+    )?;
+    Ok(())
+}
+
+/// Queue a job; returns None if the URL is already pending or running
+pub fn enqueue_job(url: &str, backend: &str, captions_lang: Option<&str>) -> Result<Option<i64>> {
+    ensure_writable("queue jobs")?;
+    let conn = get_connection()?;
+
+    let queued: bool = conn.query_row(
+        "SELECT COUNT(*) > 0 FROM jobs WHERE url = ? AND status IN ('pending', 'running')",
+        params![url],
+        |row| row.get(0),
+    )?;
+    if queued {
+        return Ok(None);
+    }
+
+    conn.execute(
+        "INSERT INTO jobs (url, backend, captions_lang) VALUES (?, ?, ?)",
+        params![url, backend, captions_lang],
+    )?;
+    Ok(Some(conn.last_insert_rowid()))
+}
+
+/// Mark the next runnable job after `after_id` as running by this process and return it
+///
+/// Runnable means pending, or failed with fewer than `max_attempts` attempts.
+pub fn claim_next_job(after_id: i64, max_attempts: i32) -> Result<Option<Job>> {
+    ensure_writable("run jobs")?;
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare(&format!(
+        "UPDATE jobs SET status = 'running', attempts = attempts + 1, error = NULL,
+             runner_pid = ?, updated_at = CURRENT_TIMESTAMP
+         WHERE id = (
+             SELECT id FROM jobs
+             WHERE id > ? AND (status = 'pending' OR (status = 'failed' AND attempts < ?))
+             ORDER BY id LIMIT 1
+         )
+         RETURNING {}",
+        JOB_COLUMNS
+    ))?;
+    let mut rows = stmt.query(params![std::process::id(), after_id, max_attempts])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(row_to_job(row)?)),
+        None => Ok(None),
+    }
+}
+
+/// Record the outcome of a running job
+pub fn finish_job(id: i64, error: Option<&str>) -> Result<()> {
+    ensure_writable("update jobs")?;
+    let conn = get_connection()?;
+    conn.execute(
+        "UPDATE jobs SET status = ?, error = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+        params![if error.is_some() { "failed" } else { "done" }, error, id],
+    )?;
+    Ok(())
+}
+
+/// Return jobs left running by an interrupted `queue run` to the queue
+///
+/// Only jobs whose runner is gone (`runner_alive` is false for its PID, or none was recorded)
+/// are requeued; jobs another `queue run` is still working on are left alone.
+pub fn requeue_orphaned_jobs(runner_alive: impl Fn(u32) -> bool) -> Result<usize> {
+    ensure_writable("update jobs")?;
+    let mut conn = get_connection()?;
+    let tx = conn.transaction()?;
+    let running: Vec<(i64, Option<u32>)> = tx
+        .prepare("SELECT id, runner_pid FROM jobs WHERE status = 'running'")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let mut changes = 0;
+    for (id, pid) in running {
+        if pid.is_some_and(&runner_alive) {
+            continue;
+        }
+        changes += tx.execute(
+            "UPDATE jobs SET status = 'pending', runner_pid = NULL, updated_at = CURRENT_TIMESTAMP
+             WHERE id = ?",
+            params![id],
+        )?;
+    }
+    tx.commit()?;
+    Ok(changes)
+}
+
+/// Give failed jobs a fresh set of attempts
+pub fn retry_failed_jobs() -> Result<usize> {
+    ensure_writable("update jobs")?;
+    let conn = get_connection()?;
+    let changes = conn.execute(
+        "UPDATE jobs SET status = 'pending', attempts = 0, updated_at = CURRENT_TIMESTAMP
+         WHERE status = 'failed'",
+        [],
+    )?;
+    Ok(changes)
+}
+
+/// Jobs in queue order, optionally only those with `status`
+pub fn list_jobs(status: Option<&str>) -> Result<Vec<Job>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM jobs WHERE ?1 IS NULL OR status = ?1 ORDER BY id",
+        JOB_COLUMNS
+    ))?;
+
+    let results = stmt
+        .query_map(params![status], row_to_job)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Transcription job that was submitted but whose result hasn't been saved
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InflightTranscription {
+    pub transcript_id: String,
+    /// Backend name as accepted by `--backend`
+    pub backend: String,
+    pub url: String,
+    pub platform: String,
+    /// `VideoMetadata` as JSON
+    pub metadata: String,
+    /// Downloaded audio waiting to be moved into storage
+    pub audio_path: Option<String>,
+    /// Cancelled with `jobs cancel`, but not deleted from the service yet
+    pub cancel_pending: bool,
+    pub started_at: String,
+}
+
+/// Remember a submitted job until its transcript is saved
+pub fn add_inflight(
+    transcript_id: &str,
+    backend: &str,
+    url: &str,
+    platform: &str,
+    metadata: &str,
+    audio_path: Option<&str>,
+) -> Result<()> {
+    ensure_writable("track transcriptions")?;
+    let conn = get_connection()?;
+    conn.execute(
+        "INSERT OR REPLACE INTO inflight (transcript_id, backend, url, platform, metadata, audio_path)
+         VALUES (?, ?, ?, ?, ?, ?)",
+        params![transcript_id, backend, url, platform, metadata, audio_path],
+    )?;
+    Ok(())
+}
+
+/// Forget a job once it is saved (or can't be finished)
+pub fn remove_inflight(transcript_id: &str) -> Result<()> {
+    ensure_writable("track transcriptions")?;
+    let conn = get_connection()?;
+    conn.execute("DELETE FROM inflight WHERE transcript_id = ?", params![transcript_id])?;
+    Ok(())
+}
+
+/// Keep a job whose cancellation failed, so its delete is retried rather than it being saved
+pub fn mark_inflight_cancelled(transcript_id: &str) -> Result<()> {
+    ensure_writable("track transcriptions")?;
+    let conn = get_connection()?;
+    conn.execute("UPDATE inflight SET cancel_pending = 1 WHERE transcript_id = ?", params![transcript_id])?;
+    Ok(())
+}
+
+/// Unfinished jobs, oldest first
+pub fn list_inflight() -> Result<Vec<InflightTranscription>> {
+    let conn = get_connection()?;
+    let mut stmt = conn.prepare(
+        "SELECT transcript_id, backend, url, platform, metadata, audio_path, cancel_pending, started_at
+         FROM inflight ORDER BY started_at, rowid",
+    )?;
+
+    let results = stmt
+        .query_map([], |row| {
+            Ok(InflightTranscription {
+                transcript_id: row.get(0)?,
+                backend: row.get(1)?,
+                url: row.get(2)?,
+                platform: row.get(3)?,
+                metadata: row.get(4)?,
+                audio_path: row.get(5)?,
+                cancel_pending: row.get(6)?,
+                started_at: row.get(7)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
 }
 
 /// Metadata for adding a transcript
@@ -151,40 +1410,178 @@ pub struct TranscriptMetadata<'a> {
     pub word_count: i32,
     pub confidence: Option<f64>,
     pub transcript_text: &'a str,
+    /// Chapter headlines, one per line
+    pub chapters: &'a str,
+    pub sentences: &'a [Sentence],
+    pub highlights: &'a [Highlight],
+    pub entities: &'a [Entity],
+    /// `TranscriptSource` ID
+    pub source: &'a str,
+    pub transcribed_by: Option<&'a str>,
+    pub language: Option<&'a str>,
+    /// See `TranscriptData::missing_tail`
+    pub missing_tail: Option<i64>,
+    /// A new transcription, dated now; a reindex keeps the stored `transcribed_at`
+    pub transcribed_now: bool,
+}
+
+/// Whether a transcript added with `add_transcript` can be searched yet
+#[derive(Debug)]
+pub enum IndexOutcome {
+    Complete,
+    /// Its row was saved but its search rows weren't, for this reason
+    Pending(Error),
 }
 
 /// Add a transcript to the database
-pub fn add_transcript(meta: &TranscriptMetadata) -> Result<i64> {
-    let conn = get_connection()?;
+pub fn add_transcript(meta: &TranscriptMetadata) -> Result<IndexOutcome> {
+    ensure_writable("index transcripts")?;
+    let mut conn = get_connection()?;
+    let tx = conn.transaction()?;
 
-    // Insert or replace the transcript
-    conn.execute(
+    // Drop the search rows of the transcript being replaced: the one of this source, and
+    // whatever was indexed from this directory before (e.g. unlabelled captions)
+    tx.execute(
+        "DELETE FROM transcripts_fts WHERE rowid IN
+         (SELECT id FROM transcripts WHERE (video_id = ?1 AND source = ?2) OR path = ?3)",
+        params![meta.video_id, meta.source, meta.path],
+    )?;
+    tx.execute(
+        "DELETE FROM sentences_fts WHERE transcript_id IN
+         (SELECT id FROM transcripts WHERE (video_id = ?1 AND source = ?2) OR path = ?3)",
+        params![meta.video_id, meta.source, meta.path],
+    )?;
+    tx.execute(
+        "DELETE FROM highlights WHERE transcript_id IN
+         (SELECT id FROM transcripts WHERE (video_id = ?1 AND source = ?2) OR path = ?3)",
+        params![meta.video_id, meta.source, meta.path],
+    )?;
+    tx.execute(
+        "DELETE FROM entity_mentions WHERE transcript_id IN
+         (SELECT id FROM transcripts WHERE (video_id = ?1 AND source = ?2) OR path = ?3)",
+        params![meta.video_id, meta.source, meta.path],
+    )?;
+    tx.execute("DELETE FROM transcripts WHERE path = ?1 AND source != ?2", params![meta.path, meta.source])?;
+
+    // Insert the transcript, or update it in place: its ID (which embeddings refer to) and,
+    // unless this is a new transcription, its `transcribed_at` stay as they were
+    let transcript_id: i64 = tx.query_row(
         r#"
-        INSERT OR REPLACE INTO transcripts
+        INSERT INTO transcripts
         (video_id, url, title, channel, channel_handle, channel_id, platform, duration, upload_date,
-         description, thumbnail, view_count, like_count, path, speaker_count, word_count, confidence)
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17)
+         description, thumbnail, view_count, like_count, path, speaker_count, word_count, confidence,
+         transcribed_by, source, language, missing_tail, index_pending)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, 1)
+        ON CONFLICT(video_id, source) DO UPDATE SET
+            url = excluded.url, title = excluded.title, channel = excluded.channel,
+            channel_handle = excluded.channel_handle, channel_id = excluded.channel_id,
+            platform = excluded.platform, duration = excluded.duration, upload_date = excluded.upload_date,
+            description = excluded.description, thumbnail = excluded.thumbnail,
+            view_count = excluded.view_count, like_count = excluded.like_count, path = excluded.path,
+            speaker_count = excluded.speaker_count, word_count = excluded.word_count,
+            confidence = excluded.confidence, transcribed_by = excluded.transcribed_by,
+            language = excluded.language, missing_tail = excluded.missing_tail, index_pending = 1,
+            transcribed_at = CASE WHEN ?22 THEN CURRENT_TIMESTAMP ELSE transcribed_at END
+        RETURNING id
         "#,
         params![
             meta.video_id, meta.url, meta.title, meta.channel, meta.channel_handle, meta.channel_id,
             meta.platform, meta.duration, meta.upload_date, meta.description,
             meta.thumbnail, meta.view_count, meta.like_count, meta.path,
-            meta.speaker_count, meta.word_count, meta.confidence
+            meta.speaker_count, meta.word_count, meta.confidence, meta.transcribed_by, meta.source,
+            meta.language, meta.missing_tail, meta.transcribed_now
         ],
+        |row| row.get(0),
     )?;
+    if meta.transcribed_now {
+        // A new transcript needs embedding again
+        tx.execute("DELETE FROM embeddings WHERE transcript_id = ?", params![transcript_id])?;
+    }
+    tx.commit()?;
 
-    let transcript_id = conn.last_insert_rowid();
+    // Search rows go in separately, so a failure here (e.g. disk full) leaves the
+    // transcript listed and flagged for `reindex --pending` instead of losing it
+    let tx = conn.transaction()?;
+    match insert_search_rows(&tx, transcript_id, meta) {
+        Ok(()) => {
+            tx.commit()?;
+            Ok(IndexOutcome::Complete)
+        }
+        Err(e) => Ok(IndexOutcome::Pending(e)),
+    }
+}
 
+/// Full-text, sentence, key phrase and entity rows of a transcript; clears its pending flag
+fn insert_search_rows(tx: &rusqlite::Transaction, transcript_id: i64, meta: &TranscriptMetadata) -> Result<()> {
     // Update FTS with transcript text
-    conn.execute(
+    tx.execute(
         r#"
-        INSERT OR REPLACE INTO transcripts_fts(rowid, title, channel, description, transcript_text)
-        VALUES (?1, ?2, ?3, ?4, ?5)
+        INSERT OR REPLACE INTO transcripts_fts(rowid, title, channel, description, transcript_text, chapters)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
         "#,
-        params![transcript_id, meta.title, meta.channel, meta.description.unwrap_or(""), meta.transcript_text],
+        params![
+            transcript_id, meta.title, meta.channel, meta.description.unwrap_or(""), meta.transcript_text,
+            meta.chapters
+        ],
+    )?;
+
+    let mut stmt = tx.prepare(
+        "INSERT INTO sentences_fts(text, speaker, transcript_id, start_ms) VALUES (?1, ?2, ?3, ?4)",
+    )?;
+    for sentence in meta.sentences {
+        stmt.execute(params![sentence.text, sentence.speaker, transcript_id, sentence.start])?;
+    }
+
+    let mut stmt = tx.prepare(
+        "INSERT INTO highlights(transcript_id, text, rank, start_ms, end_ms) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    for highlight in meta.highlights {
+        for span in &highlight.timestamps {
+            stmt.execute(params![transcript_id, highlight.text, highlight.rank, span.start, span.end])?;
+        }
+    }
+
+    let mut add_entity = tx.prepare("INSERT OR IGNORE INTO entities(entity_type, name) VALUES (?1, ?2)")?;
+    let mut entity_id = tx.prepare("SELECT id FROM entities WHERE entity_type = ?1 AND name = ?2")?;
+    let mut add_mention = tx.prepare(
+        "INSERT INTO entity_mentions(transcript_id, entity_id, start_ms, end_ms) VALUES (?1, ?2, ?3, ?4)",
     )?;
+    for entity in meta.entities {
+        let name = entity.text.trim();
+        if name.is_empty() {
+            continue;
+        }
+        add_entity.execute(params![entity.entity_type, name])?;
+        let id: i64 = entity_id.query_row(params![entity.entity_type, name], |row| row.get(0))?;
+        add_mention.execute(params![transcript_id, id, entity.start, entity.end])?;
+    }
+
+    tx.execute("UPDATE transcripts SET index_pending = 0 WHERE id = ?", params![transcript_id])?;
+    Ok(())
+}
+
+/// Directories of transcripts whose search rows are missing, oldest first
+pub fn pending_index_paths() -> Result<Vec<String>> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare("SELECT path FROM transcripts WHERE index_pending = 1 ORDER BY id")?;
+    let paths = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(paths)
+}
+
+/// Seconds missing at the end of each transcript that looks cut short, by directory
+pub fn incomplete_transcripts() -> Result<HashMap<String, i64>> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare("SELECT path, missing_tail FROM transcripts WHERE missing_tail IS NOT NULL")?;
+    let incomplete = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<std::result::Result<HashMap<_, _>, _>>()?;
 
-    Ok(transcript_id)
+    Ok(incomplete)
 }
 
 /// Search result
@@ -197,18 +1594,343 @@ pub struct SearchResult {
     pub platform: String,
     pub duration: Option<i64>,
     pub path: String,
+    pub url: Option<String>,
     pub snippet: Option<String>,
+    /// `TranscriptSource` ID
+    pub source: String,
+    /// FTS5 bm25 rank (more negative is a better match; 0 when not found by keywords)
+    pub rank: f64,
+}
+
+/// Optional quality filters applied to search and list queries
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptFilter {
+    pub min_words: Option<i32>,
+    pub min_confidence: Option<f64>,
+    /// Only transcripts from this source; without it, a video with transcripts from both
+    /// sources is listed once, preferring the API transcript
+    pub source: Option<TranscriptSource>,
+    /// Only transcripts mentioning this entity (case-insensitive name, any type)
+    pub entity: Option<String>,
+    /// Only transcripts in this language; "en" also matches regional codes like "en_us"
+    pub language: Option<String>,
+    /// Only transcripts with this tag (case-insensitive)
+    pub tag: Option<String>,
+}
+
+impl TranscriptFilter {
+    /// Append SQL conditions for this filter against the `transcripts` table alias
+    fn push_sql(&self, alias: &str, query: &mut String, params_vec: &mut Vec<Box<dyn rusqlite::ToSql>>) {
+        if let Some(min_words) = self.min_words {
+            query.push_str(&format!(" AND {}.word_count >= ?", alias));
+            params_vec.push(Box::new(min_words));
+        }
+
+        if let Some(min_confidence) = self.min_confidence {
+            query.push_str(&format!(" AND {}.confidence >= ?", alias));
+            params_vec.push(Box::new(min_confidence));
+        }
+
+        if let Some(entity) = &self.entity {
+            query.push_str(&format!(
+                " AND EXISTS (SELECT 1 FROM entity_mentions m JOIN entities e ON e.id = m.entity_id \
+                 WHERE m.transcript_id = {}.id AND e.name = ?)",
+                alias
+            ));
+            params_vec.push(Box::new(entity.clone()));
+        }
+
+        if let Some(tag) = &self.tag {
+            query.push_str(&format!(" AND EXISTS (SELECT 1 FROM tags g WHERE g.path = {}.path AND g.tag = ?)", alias));
+            params_vec.push(Box::new(tag.clone()));
+        }
+
+        if let Some(language) = &self.language {
+            let language = language.trim().to_lowercase().replace('-', "_");
+            query.push_str(&format!(
+                " AND (lower(replace({a}.language, '-', '_')) = ? \
+                 OR lower(replace({a}.language, '-', '_')) LIKE ? ESCAPE '\\')",
+                a = alias
+            ));
+            params_vec.push(Box::new(language.clone()));
+            params_vec.push(Box::new(format!("{}\\_%", language)));
+        }
+
+        match self.source {
+            Some(source) => {
+                query.push_str(&format!(" AND {}.source = ?", alias));
+                params_vec.push(Box::new(source.id()));
+            }
+            None => query.push_str(&format!(
+                " AND NOT EXISTS (SELECT 1 FROM transcripts p WHERE p.video_id = {a}.video_id \
+                 AND ((p.source = 'api') > ({a}.source = 'api') \
+                 OR ((p.source = 'api') = ({a}.source = 'api') AND p.id > {a}.id)))",
+                a = alias
+            )),
+        }
+    }
+
+    /// Check word count, confidence and language values against this filter
+    pub fn matches(&self, word_count: i32, confidence: Option<f64>, language: Option<&str>) -> bool {
+        if self.min_words.is_some_and(|min| word_count < min) {
+            return false;
+        }
+        if let Some(wanted) = &self.language {
+            let wanted = wanted.trim().to_lowercase().replace('-', "_");
+            let Some(language) = language.map(|l| l.to_lowercase().replace('-', "_")) else {
+                return false;
+            };
+            if language != wanted && !language.starts_with(&format!("{}_", wanted)) {
+                return false;
+            }
+        }
+        if let Some(min) = self.min_confidence {
+            return confidence.is_some_and(|c| c >= min);
+        }
+        true
+    }
+
+    /// Whether a word count, confidence or language filter is set
+    pub fn is_empty(&self) -> bool {
+        self.min_words.is_none() && self.min_confidence.is_none() && self.language.is_none()
+    }
 }
 
 /// Search transcripts using full-text search
-pub fn search_transcripts(query: &str, limit: i32) -> Result<Vec<SearchResult>> {
+pub fn search_transcripts(query: &str, limit: i32, filter: &TranscriptFilter) -> Result<Vec<SearchResult>> {
+    search_fts(&fts_phrase(query), limit, filter)
+}
+
+/// Search transcripts containing any of `terms`, best matches first
+pub fn search_transcripts_any(terms: &[String], limit: i32, filter: &TranscriptFilter) -> Result<Vec<SearchResult>> {
+    let query = terms.iter().map(|term| fts_phrase(term)).collect::<Vec<_>>().join(" OR ");
+    search_fts(&query, limit, filter)
+}
+
+/// A sentence matching a search, with where it starts in the recording
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SentenceHit {
+    pub speaker: Option<String>,
+    pub start_ms: i64,
+    /// The whole sentence, matches marked like `snippet`
+    pub text: String,
+}
+
+/// Best-matching sentences of one transcript for a `search_transcripts` query
+pub fn search_sentences(query: &str, transcript_id: i64, limit: i32) -> Result<Vec<SentenceHit>> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT speaker, start_ms, highlight(sentences_fts, 0, '>>> ', ' <<<')
+        FROM sentences_fts
+        WHERE sentences_fts MATCH ? AND transcript_id = ?
+        ORDER BY rank
+        LIMIT ?
+        "#,
+    )?;
+    let hits = stmt
+        .query_map(params![fts_phrase(query), transcript_id, limit], |row| {
+            Ok(SentenceHit {
+                speaker: row.get(0)?,
+                start_ms: row.get(1)?,
+                text: row.get(2)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(hits)
+}
+
+/// Key phrase across the library
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PhraseCount {
+    pub text: String,
+    /// Transcripts the phrase is a highlight of
+    pub transcripts: i64,
+    /// Times it was said across them
+    pub mentions: i64,
+}
+
+/// Key phrases highlighted in the most transcripts (case-insensitive), most widespread first,
+/// optionally only from channels whose name contains `channel`
+pub fn top_highlights(limit: i32, channel: Option<&str>) -> Result<Vec<PhraseCount>> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT MIN(h.text), COUNT(DISTINCT h.transcript_id), COUNT(*)
+        FROM highlights h
+        JOIN transcripts t ON t.id = h.transcript_id
+        WHERE ?1 IS NULL OR instr(lower(t.channel), lower(?1)) > 0
+        GROUP BY lower(h.text)
+        ORDER BY 2 DESC, 3 DESC, 1
+        LIMIT ?2
+        "#,
+    )?;
+    let results = stmt
+        .query_map(params![channel, limit], |row| {
+            Ok(PhraseCount {
+                text: row.get(0)?,
+                transcripts: row.get(1)?,
+                mentions: row.get(2)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Named entity across the library
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntityCount {
+    pub entity_type: String,
+    pub name: String,
+    /// Transcripts mentioning it
+    pub transcripts: i64,
+    /// Times it was mentioned across them
+    pub mentions: i64,
+}
+
+/// Entities mentioned in the most transcripts, most widespread first, optionally only of
+/// one type (e.g. `person_name`) and from channels whose name contains `channel`
+pub fn top_entities(limit: i32, channel: Option<&str>, entity_type: Option<&str>) -> Result<Vec<EntityCount>> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT e.entity_type, e.name, COUNT(DISTINCT m.transcript_id), COUNT(*)
+        FROM entity_mentions m
+        JOIN entities e ON e.id = m.entity_id
+        JOIN transcripts t ON t.id = m.transcript_id
+        WHERE (?1 IS NULL OR instr(lower(t.channel), lower(?1)) > 0)
+          AND (?2 IS NULL OR e.entity_type = ?2)
+        GROUP BY e.id
+        ORDER BY 3 DESC, 4 DESC, 2
+        LIMIT ?3
+        "#,
+    )?;
+    let results = stmt
+        .query_map(params![channel, entity_type, limit], |row| {
+            Ok(EntityCount {
+                entity_type: row.get(0)?,
+                name: row.get(1)?,
+                transcripts: row.get(2)?,
+                mentions: row.get(3)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Index the text of a transcript's attachment, replacing an earlier version of it
+pub fn index_attachment(path: &str, name: &str, text: &str) -> Result<()> {
+    ensure_writable("index attachments")?;
+    let conn = get_connection()?;
+
+    conn.execute("DELETE FROM attachments_fts WHERE path = ? AND name = ?", params![path, name])?;
+    conn.execute(
+        "INSERT INTO attachments_fts(name, text, path) VALUES (?1, ?2, ?3)",
+        params![name, text, path],
+    )?;
+    Ok(())
+}
+
+/// Drop an attachment's text from the search index
+pub fn remove_attachment_index(path: &str, name: &str) -> Result<()> {
+    ensure_writable("index attachments")?;
+    let conn = get_connection()?;
+    conn.execute("DELETE FROM attachments_fts WHERE path = ? AND name = ?", params![path, name])?;
+    Ok(())
+}
+
+/// Tag a transcript directory; returns how many of the tags are new
+pub fn add_tags(path: &str, tags: &[String]) -> Result<usize> {
+    ensure_writable("tag transcripts")?;
+    let conn = get_connection()?;
+    let mut added = 0;
+    for tag in tags {
+        added += conn.execute("INSERT OR IGNORE INTO tags (path, tag) VALUES (?, ?)", params![path, tag])?;
+    }
+    Ok(added)
+}
+
+/// Untag a transcript directory; returns how many of the tags it had
+pub fn remove_tags(path: &str, tags: &[String]) -> Result<usize> {
+    ensure_writable("tag transcripts")?;
+    let conn = get_connection()?;
+    let mut removed = 0;
+    for tag in tags {
+        removed += conn.execute("DELETE FROM tags WHERE path = ? AND tag = ?", params![path, tag])?;
+    }
+    Ok(removed)
+}
+
+/// Tags of a transcript directory, alphabetically
+pub fn get_tags(path: &str) -> Result<Vec<String>> {
     let conn = get_connection()?;
+    let mut stmt = conn.prepare("SELECT tag FROM tags WHERE path = ? ORDER BY tag")?;
+    let tags = stmt
+        .query_map([path], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(tags)
+}
 
-    // Escape special FTS5 characters and wrap in quotes
-    let escaped_query = format!("\"{}\"", query.replace('"', "\"\""));
+/// An attachment whose text matches a search
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttachmentHit {
+    pub title: String,
+    pub channel: String,
+    pub path: String,
+    pub name: String,
+    pub snippet: String,
+}
+
+/// Attachments whose text contains `query` as a phrase, best first
+pub fn search_attachments(query: &str, limit: i32) -> Result<Vec<AttachmentHit>> {
+    let conn = get_connection()?;
 
     let mut stmt = conn.prepare(
         r#"
+        SELECT t.title, t.channel, a.path, a.name, snippet(attachments_fts, 1, '>>> ', ' <<<', '...', 24)
+        FROM attachments_fts a
+        JOIN transcripts t ON t.path = a.path
+        WHERE attachments_fts MATCH ?
+        ORDER BY rank
+        LIMIT ?
+        "#,
+    )?;
+    let hits = stmt
+        .query_map(params![fts_phrase(query), limit], |row| {
+            Ok(AttachmentHit {
+                title: row.get(0)?,
+                channel: row.get(1)?,
+                path: row.get(2)?,
+                name: row.get(3)?,
+                snippet: row.get(4)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(hits)
+}
+
+/// Whether any sentences are indexed (databases from older versions need a reindex)
+pub fn has_sentence_index() -> Result<bool> {
+    let conn = get_connection()?;
+    Ok(conn.prepare("SELECT 1 FROM sentences_fts LIMIT 1")?.exists([])?)
+}
+
+/// Escape special FTS5 characters and wrap in quotes
+fn fts_phrase(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+fn search_fts(fts_query: &str, limit: i32, filter: &TranscriptFilter) -> Result<Vec<SearchResult>> {
+    let conn = get_connection()?;
+
+    let mut sql = r#"
         SELECT
             t.id,
             t.video_id,
@@ -217,17 +1939,28 @@ pub fn search_transcripts(query: &str, limit: i32) -> Result<Vec<SearchResult>>
             t.platform,
             t.duration,
             t.path,
-            snippet(transcripts_fts, 2, '>>> ', ' <<<', '...', 32) as snippet
+            t.url,
+            snippet(transcripts_fts, -1, '>>> ', ' <<<', '...', 32) as snippet,
+            t.source,
+            rank
         FROM transcripts_fts
         JOIN transcripts t ON transcripts_fts.rowid = t.id
-        WHERE transcripts_fts MATCH ?1
-        ORDER BY rank
-        LIMIT ?2
-        "#,
-    )?;
+        WHERE transcripts_fts MATCH ?
+        "#
+    .to_string();
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(fts_query.to_string())];
+
+    filter.push_sql("t", &mut sql, &mut params_vec);
+
+    sql.push_str(" ORDER BY rank LIMIT ?");
+    params_vec.push(Box::new(limit));
+
+    let mut stmt = conn.prepare(&sql)?;
+
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
 
     let results = stmt
-        .query_map(params![escaped_query, limit], |row| {
+        .query_map(params_refs.as_slice(), |row| {
             Ok(SearchResult {
                 id: row.get(0)?,
                 video_id: row.get(1)?,
@@ -236,7 +1969,10 @@ pub fn search_transcripts(query: &str, limit: i32) -> Result<Vec<SearchResult>>
                 platform: row.get(4)?,
                 duration: row.get(5)?,
                 path: row.get(6)?,
-                snippet: row.get(7)?,
+                url: row.get(7)?,
+                snippet: row.get(8)?,
+                source: row.get(9)?,
+                rank: row.get(10)?,
             })
         })?
         .collect::<std::result::Result<Vec<_>, _>>()?;
@@ -244,6 +1980,100 @@ pub fn search_transcripts(query: &str, limit: i32) -> Result<Vec<SearchResult>>
     Ok(results)
 }
 
+/// Transcripts without an embedding from `model`: ID and the text to embed
+pub fn transcripts_missing_embedding(model: &str) -> Result<Vec<(i64, String)>> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT t.id, f.title || char(10) || f.description || char(10) || f.transcript_text
+        FROM transcripts t
+        JOIN transcripts_fts f ON f.rowid = t.id
+        LEFT JOIN embeddings e ON e.transcript_id = t.id AND e.model = ?
+        WHERE e.transcript_id IS NULL
+        ORDER BY t.id
+        "#,
+    )?;
+    let results = stmt
+        .query_map(params![model], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+pub fn save_embedding(transcript_id: i64, model: &str, vector: &[f32]) -> Result<()> {
+    ensure_writable("store embeddings")?;
+    let conn = get_connection()?;
+    let blob: Vec<u8> = vector.iter().flat_map(|v| v.to_le_bytes()).collect();
+    conn.execute(
+        "INSERT OR REPLACE INTO embeddings (transcript_id, model, vector) VALUES (?, ?, ?)",
+        params![transcript_id, model, blob],
+    )?;
+    Ok(())
+}
+
+/// Drop embeddings of transcripts that were removed or reindexed under a new ID
+pub fn remove_orphan_embeddings() -> Result<usize> {
+    ensure_writable("store embeddings")?;
+    let conn = get_connection()?;
+    let removed = conn.execute(
+        "DELETE FROM embeddings WHERE transcript_id NOT IN (SELECT id FROM transcripts)",
+        [],
+    )?;
+    Ok(removed)
+}
+
+/// Every transcript embedded with `model` that passes `filter`, with its vector
+pub fn embedded_transcripts(model: &str, filter: &TranscriptFilter) -> Result<Vec<(SearchResult, Vec<f32>)>> {
+    let conn = get_connection()?;
+
+    let mut sql = r#"
+        SELECT t.id, t.video_id, t.title, t.channel, t.platform, t.duration, t.path, t.url, t.source, e.vector
+        FROM embeddings e
+        JOIN transcripts t ON t.id = e.transcript_id
+        WHERE e.model = ?
+        "#
+    .to_string();
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(model.to_string())];
+    filter.push_sql("t", &mut sql, &mut params_vec);
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+
+    let results = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            let blob: Vec<u8> = row.get(9)?;
+            let vector = blob
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect();
+            Ok((
+                SearchResult {
+                    id: row.get(0)?,
+                    video_id: row.get(1)?,
+                    title: row.get(2)?,
+                    channel: row.get(3)?,
+                    platform: row.get(4)?,
+                    duration: row.get(5)?,
+                    path: row.get(6)?,
+                    url: row.get(7)?,
+                    snippet: None,
+                    source: row.get(8)?,
+                    rank: 0.0,
+                },
+                vector,
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Columns selected for `TranscriptRecord`
+const TRANSCRIPT_COLUMNS: &str =
+    "id, video_id, url, title, channel, channel_handle, platform, duration, upload_date, path, speaker_count, word_count, \
+     transcribed_by, source, transcribed_at";
+
 /// Transcript listing from database
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TranscriptRecord {
@@ -259,18 +2089,24 @@ pub struct TranscriptRecord {
     pub path: String,
     pub speaker_count: Option<i32>,
     pub word_count: Option<i32>,
+    pub transcribed_by: Option<String>,
+    /// `TranscriptSource` ID
+    pub source: String,
+    /// When it was indexed (UTC)
+    pub transcribed_at: Option<String>,
 }
 
-/// List all transcripts with optional filters
+/// List all transcripts with optional filters, most recently transcribed first
 pub fn list_all_transcripts(
     platform: Option<&str>,
     channel: Option<&str>,
     handle: Option<&str>,
+    filter: &TranscriptFilter,
     limit: i32,
 ) -> Result<Vec<TranscriptRecord>> {
     let conn = get_connection()?;
 
-    let mut query = "SELECT id, video_id, url, title, channel, channel_handle, platform, duration, upload_date, path, speaker_count, word_count FROM transcripts WHERE 1=1".to_string();
+    let mut query = format!("SELECT {} FROM transcripts WHERE 1=1", TRANSCRIPT_COLUMNS);
     let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
     if let Some(p) = platform {
@@ -288,7 +2124,9 @@ pub fn list_all_transcripts(
         params_vec.push(Box::new(format!("%{}%", h)));
     }
 
-    query.push_str(" ORDER BY transcribed_at DESC LIMIT ?");
+    filter.push_sql("transcripts", &mut query, &mut params_vec);
+
+    query.push_str(" ORDER BY transcribed_at DESC, id DESC LIMIT ?");
     params_vec.push(Box::new(limit));
 
     let mut stmt = conn.prepare(&query)?;
@@ -296,54 +2134,62 @@ pub fn list_all_transcripts(
     let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
 
     let results = stmt
-        .query_map(params_refs.as_slice(), |row| {
-            Ok(TranscriptRecord {
-                id: row.get(0)?,
-                video_id: row.get(1)?,
-                url: row.get(2)?,
-                title: row.get(3)?,
-                channel: row.get(4)?,
-                channel_handle: row.get(5)?,
-                platform: row.get(6)?,
-                duration: row.get(7)?,
-                upload_date: row.get(8)?,
-                path: row.get(9)?,
-                speaker_count: row.get(10)?,
-                word_count: row.get(11)?,
-            })
-        })?
+        .query_map(params_refs.as_slice(), transcript_record)?
         .collect::<std::result::Result<Vec<_>, _>>()?;
 
     Ok(results)
 }
 
-/// Get a transcript by video ID
+/// Map a transcripts row (in `TRANSCRIPT_COLUMNS` order) to a record
+fn transcript_record(row: &rusqlite::Row) -> rusqlite::Result<TranscriptRecord> {
+    Ok(TranscriptRecord {
+        id: row.get(0)?,
+        video_id: row.get(1)?,
+        url: row.get(2)?,
+        title: row.get(3)?,
+        channel: row.get(4)?,
+        channel_handle: row.get(5)?,
+        platform: row.get(6)?,
+        duration: row.get(7)?,
+        upload_date: row.get(8)?,
+        path: row.get(9)?,
+        speaker_count: row.get(10)?,
+        word_count: row.get(11)?,
+        transcribed_by: row.get(12)?,
+        source: row.get(13)?,
+        transcribed_at: row.get(14)?,
+    })
+}
+
+/// Get a transcript by video ID, preferring the API transcript over captions
 pub fn get_transcript_by_id(video_id: &str) -> Result<Option<TranscriptRecord>> {
     let conn = get_connection()?;
 
-    let mut stmt = conn.prepare(
-        "SELECT id, video_id, url, title, channel, channel_handle, platform, duration, upload_date, path, speaker_count, word_count FROM transcripts WHERE video_id = ?",
-    )?;
-
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transcripts WHERE video_id = ? ORDER BY source = 'api' DESC, id DESC LIMIT 1",
+        TRANSCRIPT_COLUMNS
+    ))?;
     let mut rows = stmt.query(params![video_id])?;
 
-    if let Some(row) = rows.next()? {
-        Ok(Some(TranscriptRecord {
-            id: row.get(0)?,
-            video_id: row.get(1)?,
-            url: row.get(2)?,
-            title: row.get(3)?,
-            channel: row.get(4)?,
-            channel_handle: row.get(5)?,
-            platform: row.get(6)?,
-            duration: row.get(7)?,
-            upload_date: row.get(8)?,
-            path: row.get(9)?,
-            speaker_count: row.get(10)?,
-            word_count: row.get(11)?,
-        }))
-    } else {
-        Ok(None)
+    match rows.next()? {
+        Some(row) => Ok(Some(transcript_record(row)?)),
+        None => Ok(None),
+    }
+}
+
+/// Get a transcript by the URL it was transcribed from
+pub fn get_transcript_by_url(url: &str) -> Result<Option<TranscriptRecord>> {
+    let conn = get_connection()?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transcripts WHERE url = ? ORDER BY source = 'api' DESC, transcribed_at DESC LIMIT 1",
+        TRANSCRIPT_COLUMNS
+    ))?;
+    let mut rows = stmt.query(params![url])?;
+
+    match rows.next()? {
+        Some(row) => Ok(Some(transcript_record(row)?)),
+        None => Ok(None),
     }
 }
 
@@ -355,6 +2201,8 @@ pub struct Stats {
     pub unique_platforms: i64,
     pub total_duration: Option<i64>,
     pub total_words: Option<i64>,
+    /// Transcript counts per `transcribed_by` user, most active first
+    pub transcribers: Vec<(String, i64)>,
 }
 
 /// Get database statistics
@@ -373,27 +2221,81 @@ pub fn get_stats() -> Result<Stats> {
         "#,
     )?;
 
-    let stats = stmt.query_row([], |row| {
+    let mut stats = stmt.query_row([], |row| {
         Ok(Stats {
             total_transcripts: row.get(0)?,
             unique_channels: row.get(1)?,
             unique_platforms: row.get(2)?,
             total_duration: row.get(3)?,
             total_words: row.get(4)?,
+            transcribers: Vec::new(),
         })
     })?;
 
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT transcribed_by, COUNT(*)
+        FROM transcripts
+        WHERE transcribed_by IS NOT NULL
+        GROUP BY transcribed_by
+        ORDER BY COUNT(*) DESC, transcribed_by
+        "#,
+    )?;
+    stats.transcribers = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
     Ok(stats)
 }
 
-/// Delete a transcript from the database
-pub fn delete_transcript(video_id: &str) -> Result<bool> {
+/// Every transcript of a video (captions and API ones alike), API first
+pub fn transcripts_for_video(video_id: &str) -> Result<Vec<TranscriptRecord>> {
     let conn = get_connection()?;
 
-    let changes = conn.execute(
-        "DELETE FROM transcripts WHERE video_id = ?",
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM transcripts WHERE video_id = ? ORDER BY source = 'api' DESC, id DESC",
+        TRANSCRIPT_COLUMNS
+    ))?;
+    let results = stmt
+        .query_map(params![video_id], transcript_record)?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+/// Delete a video's transcripts from the database, with their search rows, highlights,
+/// entities, embeddings, tags and attachment index
+pub fn delete_transcript(video_id: &str) -> Result<bool> {
+    ensure_writable("delete transcripts")?;
+    let mut conn = get_connection()?;
+    let tx = conn.transaction()?;
+
+    for table in ["sentences_fts", "highlights", "entity_mentions", "embeddings"] {
+        tx.execute(
+            &format!(
+                "DELETE FROM {} WHERE transcript_id IN (SELECT id FROM transcripts WHERE video_id = ?)",
+                table
+            ),
+            params![video_id],
+        )?;
+    }
+    for table in ["attachments_fts", "tags"] {
+        tx.execute(
+            &format!("DELETE FROM {} WHERE path IN (SELECT path FROM transcripts WHERE video_id = ?)", table),
+            params![video_id],
+        )?;
+    }
+    tx.execute(
+        "DELETE FROM audio_files WHERE EXISTS (SELECT 1 FROM transcripts t WHERE t.video_id = ?
+         AND substr(audio_files.path, 1, length(t.path) + 1) = t.path || '/')",
+        params![video_id],
+    )?;
+    tx.execute(
+        "DELETE FROM transcripts_fts WHERE rowid IN (SELECT id FROM transcripts WHERE video_id = ?)",
         params![video_id],
     )?;
+    let changes = tx.execute("DELETE FROM transcripts WHERE video_id = ?", params![video_id])?;
 
+    tx.commit()?;
     Ok(changes > 0)
 }
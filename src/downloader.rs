@@ -1,10 +1,20 @@
+use std::io::{self, BufRead, BufReader, IsTerminal, Read};
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use serde::{Deserialize, Serialize};
 
-use crate::config::{downloads_dir, ensure_directories, firefox_cookies_args};
+use crate::config::{
+    cookies_enabled, downloads_dir, ensure_directories, firefox_cookies_args, politeness, read_only,
+    settings,
+};
+use crate::database::{cache_listing, cache_metadata, get_cached_listing, get_cached_metadata};
 use crate::error::{Error, Result};
+use crate::platform::{Platform, YouTube};
+use crate::transcriber::Section;
 
 /// Playlist entry from yt-dlp --flat-playlist
 /// Used for channel listings and YouTube search results
@@ -50,7 +60,8 @@ impl YtDlpPlaylistEntry {
             title,
             url: self
                 .url
-                .unwrap_or_else(|| format!("https://www.youtube.com/watch?v={}", id)),
+                .or_else(|| YouTube.canonical_url(&id))
+                .unwrap_or_default(),
             channel: self
                 .channel
                 .or(self.uploader)
@@ -67,6 +78,14 @@ impl YtDlpPlaylistEntry {
     }
 }
 
+/// Chapter or segment exposed by the platform (e.g. Twitch game changes), times in seconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoChapter {
+    pub title: String,
+    pub start_time: f64,
+    pub end_time: Option<f64>,
+}
+
 /// Video metadata extracted from yt-dlp
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoMetadata {
@@ -84,6 +103,49 @@ pub struct VideoMetadata {
     pub url: String,
     pub webpage_url: Option<String>,
     pub extractor: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chapters: Vec<VideoChapter>,
+    /// Show name for podcast episodes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub episode: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub episode_number: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub season_number: Option<i64>,
+    /// Configured user name of whoever transcribed it (team libraries)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcribed_by: Option<String>,
+    /// Spoken language reported by the platform (e.g. "es")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+impl VideoMetadata {
+    /// Platform chapters as transcript sections
+    pub fn sections(&self) -> Vec<Section> {
+        chapter_sections(&self.chapters)
+    }
+}
+
+/// Chapters as transcript sections, start in milliseconds
+pub fn chapter_sections(chapters: &[VideoChapter]) -> Vec<Section> {
+    chapters
+        .iter()
+        .map(|c| Section {
+            title: c.title.clone(),
+            start: (c.start_time * 1000.0) as i64,
+        })
+        .collect()
+}
+
+/// Raw yt-dlp chapter entry
+#[derive(Debug, Deserialize)]
+struct YtDlpChapter {
+    title: Option<String>,
+    start_time: Option<f64>,
+    end_time: Option<f64>,
 }
 
 /// Raw yt-dlp JSON output
@@ -102,6 +164,16 @@ struct YtDlpOutput {
     thumbnail: Option<String>,
     webpage_url: Option<String>,
     extractor: Option<String>,
+    chapters: Option<Vec<YtDlpChapter>>,
+    series: Option<String>,
+    episode: Option<String>,
+    episode_number: Option<i64>,
+    season_number: Option<i64>,
+    language: Option<String>,
+    /// Media URL of the selected format (only set when a single format is selected)
+    #[serde(rename = "url")]
+    media_url: Option<String>,
+    protocol: Option<String>,
 }
 
 impl YtDlpOutput {
@@ -109,9 +181,11 @@ impl YtDlpOutput {
         VideoMetadata {
             id: self.id.unwrap_or_default(),
             title: self.title.unwrap_or_else(|| "Unknown Title".to_string()),
+            // Podcast extractors usually only provide the show name as "series"
             channel: self
                 .channel
                 .or(self.uploader.clone())
+                .or(self.series.clone())
                 .unwrap_or_else(|| "Unknown Channel".to_string()),
             uploader: self.uploader,
             uploader_id: self.uploader_id,
@@ -124,6 +198,24 @@ impl YtDlpOutput {
             url: url.to_string(),
             webpage_url: self.webpage_url,
             extractor: self.extractor,
+            chapters: self
+                .chapters
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|c| {
+                    Some(VideoChapter {
+                        title: c.title.unwrap_or_else(|| "Untitled".to_string()),
+                        start_time: c.start_time?,
+                        end_time: c.end_time,
+                    })
+                })
+                .collect(),
+            series: self.series,
+            episode: self.episode,
+            episode_number: self.episode_number,
+            season_number: self.season_number,
+            transcribed_by: None,
+            language: self.language,
         }
     }
 }
@@ -145,12 +237,12 @@ fn find_ytdlp() -> Result<PathBuf> {
     }
 
     // Try PATH
-    if let Ok(output) = Command::new("which").arg("yt-dlp").output() {
-        if output.status.success() {
-            let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if !path.is_empty() {
-                return Ok(PathBuf::from(path));
-            }
+    if let Ok(output) = Command::new("which").arg("yt-dlp").output()
+        && output.status.success()
+    {
+        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !path.is_empty() {
+            return Ok(PathBuf::from(path));
         }
     }
 
@@ -159,34 +251,276 @@ fn find_ytdlp() -> Result<PathBuf> {
     ))
 }
 
-/// Run yt-dlp with the given arguments
+/// Number of running yt-dlp processes, bounded by `Politeness::max_concurrent_fetches`
+static RUNNING_FETCHES: Mutex<usize> = Mutex::new(0);
+static FETCH_SLOT_FREED: Condvar = Condvar::new();
+/// When the most recent yt-dlp process was started
+static LAST_FETCH_START: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Slot held while a yt-dlp process runs; released on drop
+struct FetchSlot;
+
+impl Drop for FetchSlot {
+    fn drop(&mut self) {
+        let mut running = RUNNING_FETCHES.lock().unwrap_or_else(|e| e.into_inner());
+        *running = running.saturating_sub(1);
+        FETCH_SLOT_FREED.notify_one();
+    }
+}
+
+/// Wait for a free yt-dlp slot and honor the minimum delay between process starts
+fn acquire_fetch_slot() -> FetchSlot {
+    let pacing = politeness();
+
+    let mut running = RUNNING_FETCHES.lock().unwrap_or_else(|e| e.into_inner());
+    while *running >= pacing.max_concurrent_fetches {
+        running = FETCH_SLOT_FREED.wait(running).unwrap_or_else(|e| e.into_inner());
+    }
+    *running += 1;
+    drop(running);
+
+    let mut last_start = LAST_FETCH_START.lock().unwrap_or_else(|e| e.into_inner());
+    if let (Some(delay), Some(last)) = (pacing.fetch_delay, *last_start) {
+        let delay = Duration::try_from_secs_f64(delay.max(0.0)).unwrap_or_default();
+        let elapsed = last.elapsed();
+        if elapsed < delay {
+            std::thread::sleep(delay - elapsed);
+        }
+    }
+    *last_start = Some(Instant::now());
+
+    FetchSlot
+}
+
+/// Check yt-dlp stderr for bot checks and rate limiting
+fn is_throttled(stderr: &str) -> bool {
+    let lower = stderr.to_lowercase();
+    (lower.contains("confirm you") && lower.contains("not a bot"))
+        || lower.contains("http error 429")
+        || lower.contains("too many requests")
+}
+
+/// Check yt-dlp stderr for age gates and consent walls
+fn is_age_restricted(stderr: &str) -> bool {
+    let lower = stderr.to_lowercase();
+    lower.contains("confirm your age")
+        || lower.contains("age-restricted")
+        || lower.contains("age restricted")
+        || lower.contains("inappropriate for some users")
+        || lower.contains("consent.youtube.com")
+}
+
+/// First error line from yt-dlp stderr, for concise messages
+fn error_summary(stderr: &str) -> String {
+    stderr
+        .lines()
+        .find(|line| line.starts_with("ERROR"))
+        .unwrap_or_else(|| stderr.lines().next().unwrap_or_default())
+        .trim()
+        .to_string()
+}
+
+/// Run yt-dlp, retrying with browser cookies when the request is throttled or age-gated
 fn run_ytdlp(args: &[&str]) -> Result<String> {
+    let use_cookies = cookies_enabled();
+
+    match run_ytdlp_once(args, use_cookies) {
+        Err(Error::Throttled(_)) if !use_cookies => {
+            eprintln!("Request was throttled, retrying with browser cookies...");
+            run_ytdlp_once(args, true)
+        }
+        Err(Error::AgeRestricted(_)) if !use_cookies => {
+            eprintln!("Video is age-restricted, retrying with browser cookies...");
+            run_ytdlp_once(args, true)
+        }
+        Err(Error::Throttled(reason)) if io::stdin().is_terminal() && io::stderr().is_terminal() => {
+            eprintln!("Request was throttled: {}", reason);
+            eprint!("Sign in to YouTube in Firefox to refresh your session, then press Enter to retry (Ctrl-C to abort): ");
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            run_ytdlp_once(args, true)
+        }
+        result => result,
+    }
+}
+
+/// Run yt-dlp once with the given arguments
+fn run_ytdlp_once(args: &[&str], use_cookies: bool) -> Result<String> {
     let ytdlp = find_ytdlp()?;
-    let cookies_args = firefox_cookies_args();
 
     let mut cmd = Command::new(&ytdlp);
-    for arg in &cookies_args {
+    if use_cookies {
+        for arg in firefox_cookies_args() {
+            cmd.arg(arg);
+        }
+    }
+    for arg in politeness().ytdlp_args() {
         cmd.arg(arg);
     }
+    // Progress lines (also printed in --print-json's quiet mode) show the watchdog that a
+    // long download is still moving; they are left out of the output
+    cmd.args(["--progress", "--newline", "--progress-template"])
+        .arg(format!("download:{} %(progress._percent_str)s", PROGRESS_MARKER))
+        .arg("--progress-template")
+        .arg(format!("postprocess:{} %(progress.postprocessor)s", PROGRESS_MARKER));
     for arg in args {
         cmd.arg(arg);
     }
+    for arg in &settings().ytdlp_args {
+        cmd.arg(arg);
+    }
 
-    let output = cmd.output()?;
+    let _slot = acquire_fetch_slot();
+    let output = run_supervised(cmd)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(Error::Download(stderr.to_string()));
+        if is_throttled(&stderr) {
+            return Err(Error::Throttled(error_summary(&stderr)));
+        }
+        if is_age_restricted(&stderr) {
+            return Err(Error::AgeRestricted(error_summary(&stderr)));
+        }
+        return Err(Error::Download(stderr.trim().to_string()));
     }
 
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// Start of the progress lines yt-dlp is asked for, told apart from JSON output
+const PROGRESS_MARKER: &str = "[yt-cli-progress]";
+
+/// How often the watchdog checks on a running yt-dlp
+const WATCHDOG_POLL: Duration = Duration::from_millis(250);
+
+/// Run a yt-dlp command, killing it (and the ffmpeg it started) when it runs longer than
+/// `Politeness::ytdlp_timeout` or prints nothing for `Politeness::ytdlp_idle_timeout`
+///
+/// A killed run fails like any download, its reason in the error for the caller to report
+/// and log, so batch jobs move on to the next video instead of waiting on a hung extractor.
+fn run_supervised(mut cmd: Command) -> Result<Output> {
+    let limits = politeness();
+    let mut child = cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+    let started = Instant::now();
+    let last_output = Arc::new(Mutex::new(started));
+    let stdout = read_output(child.stdout.take(), last_output.clone(), true);
+    let stderr = read_output(child.stderr.take(), last_output.clone(), false);
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        let idle = last_output.lock().map(|last| last.elapsed()).unwrap_or_default();
+        let reason = match (limits.ytdlp_timeout, limits.ytdlp_idle_timeout) {
+            (Some(limit), _) if started.elapsed() > minutes(limit) => {
+                Some(format!("ran for over {} min", limit))
+            }
+            (_, Some(limit)) if idle > minutes(limit) => Some(format!("printed nothing for {} min", limit)),
+            _ => None,
+        };
+        if let Some(reason) = reason {
+            kill_tree(&mut child);
+            // The readers finish on their own once the pipes close
+            return Err(Error::Download(format!("yt-dlp {} and was stopped", reason)));
+        }
+
+        thread::sleep(WATCHDOG_POLL);
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.map(|h| h.join().unwrap_or_default()).unwrap_or_default(),
+        stderr: stderr.map(|h| h.join().unwrap_or_default()).unwrap_or_default(),
+    })
+}
+
+/// Collect a pipe's output on a thread, noting when each line arrives; progress lines on
+/// stdout only count as activity
+fn read_output(
+    pipe: Option<impl Read + Send + 'static>,
+    last_output: Arc<Mutex<Instant>>,
+    is_stdout: bool,
+) -> Option<JoinHandle<Vec<u8>>> {
+    let pipe = pipe?;
+    Some(thread::spawn(move || {
+        let mut reader = BufReader::new(pipe);
+        let mut output = Vec::new();
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            match reader.read_until(b'\n', &mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    if let Ok(mut last) = last_output.lock() {
+                        *last = Instant::now();
+                    }
+                    if !(is_stdout && line.starts_with(PROGRESS_MARKER.as_bytes())) {
+                        output.extend_from_slice(&line);
+                    }
+                }
+            }
+        }
+        output
+    }))
+}
+
+/// Stop yt-dlp, its children (ffmpeg) first so none keeps running on its own
+fn kill_tree(child: &mut Child) {
+    if cfg!(unix) {
+        let _ = Command::new("pkill")
+            .args(["-TERM", "-P", &child.id().to_string()])
+            .stderr(Stdio::null())
+            .status();
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+/// A limit in minutes; one too large to represent never runs out
+fn minutes(minutes: f64) -> Duration {
+    Duration::try_from_secs_f64(minutes * 60.0).unwrap_or(Duration::MAX)
+}
+
 /// Extract video metadata without downloading
+///
+/// Lookups within `[cache] metadata_ttl` seconds are answered from the database (unless
+/// `--no-cache` is given), so checks repeated over the same URLs don't rerun yt-dlp.
 pub fn extract_metadata(url: &str) -> Result<VideoMetadata> {
+    let cache = settings().cache;
+    if cache.metadata_ttl > 0
+        && !cache.bypass
+        && let Ok(Some(cached)) = get_cached_metadata(url, cache.metadata_ttl)
+        && let Ok(metadata) = serde_json::from_str(&cached)
+    {
+        return Ok(metadata);
+    }
+
     let output = run_ytdlp(&["--dump-json", "--no-download", url])?;
     let yt_output: YtDlpOutput = serde_json::from_str(&output)?;
-    Ok(yt_output.into_metadata(url))
+    let metadata = yt_output.into_metadata(url);
+
+    if cache.metadata_ttl > 0
+        && !read_only()
+        && let Ok(json) = serde_json::to_string(&metadata)
+    {
+        let _ = cache_metadata(url, &json);
+    }
+    Ok(metadata)
+}
+
+/// Resolve a plain HTTP(S) audio URL for the best audio format, without downloading
+///
+/// Returns `None` when the selected format is streamed (HLS/DASH) rather than a single file.
+pub fn resolve_audio_url(url: &str) -> Result<Option<(String, VideoMetadata)>> {
+    let output = run_ytdlp(&["-f", "bestaudio", "--dump-json", "--no-download", url])?;
+    let mut yt_output: YtDlpOutput = serde_json::from_str(&output)?;
+
+    let is_http = matches!(yt_output.protocol.as_deref(), Some("http" | "https"));
+    let media_url = yt_output.media_url.take().filter(|_| is_http);
+
+    Ok(media_url.map(|media_url| (media_url, yt_output.into_metadata(url))))
 }
 
 /// Download audio from a video URL
@@ -233,9 +567,83 @@ pub fn download_audio(url: &str) -> Result<(PathBuf, VideoMetadata)> {
     )))
 }
 
+/// Download a video's captions (manual preferred, else auto-generated) without the media
+///
+/// `lang` is a yt-dlp language code such as "en"; regional variants ("en-US") also match.
+/// Returns the subtitle file, or `None` when the video has no captions in that language.
+pub fn download_captions(url: &str, lang: &str) -> Result<(Option<PathBuf>, VideoMetadata)> {
+    ensure_directories()?;
+
+    let output_id = uuid::Uuid::new_v4().to_string()[..8].to_string();
+    let output_template = downloads_dir().join(format!("{}.%(ext)s", output_id));
+    let sub_langs = format!("{0},{0}-.*", lang);
+
+    let output = run_ytdlp(&[
+        "--skip-download",
+        "--write-subs",
+        "--write-auto-subs",
+        "--sub-langs",
+        &sub_langs,
+        "--sub-format",
+        "json3/vtt",
+        "--print-json",
+        "-o",
+        output_template.to_str().unwrap(),
+        url,
+    ])?;
+
+    let yt_output: YtDlpOutput = serde_json::from_str(&output)?;
+    let metadata = yt_output.into_metadata(url);
+
+    // Subtitle files are named {output_id}.{lang}.{ext}; prefer the plain language code
+    let mut files: Vec<PathBuf> = std::fs::read_dir(downloads_dir())?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .map(|n| n.to_string_lossy().starts_with(&output_id))
+                .unwrap_or(false)
+        })
+        .collect();
+    files.sort_by_key(|path| path.to_string_lossy().len());
+
+    let mut files = files.into_iter();
+    let captions = files.next();
+    for extra in files {
+        let _ = std::fs::remove_file(extra);
+    }
+
+    Ok((captions, metadata))
+}
+
 /// Fetch video entries from a playlist URL (channel or search)
-/// Uses --flat-playlist to get metadata without downloading
+///
+/// Listings fetched within `[cache] listings_ttl` seconds are reused from the database
+/// unless `--no-cache` is given. Cache failures only mean asking yt-dlp again.
 pub fn fetch_playlist_entries(url: &str, limit: usize) -> Result<Vec<PlaylistEntry>> {
+    let cache = settings().cache;
+    if cache.listings_ttl == 0 {
+        return fetch_playlist_entries_uncached(url, limit);
+    }
+
+    if !cache.bypass
+        && let Ok(Some(cached)) = get_cached_listing(url, limit, cache.listings_ttl)
+        && let Ok(entries) = serde_json::from_str(&cached)
+    {
+        return Ok(entries);
+    }
+
+    let entries = fetch_playlist_entries_uncached(url, limit)?;
+    if !read_only()
+        && let Ok(json) = serde_json::to_string(&entries)
+    {
+        let _ = cache_listing(url, limit, &json);
+    }
+    Ok(entries)
+}
+
+/// Uses --flat-playlist to get metadata without downloading
+fn fetch_playlist_entries_uncached(url: &str, limit: usize) -> Result<Vec<PlaylistEntry>> {
     let limit_str = limit.to_string();
     let output = run_ytdlp(&[
         "--dump-json",
@@ -275,35 +683,67 @@ pub fn fetch_playlist_entries(url: &str, limit: usize) -> Result<Vec<PlaylistEnt
 
 /// Fetch latest videos from a YouTube channel
 pub fn fetch_channel_videos(channel_url: &str, limit: usize) -> Result<Vec<PlaylistEntry>> {
-    let videos_url = normalize_channel_url(channel_url);
+    let videos_url = YouTube::channel_videos_url(channel_url);
     fetch_playlist_entries(&videos_url, limit)
 }
 
-/// Search YouTube for videos
-pub fn search_youtube(query: &str, limit: usize) -> Result<Vec<PlaylistEntry>> {
-    let search_url = format!("ytsearch{}:{}", limit, query);
-    fetch_playlist_entries(&search_url, limit)
+/// Channel found by [`search_channels`]
+#[derive(Debug, Clone)]
+pub struct ChannelMatch {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub handle: Option<String>,
+    pub followers: Option<i64>,
 }
 
-/// Normalize channel URL to point to videos tab
-fn normalize_channel_url(url: &str) -> String {
-    let url = url.trim_end_matches('/');
-
-    // If already pointing to /videos, return as-is
-    if url.ends_with("/videos") {
-        return url.to_string();
-    }
+/// Raw yt-dlp entry of a channel search result (internal)
+#[derive(Debug, Deserialize)]
+struct YtDlpChannelEntry {
+    id: Option<String>,
+    title: Option<String>,
+    channel: Option<String>,
+    url: Option<String>,
+    uploader_id: Option<String>,
+    channel_follower_count: Option<i64>,
+}
 
-    // If it's a channel URL, append /videos
-    if url.contains("youtube.com/") {
-        return format!("{}/videos", url);
-    }
+/// Search YouTube for channels named like `query`, most relevant first
+pub fn search_channels(query: &str, limit: usize) -> Result<Vec<ChannelMatch>> {
+    // `sp=EgIQAg==` is YouTube's "Type: Channel" search filter
+    let search_url = reqwest::Url::parse_with_params(
+        "https://www.youtube.com/results?sp=EgIQAg%3D%3D",
+        &[("search_query", query)],
+    )
+    .map_err(|e| Error::Download(format!("Invalid channel search '{}': {}", query, e)))?;
+    let limit_str = limit.to_string();
+    let output = run_ytdlp(&[
+        "--dump-json",
+        "--flat-playlist",
+        "--playlist-end",
+        &limit_str,
+        "--no-warnings",
+        search_url.as_str(),
+    ])?;
 
-    // Assume it's a channel handle if it starts with @
-    if url.starts_with('@') {
-        return format!("https://www.youtube.com/{}/videos", url);
-    }
+    Ok(output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<YtDlpChannelEntry>(line.trim()).ok())
+        .filter_map(|entry| {
+            let id = entry.id.filter(|id| id.starts_with("UC"))?;
+            Some(ChannelMatch {
+                name: entry.title.or(entry.channel).unwrap_or_else(|| id.clone()),
+                url: entry.url.unwrap_or_else(|| format!("https://www.youtube.com/channel/{}", id)),
+                handle: entry.uploader_id.filter(|h| h.starts_with('@')),
+                followers: entry.channel_follower_count,
+                id,
+            })
+        })
+        .collect())
+}
 
-    // Assume it's a channel ID
-    format!("https://www.youtube.com/channel/{}/videos", url)
+/// Search YouTube for videos
+pub fn search_youtube(query: &str, limit: usize) -> Result<Vec<PlaylistEntry>> {
+    let search_url = format!("ytsearch{}:{}", limit, query);
+    fetch_playlist_entries(&search_url, limit)
 }
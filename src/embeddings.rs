@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::config::settings;
+use crate::error::{Error, Result};
+
+/// Characters of a transcript sent for embedding, well within the usual 8k-token input limit
+pub const MAX_INPUT_CHARS: usize = 20_000;
+
+#[derive(Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingData {
+    #[serde(default)]
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+/// Embed `texts` with the `[embeddings]` model, one vector per text in the same order
+pub async fn embed(texts: &[String]) -> Result<Vec<Vec<f32>>> {
+    let config = &settings().embeddings;
+    let key = std::env::var(&config.api_key_env).map_err(|_| {
+        Error::Config(format!("{} is not set ([embeddings] api_key_env)", config.api_key_env))
+    })?;
+
+    let input: Vec<&str> = texts.iter().map(|text| truncate(text, MAX_INPUT_CHARS)).collect();
+    let client = Client::builder().timeout(Duration::from_secs(120)).build()?;
+    let response = client
+        .post(&config.endpoint)
+        .bearer_auth(key)
+        .json(&json!({ "model": config.model, "input": input }))
+        .send()
+        .await?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(Error::Llm(format!("{}: {}", status, text)));
+    }
+
+    let mut response: EmbeddingResponse = response.json().await?;
+    if response.data.len() != texts.len() {
+        return Err(Error::Llm(format!(
+            "expected {} embedding(s), got {}",
+            texts.len(),
+            response.data.len()
+        )));
+    }
+    response.data.sort_by_key(|d| d.index);
+    Ok(response.data.into_iter().map(|d| d.embedding).collect())
+}
+
+/// Cosine similarity, 0 for vectors of different lengths or zero vectors
+pub fn cosine(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f64, 0.0f64, 0.0f64);
+    for (&x, &y) in a.iter().zip(b) {
+        let (x, y) = (f64::from(x), f64::from(y));
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+fn truncate(text: &str, max_chars: usize) -> &str {
+    match text.char_indices().nth(max_chars) {
+        Some((i, _)) => &text[..i],
+        None => text,
+    }
+}
@@ -0,0 +1,58 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::config::settings;
+use crate::downloader::VideoMetadata;
+
+/// Title patterns tried after a channel's own `episode_patterns`, e.g. "Market Recap #42",
+/// "Market Recap - Episode 42: The Fed" or "Ep. 42 | The Fed"
+const BUILTIN_PATTERNS: &[&str] = &[
+    r"(?i)^(?P<series>.*?\S)\s*[-–—:|,]?\s*\b(?:episode|ep\.?|no\.|part)\s*(?P<episode>\d+)\b",
+    r"(?i)^(?P<series>.*?\S)\s*[-–—:|,]?\s*#(?P<episode>\d+)\b",
+    r"(?i)^(?:episode|ep\.?|#)\s*(?P<episode>\d+)\s*[-–—:|]",
+];
+
+static BUILTIN_REGEXES: LazyLock<Vec<Regex>> =
+    LazyLock::new(|| BUILTIN_PATTERNS.iter().map(|pattern| Regex::new(pattern).unwrap()).collect());
+
+/// Episode found in a title
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Episode {
+    /// Show name, when the title has one before the number
+    pub series: Option<String>,
+    pub number: i64,
+}
+
+/// Find the episode in `title` with the channel's `episode_patterns`, then the built-in ones
+///
+/// Patterns need an `episode` group for the number and may have a `series` group.
+pub fn detect(title: &str, channel: &str, handle: Option<&str>) -> Option<Episode> {
+    let configured = settings().episode_patterns(channel, handle);
+    for regex in configured.iter().chain(BUILTIN_REGEXES.iter()) {
+        let Some(captures) = regex.captures(title) else {
+            continue;
+        };
+        let Some(number) = captures.name("episode").and_then(|m| m.as_str().parse().ok()) else {
+            continue;
+        };
+        let series = captures
+            .name("series")
+            .map(|m| m.as_str().trim_matches(|c: char| c.is_whitespace() || "-–—:|,".contains(c)))
+            .filter(|s| !s.is_empty())
+            .map(String::from);
+        return Some(Episode { series, number });
+    }
+    None
+}
+
+/// Fill in the series and episode number from the title where the platform gave none
+pub fn fill_in(metadata: &mut VideoMetadata) {
+    if metadata.episode_number.is_some() {
+        return;
+    }
+    if let Some(episode) = detect(&metadata.title, &metadata.channel, metadata.uploader_id.as_deref()) {
+        metadata.episode_number = Some(episode.number);
+        metadata.series = metadata.series.take().or(episode.series);
+    }
+}
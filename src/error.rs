@@ -1,3 +1,5 @@
+use std::io::{self, IsTerminal};
+
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -11,9 +13,42 @@ pub enum Error {
     #[error("Download failed: {0}")]
     Download(String),
 
+    #[error("Throttled by the platform: {0}")]
+    Throttled(String),
+
+    #[error("Age-restricted video: {0}")]
+    AgeRestricted(String),
+
     #[error("Transcription failed: {0}")]
     Transcription(String),
 
+    #[error("Read-only mode: refusing to {0} (unset --read-only / YT_TRANSCRIBE_READ_ONLY to allow writes)")]
+    ReadOnly(String),
+
+    #[error("Language model request failed: {0}")]
+    Llm(String),
+
+    #[error("Alignment failed: {0}")]
+    Aligner(String),
+
+    #[error("ffmpeg failed: {0}")]
+    Ffmpeg(String),
+
+    #[error("Storage backend error: {0}")]
+    Storage(String),
+
+    #[error("Notification failed: {0}")]
+    Notify(String),
+
+    #[error("Batch failed: {0}")]
+    Script(String),
+
+    #[error("Sync conflict: {0}")]
+    SyncConflict(String),
+
+    #[error("Self-update failed: {0}")]
+    Update(String),
+
     #[error("File not found: {0}")]
     FileNotFound(String),
 
@@ -30,4 +65,109 @@ pub enum Error {
     Http(#[from] reqwest::Error),
 }
 
+impl Error {
+    /// What the user can do about this error, shown under it by `main`
+    pub fn hint(&self) -> Option<String> {
+        let hint = match self {
+            Error::ApiKeyMissing => "Or set ASSEMBLYAI_API_KEY (DEEPGRAM_API_KEY for --backend deepgram).",
+            Error::Download(message) => return download_hint(message),
+            Error::Throttled(_) => {
+                "Sign in to YouTube in Firefox (or refresh the session), then retry. \
+                 Slowing down with --sleep-requests/--fetch-delay also helps."
+            }
+            Error::AgeRestricted(_) => {
+                "To access it:\n  \
+                 1. Sign in to YouTube in Firefox with an age-verified account\n  \
+                 2. Open the video once in Firefox and accept the content warning or consent prompt\n  \
+                 3. Retry (Docker: mount the Firefox profile via FIREFOX_COOKIES_PATH)"
+            }
+            Error::Transcription(message) => return transcription_hint(message),
+            Error::SyncConflict(_) => {
+                "Check `yt-cli sync-remote status`; `push --force` keeps this library, `pull --force` the remote's."
+            }
+            Error::Llm(message) if message.starts_with("401") || message.starts_with("403") => {
+                "Check the key named by `api_key_env` under [ask] / [embeddings] in config.toml."
+            }
+            Error::Database(rusqlite::Error::SqliteFailure(e, _)) => match e.code {
+                rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked => {
+                    "Another yt-cli process (the daemon, a queue run?) is writing to the database; \
+                     retry when it's done."
+                }
+                rusqlite::ErrorCode::DiskFull => {
+                    "Free up disk space, then run `yt-cli reindex --pending` to finish what wasn't indexed."
+                }
+                _ => return None,
+            },
+            Error::Io(e) => match e.kind() {
+                std::io::ErrorKind::PermissionDenied => {
+                    "Check the permissions of the data directory (YT_TRANSCRIBE_DATA_DIR or `data_dir` \
+                     in config.toml)."
+                }
+                std::io::ErrorKind::StorageFull => {
+                    "Free up disk space, then run `yt-cli reindex --pending` to finish what wasn't indexed."
+                }
+                _ => return None,
+            },
+            Error::Http(e) if e.is_connect() || e.is_timeout() => "Check your network connection and retry.",
+            _ => return None,
+        };
+        Some(hint.to_string())
+    }
+}
+
+/// Known yt-dlp failures and how to get past them
+fn download_hint(message: &str) -> Option<String> {
+    let lower = message.to_lowercase();
+    let hint = if lower.contains("requested format") && lower.contains("not available") {
+        "Pick another format, e.g. --ytdlp-args \"-f bestaudio/best\" (list them with `yt-dlp -F URL`), \
+         or update yt-dlp."
+    } else if lower.starts_with("yt-dlp printed nothing for") || lower.starts_with("yt-dlp ran for over") {
+        "If it was only slow (hours-long streams, a slow connection), raise --ytdlp-idle-timeout or \
+         --ytdlp-timeout; 0 turns a limit off."
+    } else if lower.contains("private video") || lower.contains("members-only") || lower.contains("join this channel") {
+        "Sign in to YouTube in Firefox with an account that can watch it; its cookies are used when \
+         YT_TRANSCRIBE_COOKIES isn't \"none\"."
+    } else if lower.contains("not available in your country") || lower.contains("geo restrict") {
+        "The video is region-locked; try a proxy with --ytdlp-args \"--proxy URL\"."
+    } else if lower.contains("premieres in") || lower.contains("live event will begin") {
+        "The video hasn't aired yet; retry once it has."
+    } else if lower.contains("unsupported url") {
+        "yt-dlp doesn't recognize this URL; check it, or update yt-dlp if the site is supported."
+    } else if lower.contains("unable to extract") || lower.contains("http error 403") || lower.contains("nsig") {
+        "The platform likely changed; update yt-dlp (`yt-dlp -U`, `brew upgrade yt-dlp` or `pip install -U yt-dlp`)."
+    } else if lower.contains("video unavailable") {
+        "Check the URL; the video may have been removed or made private."
+    } else {
+        return None;
+    };
+    Some(hint.to_string())
+}
+
+fn transcription_hint(message: &str) -> Option<String> {
+    let lower = message.to_lowercase();
+    let hint = if lower.contains("(401") || lower.contains("(403") || lower.contains("invalid api key") {
+        "Check the API key: run `yt-cli init --force` or set ASSEMBLYAI_API_KEY / DEEPGRAM_API_KEY."
+    } else if lower.contains("(402") || lower.contains("insufficient") || lower.contains("balance") {
+        "Add credit to the account (AssemblyAI: https://www.assemblyai.com/app/account)."
+    } else if lower.contains("(429") {
+        "The service is rate limiting requests; retry in a few minutes."
+    } else {
+        return None;
+    };
+    Some(hint.to_string())
+}
+
+/// Print an error and, when known, what to do about it
+///
+/// Used wherever an error is reported, including for one item of a batch that goes on.
+pub fn print_error(e: &Error) {
+    eprintln!("Error: {}", e);
+    if let Some(hint) = e.hint() {
+        // Set apart from the error in color on a terminal, and by the `hint:` label otherwise
+        let color = io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+        let (start, end) = if color { ("\x1b[1;36m", "\x1b[0m") } else { ("", "") };
+        eprintln!("{}hint:{} {}", start, end, hint.replace('\n', "\n      "));
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
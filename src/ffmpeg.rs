@@ -0,0 +1,63 @@
+use std::ffi::OsStr;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{Error, Result};
+
+/// Cut `input` from `start_ms` to `end_ms` into `output`
+///
+/// Audio is copied without re-encoding when the output has the input's extension;
+/// other extensions (e.g. `.wav`, `.m4a`) are converted by ffmpeg.
+pub fn cut(input: &Path, output: &Path, start_ms: i64, end_ms: i64) -> Result<()> {
+    let mut cmd = Command::new("ffmpeg");
+    cmd.args(["-hide_banner", "-loglevel", "error", "-y"])
+        .arg("-ss")
+        .arg(seconds(start_ms))
+        .arg("-i")
+        .arg(input)
+        .arg("-t")
+        .arg(seconds(end_ms - start_ms))
+        .arg("-vn");
+    if output.extension() == input.extension() {
+        cmd.args(["-c", "copy"]);
+    }
+    cmd.arg(output);
+
+    let result = cmd.output().map_err(|e| match e.kind() {
+        ErrorKind::NotFound => Error::Ffmpeg("ffmpeg not found. Install it with: brew install ffmpeg".to_string()),
+        _ => e.into(),
+    })?;
+    if !result.status.success() {
+        return Err(Error::Ffmpeg(String::from_utf8_lossy(&result.stderr).trim().to_string()));
+    }
+
+    Ok(())
+}
+
+/// Length in whole seconds of an audio file or URL, as read by ffprobe
+pub fn duration(input: impl AsRef<OsStr>) -> Result<i64> {
+    let result = Command::new("ffprobe")
+        .args(["-v", "error", "-show_entries", "format=duration", "-of", "default=noprint_wrappers=1:nokey=1"])
+        .arg(input)
+        .output()
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => Error::Ffmpeg("ffprobe not found. Install it with: brew install ffmpeg".to_string()),
+            _ => e.into(),
+        })?;
+    if !result.status.success() {
+        return Err(Error::Ffmpeg(String::from_utf8_lossy(&result.stderr).trim().to_string()));
+    }
+
+    let output = String::from_utf8_lossy(&result.stdout);
+    output
+        .trim()
+        .parse::<f64>()
+        .map(|seconds| seconds.ceil() as i64)
+        .map_err(|_| Error::Ffmpeg(format!("ffprobe reported no duration: {}", output.trim())))
+}
+
+/// Milliseconds as ffmpeg's decimal seconds
+fn seconds(ms: i64) -> String {
+    format!("{}.{:03}", ms / 1000, ms % 1000)
+}
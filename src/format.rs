@@ -0,0 +1,116 @@
+use std::sync::OnceLock;
+
+use rusqlite::Connection;
+
+use crate::config::{settings, TimeZone};
+
+/// Digit grouping and decimal mark of the locale in `LC_ALL` / `LC_NUMERIC` / `LANG`
+struct NumberStyle {
+    group: &'static str,
+    decimal: &'static str,
+}
+
+fn number_style() -> &'static NumberStyle {
+    static STYLE: OnceLock<NumberStyle> = OnceLock::new();
+    STYLE.get_or_init(|| {
+        let locale = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        let language = locale.split(['_', '.', '@']).next().unwrap_or_default().to_lowercase();
+        match language.as_str() {
+            "de" | "es" | "it" | "nl" | "pt" | "da" | "id" | "tr" | "el" | "ro" | "hr" | "sl" | "sr" => {
+                NumberStyle { group: ".", decimal: "," }
+            }
+            "fr" | "ru" | "sv" | "pl" | "cs" | "sk" | "fi" | "nb" | "no" | "uk" | "hu" | "bg" | "et" | "lt"
+            | "lv" => NumberStyle { group: "\u{a0}", decimal: "," },
+            _ => NumberStyle { group: ",", decimal: "." },
+        }
+    })
+}
+
+/// `4:05` or, from an hour on, `1:15:03`
+pub fn clock(seconds: i64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// `45s`, `4m 5s` or, from an hour on, `1h 15m 3s`
+pub fn duration(seconds: i64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Whole number with the locale's digit grouping, e.g. `1,234,567`
+pub fn number(n: i64) -> String {
+    let digits = n.unsigned_abs().to_string();
+    let mut grouped = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push_str(number_style().group);
+        }
+        grouped.push(digit);
+    }
+    if n < 0 { format!("-{}", grouped) } else { grouped }
+}
+
+/// Short form of a large count, e.g. `1.2M` or `3.4K`
+pub fn compact_number(n: i64) -> String {
+    let (value, suffix) = match n.unsigned_abs() {
+        v if v >= 1_000_000_000 => (n as f64 / 1e9, "B"),
+        v if v >= 1_000_000 => (n as f64 / 1e6, "M"),
+        v if v >= 1_000 => (n as f64 / 1e3, "K"),
+        _ => return number(n),
+    };
+    format!("{:.1}{}", value, suffix).replace('.', number_style().decimal)
+}
+
+/// File size such as `512 B`, `3.4 KB` or `210.7 MB`
+pub fn size(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{:.1} GB", b as f64 / (1u64 << 30) as f64),
+        b if b >= 1 << 20 => format!("{:.1} MB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+/// yt-dlp's `YYYYMMDD` as `YYYY-MM-DD` (other values unchanged)
+pub fn upload_date(date: &str) -> String {
+    if date.len() == 8 && date.chars().all(|c| c.is_ascii_digit()) {
+        format!("{}-{}-{}", &date[0..4], &date[4..6], &date[6..8])
+    } else {
+        date.to_string()
+    }
+}
+
+/// A database timestamp (UTC, `YYYY-MM-DD HH:MM:SS`) in the configured time zone
+pub fn timestamp(utc: &str) -> String {
+    match settings().timezone {
+        TimeZone::Utc => utc.to_string(),
+        TimeZone::Local => to_local(utc).unwrap_or_else(|| utc.to_string()),
+    }
+}
+
+/// SQLite's `localtime` modifier handles the system time zone, including past DST changes
+fn to_local(utc: &str) -> Option<String> {
+    thread_local! {
+        static CONN: Option<Connection> = Connection::open_in_memory().ok();
+    }
+    CONN.with(|conn| {
+        conn.as_ref()?
+            .query_row("SELECT datetime(?1, 'localtime')", [utc], |row| row.get(0))
+            .ok()?
+    })
+}
@@ -0,0 +1,132 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+
+use serde::Serialize;
+
+use crate::commands::history;
+use crate::config::{data_dir, settings};
+use crate::downloader::VideoMetadata;
+use crate::error::{Error, Result};
+
+/// The URL to download for `url`: `[[rewrite]]` rules applied in order, then the
+/// `[hooks] pre_download` command
+///
+/// The hook gets the URL in `YT_CLI_URL` and on stdin and prints the URL to use (nothing
+/// keeps it as is). A hook that fails rejects the URL.
+pub fn rewrite_url(url: &str) -> Result<String> {
+    let mut rewritten = url.trim().to_string();
+    for rule in &settings().rewrite {
+        if let Some(regex) = rule.regex() {
+            rewritten = regex.replace(&rewritten, rule.replace.as_str()).into_owned();
+        }
+    }
+
+    if let Some(hook) = &settings().hooks.pre_download {
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(hook)
+            .env("YT_CLI_URL", &rewritten)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(mut stdin) = child.stdin.take() {
+                    let _ = writeln!(stdin, "{}", rewritten);
+                }
+                child.wait_with_output()
+            })?;
+        if !output.status.success() {
+            return Err(Error::Download(format!(
+                "pre-download hook rejected {} ({})",
+                rewritten, output.status
+            )));
+        }
+        let printed = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !printed.is_empty() {
+            rewritten = printed;
+        }
+    }
+
+    if rewritten != url.trim() {
+        eprintln!("Rewrote URL: {} -> {}", url.trim(), rewritten);
+    }
+    Ok(rewritten)
+}
+
+/// What a post-transcribe hook receives as JSON on stdin
+#[derive(Serialize)]
+struct HookPayload<'a> {
+    /// Directory holding transcript.md, transcript.json and metadata.json
+    path: &'a Path,
+    url: &'a str,
+    platform: &'a str,
+    metadata: &'a VideoMetadata,
+}
+
+/// Run the `[hooks] post_transcribe` commands for a newly saved transcript
+///
+/// Hooks run through `sh -c` with the transcript described in `YT_CLI_*` environment
+/// variables and as JSON on stdin. A failing hook is reported but doesn't fail the
+/// transcription, which is already saved by then.
+pub fn post_transcribe(storage_path: &Path, url: &str, platform: &str, metadata: &VideoMetadata) {
+    let hooks = &settings().hooks.post_transcribe;
+    if hooks.is_empty() {
+        return;
+    }
+
+    let payload = HookPayload {
+        path: storage_path,
+        url,
+        platform,
+        metadata,
+    };
+    let payload = match serde_json::to_vec(&payload) {
+        Ok(payload) => payload,
+        Err(e) => {
+            eprintln!("Warning: skipping hooks: {}", e);
+            return;
+        }
+    };
+
+    for hook in hooks {
+        eprintln!("Running hook: {}", hook);
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(hook)
+            .current_dir(storage_path)
+            .env("YT_CLI_TRANSCRIPT_DIR", storage_path)
+            .env("YT_CLI_TRANSCRIPT_MD", storage_path.join("transcript.md"))
+            .env("YT_CLI_TRANSCRIPT_JSON", storage_path.join("transcript.json"))
+            .env("YT_CLI_METADATA_JSON", storage_path.join("metadata.json"))
+            .env("YT_CLI_DATA_DIR", data_dir())
+            .env("YT_CLI_VIDEO_ID", &metadata.id)
+            .env("YT_CLI_TITLE", &metadata.title)
+            .env("YT_CLI_CHANNEL", &metadata.channel)
+            .env("YT_CLI_URL", url)
+            .env("YT_CLI_PLATFORM", platform);
+
+        let failure = match run(cmd, &payload) {
+            Ok(status) if status.success() => continue,
+            Ok(status) => format!("exited with {}", status),
+            Err(e) => e.to_string(),
+        };
+        eprintln!("Warning: hook failed ({}): {}", hook, failure);
+        history::record("hook", Some(&metadata.id), Some(&format!("{}: {}", hook, failure)), false);
+    }
+}
+
+fn run(mut cmd: Command, payload: &[u8]) -> Result<ExitStatus> {
+    let mut child = cmd.stdin(Stdio::piped()).spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // Hooks that don't read stdin close it early; that's not a failure
+        if let Err(e) = stdin.write_all(payload)
+            && e.kind() != std::io::ErrorKind::BrokenPipe
+        {
+            return Err(e.into());
+        }
+    }
+
+    Ok(child.wait()?)
+}
@@ -1,7 +1,16 @@
+pub mod aligner;
+pub mod anonymize;
 pub mod commands;
 pub mod config;
 pub mod database;
 pub mod downloader;
+pub mod embeddings;
+pub mod episode;
 pub mod error;
+pub mod ffmpeg;
+pub mod format;
+pub mod hooks;
+pub mod notify;
+pub mod platform;
 pub mod storage;
 pub mod transcriber;
@@ -1,15 +1,370 @@
-use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand};
 
 use yt_cli::commands;
-use yt_cli::config::load_env;
+use yt_cli::commands::channel_transcribe::DurationFilter;
+use yt_cli::commands::subscriptions::SyncOptions;
+use yt_cli::commands::export::ExportFormat;
+use yt_cli::commands::history;
+use yt_cli::commands::list::ListSort;
+use yt_cli::config::{
+    load_env, set_politeness, set_read_only, set_settings, settings, LanguageMismatch, NotifyEvent, Politeness,
+    PiiSubstitution, Settings, SpeechModel, TimeZone,
+};
+use yt_cli::database::TranscriptFilter;
+use yt_cli::error::{print_error, Error, Result};
+use yt_cli::transcriber::{Backend, TranscriptSource};
 
 #[derive(Parser)]
 #[command(name = "yt-cli")]
-#[command(about = "Download and transcribe videos using yt-dlp and AssemblyAI")]
+#[command(about = "Download and transcribe videos using yt-dlp and AssemblyAI or Deepgram")]
 #[command(version)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    #[command(flatten)]
+    politeness: PolitenessArgs,
+
+    /// Refuse all write operations, e.g. for a shared library mounted read-only
+    /// (also YT_TRANSCRIBE_READ_ONLY=1)
+    #[arg(long, global = true)]
+    read_only: bool,
+
+    /// Spoken language to transcribe in, e.g. `es` (default: `language` in config.toml, else English);
+    /// with list and search, only show transcripts in this language
+    #[arg(long, global = true, value_name = "CODE")]
+    language: Option<String>,
+
+    /// Let the transcription service detect the spoken language (`detect_language = true`)
+    #[arg(long, global = true, conflicts_with = "language")]
+    detect_language: bool,
+
+    /// Transcribe again in the video's language when it differs from --language
+    /// (`language_mismatch = "retranscribe"` in config.toml)
+    #[arg(long, global = true)]
+    retranscribe_on_mismatch: bool,
+
+    /// Leave out punctuation (`[transcription] punctuate = false`)
+    #[arg(long, global = true)]
+    no_punctuate: bool,
+
+    /// Skip casing and number formatting (`[transcription] format_text = false`)
+    #[arg(long, global = true)]
+    no_format_text: bool,
+
+    /// Keep filler words like "um" and "uh" for verbatim transcripts
+    /// (`[transcription] disfluencies = true`)
+    #[arg(long, global = true)]
+    disfluencies: bool,
+
+    /// Mask profanity in transcripts, e.g. "s***" (`[transcription] filter_profanity = true`)
+    #[arg(long, global = true)]
+    filter_profanity: bool,
+
+    /// AssemblyAI speech model, e.g. nano for cheaper bulk backfills
+    /// (`[transcription] speech_model`)
+    #[arg(long, global = true, value_enum)]
+    model: Option<SpeechModel>,
+
+    /// Ask before transcribing a recording whose estimated cost is above this many USD;
+    /// without a terminal to ask at, it is skipped (`max_cost` in config.toml)
+    #[arg(long, global = true, value_name = "USD")]
+    max_cost: Option<f64>,
+
+    /// Transcribe recordings above --max-cost without asking
+    #[arg(long, global = true)]
+    yes: bool,
+
+    /// Extra yt-dlp arguments, space-separated, e.g. "-f bestaudio/best" or "--proxy URL"
+    /// (`ytdlp_args` in config.toml)
+    #[arg(long, global = true, value_name = "ARGS", allow_hyphen_values = true)]
+    ytdlp_args: Option<String>,
+
+    /// Fetch channel and search listings fresh instead of reusing recent ones
+    /// (`[cache] listings_ttl` in config.toml)
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// Show stored times (history, queue, syncs) in UTC (`timezone = "utc"`)
+    #[arg(long, global = true, conflicts_with = "local")]
+    utc: bool,
+
+    /// Show stored times in the system time zone, the default (`timezone = "local"`)
+    #[arg(long, global = true)]
+    local: bool,
+}
+
+/// Global pacing options for yt-dlp (override YT_TRANSCRIBE_* environment variables)
+#[derive(Args)]
+struct PolitenessArgs {
+    /// Seconds to sleep between yt-dlp extraction requests
+    #[arg(long, global = true)]
+    sleep_requests: Option<f64>,
+
+    /// Seconds to sleep before each download (randomized up to --max-sleep-interval)
+    #[arg(long, global = true)]
+    sleep_interval: Option<f64>,
+
+    /// Upper bound for the randomized download sleep
+    #[arg(long, global = true)]
+    max_sleep_interval: Option<f64>,
+
+    /// Minimum seconds between starting consecutive yt-dlp processes
+    #[arg(long, global = true)]
+    fetch_delay: Option<f64>,
+
+    /// Maximum number of concurrent yt-dlp processes
+    #[arg(long, global = true)]
+    max_concurrent_fetches: Option<usize>,
+
+    /// Kill a yt-dlp process running longer than this many minutes (0: no limit, the default)
+    #[arg(long, global = true, value_name = "MINUTES")]
+    ytdlp_timeout: Option<f64>,
+
+    /// Kill a yt-dlp process that prints nothing for this many minutes (default 15, 0: no limit)
+    #[arg(long, global = true, value_name = "MINUTES")]
+    ytdlp_idle_timeout: Option<f64>,
+}
+
+impl PolitenessArgs {
+    fn apply(self, politeness: &mut Politeness) {
+        politeness.sleep_requests = self.sleep_requests.or(politeness.sleep_requests);
+        politeness.sleep_interval = self.sleep_interval.or(politeness.sleep_interval);
+        politeness.max_sleep_interval = self.max_sleep_interval.or(politeness.max_sleep_interval);
+        politeness.fetch_delay = self.fetch_delay.or(politeness.fetch_delay);
+        if let Some(max) = self.max_concurrent_fetches.filter(|n| *n > 0) {
+            politeness.max_concurrent_fetches = max;
+        }
+        if let Some(minutes) = self.ytdlp_timeout {
+            politeness.ytdlp_timeout = Some(minutes).filter(|m| *m > 0.0);
+        }
+        if let Some(minutes) = self.ytdlp_idle_timeout {
+            politeness.ytdlp_idle_timeout = Some(minutes).filter(|m| *m > 0.0);
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum BackupAction {
+    /// Check a backup of the transcripts directory against the live library
+    Verify {
+        /// Backup directory or s3://bucket/prefix (default: the [storage] S3 bucket)
+        location: Option<String>,
+
+        /// Custom endpoint for S3-compatible servers
+        #[arg(long)]
+        endpoint: Option<String>,
+
+        /// Named `aws` CLI profile holding the credentials
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Compare file sizes only instead of SHA-256 checksums (no downloads)
+        #[arg(long)]
+        quick: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ArchiveAction {
+    /// Pack the database and transcripts directory into one file (.tar.zst, .tar.gz, ...)
+    Export {
+        /// Archive file to write
+        file: String,
+    },
+
+    /// Unpack an archive into the data directory, pointing stored paths at this machine's
+    Import {
+        /// Archive file written by `archive export`
+        file: String,
+
+        /// Replace the database of an existing library and merge the archived files into it;
+        /// transcripts only the library had are indexed again
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum SyncRemoteAction {
+    /// Upload this library to the remote
+    Push {
+        /// Replace what's there even if another machine pushed since this one last synced
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Replace this library's database with the remote's and download its transcript files
+    Pull {
+        /// Pull even if this library has changes that weren't pushed
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Show whether this library or the remote changed since the last sync
+    Status,
+}
+
+#[derive(Subcommand)]
+enum SpeakersAction {
+    /// Name a speaker, e.g. `speakers set VIDEO_ID A "Jerome Powell"` (the label itself as
+    /// the name removes it)
+    Set {
+        /// Video ID or path to transcript directory
+        path: String,
+
+        /// Speaker label from the transcript (A, B, ...)
+        label: String,
+
+        /// Name to show instead
+        name: String,
+    },
+
+    /// List a transcript's speakers and their names
+    List {
+        /// Video ID or path to transcript directory
+        path: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum JobsAction {
+    /// Ask AssemblyAI how outstanding jobs are doing (queued, processing, errors)
+    /// without waiting for them
+    Status {
+        /// Job (AssemblyAI transcript) ID (default: every job `resume` would finish)
+        job_id: Option<String>,
+    },
+
+    /// Delete a job from AssemblyAI and stop `resume` from saving it
+    /// (jobs already running can't be stopped and are still billed)
+    Cancel {
+        /// Job (AssemblyAI transcript) ID, as shown by `jobs status`
+        job_id: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Bundle config.toml and channel subscriptions into one file
+    Export {
+        /// Bundle file to write
+        file: String,
+
+        /// Also include API keys and storage passwords
+        #[arg(long)]
+        with_secrets: bool,
+    },
+
+    /// Apply a bundle written by `config export`
+    Import {
+        /// Bundle file to read
+        file: String,
+
+        /// Overwrite an existing config.toml and API keys
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum QueueAction {
+    /// Add URLs to the queue
+    Add {
+        /// Video URLs to queue
+        #[arg(required_unless_present = "from_file", conflicts_with = "from_file")]
+        urls: Vec<String>,
+
+        /// Queue every URL in this file, one per line (`-` reads stdin)
+        #[arg(long, value_name = "PATH")]
+        from_file: Option<String>,
+
+        /// Transcription service to use (default: `backend` in config.toml, else assemblyai)
+        #[arg(long, value_enum, conflicts_with = "use_captions")]
+        backend: Option<Backend>,
+
+        /// Store existing captions instead of paying for transcriptions
+        #[arg(long)]
+        use_captions: bool,
+
+        /// Caption language for --use-captions
+        #[arg(long, default_value = "en", requires = "use_captions")]
+        captions_lang: String,
+    },
+
+    /// Run queued jobs, retrying earlier failures
+    Run {
+        /// Stop after this many jobs
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+
+        /// Give up on a job after this many failed attempts
+        #[arg(long, default_value = "3")]
+        max_attempts: i32,
+
+        /// Jobs downloading and uploading at once (ones already with the service don't count)
+        #[arg(short, long, default_value = "1", value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+    },
+
+    /// Show pending, running and failed jobs
+    Status {
+        /// Include finished jobs
+        #[arg(short, long)]
+        all: bool,
+    },
+
+    /// Requeue failed jobs with a fresh set of attempts
+    Retry,
+}
+
+/// Options shared by `sync` and `daemon`
+#[derive(Args)]
+struct SyncArgs {
+    /// Number of latest videos to check per channel (default: 20)
+    #[arg(short = 'n', long, default_value = "20")]
+    limit: usize,
+
+    /// Skip videos shorter than this many minutes
+    #[arg(long)]
+    min_duration: Option<i64>,
+
+    /// Skip videos longer than this many minutes (e.g. multi-hour livestreams)
+    #[arg(long)]
+    max_duration: Option<i64>,
+
+    /// Transcription service to use (default: `backend` in config.toml, else assemblyai)
+    #[arg(long, value_enum, conflicts_with = "use_captions")]
+    backend: Option<Backend>,
+
+    /// Store existing captions instead of paying for transcriptions
+    #[arg(long)]
+    use_captions: bool,
+
+    /// Caption language for --use-captions
+    #[arg(long, default_value = "en", requires = "use_captions")]
+    captions_lang: String,
+
+    /// Videos downloading and uploading at once (ones already with the service don't count)
+    #[arg(short, long, default_value = "1", value_parser = clap::value_parser!(u16).range(1..))]
+    jobs: u16,
+}
+
+impl SyncArgs {
+    fn into_options(self, default_backend: Backend) -> SyncOptions {
+        SyncOptions {
+            limit: self.limit,
+            durations: DurationFilter {
+                min: self.min_duration.map(|mins| mins * 60),
+                max: self.max_duration.map(|mins| mins * 60),
+            },
+            backend: self.backend.unwrap_or(default_backend),
+            captions_lang: self.use_captions.then_some(self.captions_lang),
+            jobs: self.jobs.into(),
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -17,7 +372,124 @@ enum Commands {
     /// Download and transcribe a video
     Transcribe {
         /// Video URL to transcribe
+        #[arg(required_unless_present = "from_file", conflicts_with = "from_file")]
+        url: Option<String>,
+
+        /// Transcribe every URL in this file, one per line (`-` reads stdin)
+        #[arg(long, value_name = "PATH")]
+        from_file: Option<String>,
+
+        /// Transcription service to use (default: `backend` in config.toml, else assemblyai)
+        #[arg(long, value_enum, conflicts_with = "use_captions")]
+        backend: Option<Backend>,
+
+        /// Store the video's existing captions (manual, else auto-generated) instead of
+        /// paying for a transcription
+        #[arg(long)]
+        use_captions: bool,
+
+        /// Caption language for --use-captions
+        #[arg(long, default_value = "en", requires = "use_captions")]
+        captions_lang: String,
+
+        /// Number of speakers to expect, improving diarization (AssemblyAI; overrides the
+        /// channel's `speakers` in config.toml)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        speakers: Option<u32>,
+
+        /// Names and jargon to favour, comma-separated (added to the channel's
+        /// `boost_words` in config.toml)
+        #[arg(long, value_name = "WORDS", value_delimiter = ',')]
+        boost_words: Vec<String>,
+
+        /// Transcribe each audio channel separately as its own speaker instead of diarizing,
+        /// for recordings with one person per channel (AssemblyAI; `[transcription]
+        /// multichannel = true` turns it on for every run)
+        #[arg(long, conflicts_with_all = ["use_captions", "speakers"])]
+        multichannel: bool,
+
+        /// Delete the downloaded audio once transcribed instead of storing it as audio.mp3
+        /// (`keep_audio = false` in config.toml)
+        #[arg(long)]
+        no_keep_audio: bool,
+
+        /// Detect chapters and add a table of contents to the transcript (AssemblyAI;
+        /// `[transcription] chapters = true` turns it on for every run)
+        #[arg(long, conflicts_with = "use_captions")]
+        chapters: bool,
+
+        /// Detect key phrases and when they were said (AssemblyAI; `[transcription]
+        /// highlights = true` turns it on for every run)
+        #[arg(long, conflicts_with = "use_captions")]
+        highlights: bool,
+
+        /// Detect people, organizations and places mentioned, for `yt-cli entities` and
+        /// `search --entity` (AssemblyAI; `[transcription] entities = true` turns it on for
+        /// every run)
+        #[arg(long, conflicts_with = "use_captions")]
+        entities: bool,
+
+        /// Redact these kinds of personal information before the transcript is saved, e.g.
+        /// `person_name,phone_number` (AssemblyAI; `[transcription] redact_pii`)
+        #[arg(long, value_name = "TYPES", value_delimiter = ',', conflicts_with = "use_captions")]
+        redact_pii: Vec<String>,
+
+        /// What redacted text becomes (`[transcription] redact_pii_sub`)
+        #[arg(long, value_enum, value_name = "SUB")]
+        redact_pii_sub: Option<PiiSubstitution>,
+
+        /// Videos from --from-file downloading and uploading at once (ones already with the
+        /// service don't count)
+        #[arg(
+            short,
+            long,
+            default_value = "1",
+            conflicts_with = "url",
+            value_parser = clap::value_parser!(u16).range(1..)
+        )]
+        jobs: u16,
+    },
+
+    /// Import transcripts made by whisperX, yt-whisper or Descript
+    Import {
+        /// Transcript files (.json, .srt, .vtt, .txt) or folders of them
+        #[arg(required = true)]
+        paths: Vec<String>,
+
+        /// Channel to file the transcripts under (default: "Imported")
+        #[arg(short, long)]
+        channel: Option<String>,
+
+        /// Replace transcripts already in the library
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Transcribe a direct audio URL (e.g. an .mp3 link) without yt-dlp
+    TranscribeUrl {
+        /// Publicly reachable audio file URL
         url: String,
+
+        /// Title to store (default: file name)
+        #[arg(short, long)]
+        title: Option<String>,
+
+        /// Channel/show name to file it under (default: the URL's host)
+        #[arg(short, long)]
+        channel: Option<String>,
+
+        /// Transcription service to use (default: `backend` in config.toml, else assemblyai)
+        #[arg(long, value_enum)]
+        backend: Option<Backend>,
+
+        /// Number of speakers to expect, improving diarization (AssemblyAI)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        speakers: Option<u32>,
+
+        /// Names and jargon to favour, comma-separated (added to the channel's
+        /// `boost_words` in config.toml)
+        #[arg(long, value_name = "WORDS", value_delimiter = ',')]
+        boost_words: Vec<String>,
     },
 
     /// List available transcripts
@@ -33,6 +505,38 @@ enum Commands {
         /// Filter by channel handle (e.g., "@EconomicsUnmasked")
         #[arg(short = 'H', long)]
         handle: Option<String>,
+
+        /// Only include transcripts with at least this many words
+        #[arg(long)]
+        min_words: Option<i32>,
+
+        /// Only include transcripts with at least this confidence (0.0-1.0)
+        #[arg(long)]
+        min_confidence: Option<f64>,
+
+        /// Only transcripts from this source (default: one per video, preferring the API)
+        #[arg(long, value_enum)]
+        source: Option<TranscriptSource>,
+
+        /// Only episodes of this show (case-insensitive), as detected from titles
+        #[arg(long)]
+        series: Option<String>,
+
+        /// Order transcripts by episode number or upload date
+        #[arg(long, value_enum)]
+        sort: Option<ListSort>,
+
+        /// Only transcripts that stop well before the end of their audio (redo them with
+        /// `retranscribe`)
+        #[arg(long)]
+        incomplete: bool,
+    },
+
+    /// Show the most recently transcribed videos with when they were done and where they are
+    Recent {
+        /// Number of transcripts to show
+        #[arg(default_value = "10")]
+        limit: i32,
     },
 
     /// Read a transcript
@@ -43,6 +547,138 @@ enum Commands {
         /// Output as JSON with timestamps
         #[arg(short, long)]
         json: bool,
+
+        /// Leave the word-level timings out of the JSON (much smaller for long recordings)
+        #[arg(long, requires = "json")]
+        no_words: bool,
+    },
+
+    /// Find the moments in one transcript that match a pattern
+    Grep {
+        /// Video ID or path to transcript directory
+        path: String,
+
+        /// Regular expression (or plain text with --fixed-strings)
+        pattern: String,
+
+        /// Treat the pattern as plain text
+        #[arg(short = 'F', long)]
+        fixed_strings: bool,
+
+        /// Match regardless of case
+        #[arg(short, long)]
+        ignore_case: bool,
+    },
+
+    /// Show a transcript's metadata, files and attachments
+    Describe {
+        /// Video ID or path to transcript directory
+        path: String,
+    },
+
+    /// Store a file (slides, show notes, ...) with a transcript
+    Attach {
+        /// Video ID or path to transcript directory
+        path: String,
+
+        /// File to attach
+        file: String,
+
+        /// Name to store it under (default: the file's name)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Extract its text (PDF, HTML or plain text) and index it for search
+        #[arg(short, long)]
+        extract: bool,
+
+        /// Replace an attachment with the same name
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Tag a transcript (e.g. `public` for publish), or remove tags; without tags, list its tags
+    Tag {
+        /// Video ID or path to transcript directory
+        path: String,
+
+        /// Tags to add or remove
+        tags: Vec<String>,
+
+        /// Remove the tags instead of adding them
+        #[arg(long, requires = "tags")]
+        remove: bool,
+    },
+
+    /// Put names to a transcript's speakers
+    Speakers {
+        #[command(subcommand)]
+        action: SpeakersAction,
+    },
+
+    /// Show key phrases of a transcript, or the most common ones across the library
+    Highlights {
+        /// Video ID or path to transcript directory (default: the whole library)
+        path: Option<String>,
+
+        /// Only count transcripts from channels whose name contains this
+        #[arg(short, long, conflicts_with = "path")]
+        channel: Option<String>,
+
+        /// Maximum number of phrases across the library
+        #[arg(short, long, default_value = "30", conflicts_with = "path")]
+        limit: i32,
+    },
+
+    /// Show people, organizations and places named in a transcript, or across the library
+    Entities {
+        /// Video ID or path to transcript directory (default: the whole library)
+        path: Option<String>,
+
+        /// Only entities of this type, e.g. person_name, organization, location
+        #[arg(short = 't', long = "type")]
+        entity_type: Option<String>,
+
+        /// Only count transcripts from channels whose name contains this
+        #[arg(short, long, conflicts_with = "path")]
+        channel: Option<String>,
+
+        /// Maximum number of entities across the library
+        #[arg(short, long, default_value = "30", conflicts_with = "path")]
+        limit: i32,
+    },
+
+    /// Cut a stretch of a transcript's stored audio and print what was said in it
+    Clip {
+        /// Video ID or path to transcript directory
+        path: String,
+
+        /// Start of the clip, e.g. 12:30 or 1:02:03
+        #[arg(long)]
+        from: String,
+
+        /// End of the clip
+        #[arg(long)]
+        to: String,
+
+        /// Output file (default: VIDEO_ID_START-END.mp3; other extensions are converted)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Add word timings to a captions or imported transcript by aligning it against audio
+    /// (needs aeneas: pip install aeneas)
+    Align {
+        /// Video ID or path to transcript directory
+        path: String,
+
+        /// Audio to align against (default: the stored audio.mp3)
+        #[arg(long)]
+        audio: Option<String>,
+
+        /// Align again even if the transcript already has word timings
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Search transcripts using full-text search
@@ -53,11 +689,127 @@ enum Commands {
         /// Maximum results (default: 20)
         #[arg(short = 'n', long, default_value = "20")]
         limit: i32,
+
+        /// Only include transcripts with at least this many words
+        #[arg(long)]
+        min_words: Option<i32>,
+
+        /// Only include transcripts with at least this confidence (0.0-1.0)
+        #[arg(long)]
+        min_confidence: Option<f64>,
+
+        /// Only transcripts from this source (default: one per video, preferring the API)
+        #[arg(long, value_enum)]
+        source: Option<TranscriptSource>,
+
+        /// Only transcripts mentioning this person, organization or place (see `yt-cli entities`)
+        #[arg(long)]
+        entity: Option<String>,
+
+        /// Rank by keyword and semantic similarity combined (needs `yt-cli embed`)
+        #[arg(long)]
+        hybrid: bool,
+
+        /// Share of the hybrid score from semantic similarity, 0.0-1.0
+        /// (default: `hybrid_weight` in [search], else 0.5)
+        #[arg(long, requires = "hybrid")]
+        weight: Option<f64>,
+    },
+
+    /// Embed indexed transcripts for `search --hybrid`
+    Embed,
+
+    /// Answer a question from the library, citing transcript excerpts with timestamped links
+    Ask {
+        /// Question to answer, e.g. "what did they say about rate cuts?"
+        question: String,
+
+        /// Number of transcript excerpts given to the model
+        #[arg(short = 'n', long, default_value = "8")]
+        chunks: usize,
+
+        /// Only list the matching excerpts, without asking the model
+        #[arg(long)]
+        sources_only: bool,
     },
 
     /// Show database statistics
     Stats,
 
+    /// Show recent activity (transcriptions, reindexes, batch and channel runs)
+    History {
+        /// Number of entries to show (default: 20)
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: i32,
+
+        /// Only show this action (e.g. transcribe, reindex, channel-transcribe)
+        #[arg(short, long)]
+        action: Option<String>,
+
+        /// Only show failures
+        #[arg(long)]
+        failed: bool,
+    },
+
+    /// Remove audio and partial downloads left behind by failed or interrupted runs
+    Clean {
+        /// Only files last modified more than this many hours ago
+        /// (default: `clean_after_hours` in config.toml, else 24)
+        #[arg(long, value_name = "HOURS")]
+        older_than: Option<u64>,
+
+        /// List what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also remove empty channel and platform directories under the transcripts directory
+        #[arg(long)]
+        dirs: bool,
+    },
+
+    /// Send a test notification to the notifiers in config.toml (`[[notify]]` tables)
+    NotifyTest {
+        /// Only the notifiers routed this event
+        #[arg(long, value_enum)]
+        event: Option<NotifyEvent>,
+    },
+
+    /// Delete the stored audio of older transcripts, keeping the transcripts
+    PruneAudio {
+        /// Only audio stored longer ago than this, e.g. 90d, 12w or 36h
+        /// (default: each platform's `prune_audio_after` in config.toml)
+        #[arg(long, value_name = "AGE")]
+        older_than: Option<String>,
+
+        /// List what would be removed without removing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Delete a video's transcripts from the index and move its directory to the trash
+    Delete {
+        /// Video ID
+        video_id: String,
+
+        /// Only remove it from the database and search index, leaving its files
+        #[arg(long)]
+        keep_files: bool,
+
+        /// Show what would be deleted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Show AssemblyAI account activity and locally tracked transcription usage
+    Account,
+
+    /// Report audio transcribed and estimated spend per month and backend
+    Usage {
+        /// Only this month (YYYY-MM, UTC), listing each transcription
+        #[arg(long)]
+        month: Option<String>,
+    },
+
     /// Initialize with AssemblyAI API key
     Init {
         /// AssemblyAI API key
@@ -67,25 +819,314 @@ enum Commands {
         /// Overwrite existing config
         #[arg(short, long)]
         force: bool,
+
+        /// Store the key in the SQLCipher-encrypted database instead of .env
+        #[arg(long)]
+        encrypt: bool,
+
+        /// Save the key without checking it against the AssemblyAI API
+        #[arg(long)]
+        skip_verify: bool,
     },
 
     /// Reindex all transcripts in the database
-    Reindex,
+    Reindex {
+        /// Only finish transcripts saved without their search index (e.g. after a full disk)
+        #[arg(long)]
+        pending: bool,
+    },
 
     /// Get transcript path for a video URL
     Get {
         /// Video URL
         url: String,
+
+        /// Transcription service to use if not yet transcribed (default: from config.toml)
+        #[arg(long, value_enum)]
+        backend: Option<Backend>,
     },
 
     /// List latest videos from a YouTube channel
     Channel {
-        /// Channel URL (e.g., https://youtube.com/@CHANNEL or channel ID)
+        /// Channel URL, @handle, channel ID or name to search for
         channel: String,
 
         /// Maximum number of videos to show (default: 20)
         #[arg(short = 'n', long, default_value = "20")]
         limit: usize,
+
+        /// Take the top match when a channel name matches several channels, instead of asking
+        #[arg(long)]
+        first: bool,
+    },
+
+    /// Show how much of a channel's uploads are transcribed and what the rest would cost
+    Coverage {
+        /// Channel URL, @handle, channel ID or name to search for
+        channel: String,
+
+        /// Only consider the latest N uploads (default: all of them)
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+
+        /// Service to price the rest with (default: `backend` in config.toml, else assemblyai)
+        #[arg(long, value_enum)]
+        backend: Option<Backend>,
+
+        /// Price per audio hour in USD, instead of the service's list price
+        #[arg(long, value_name = "USD")]
+        price_per_hour: Option<f64>,
+
+        /// Take the top match when a channel name matches several channels, instead of asking
+        #[arg(long)]
+        first: bool,
+    },
+
+    /// Transcribe a channel's latest videos that aren't transcribed yet
+    ChannelTranscribe {
+        /// Channel URL, @handle, channel ID or name to search for
+        channel: String,
+
+        /// Number of latest videos to consider (default: 20)
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+
+        /// Skip videos shorter than this many minutes
+        #[arg(long)]
+        min_duration: Option<i64>,
+
+        /// Skip videos longer than this many minutes (e.g. multi-hour livestreams)
+        #[arg(long)]
+        max_duration: Option<i64>,
+
+        /// Transcription service to use (default: `backend` in config.toml, else assemblyai)
+        #[arg(long, value_enum, conflicts_with = "use_captions")]
+        backend: Option<Backend>,
+
+        /// Store existing captions instead of paying for transcriptions
+        #[arg(long)]
+        use_captions: bool,
+
+        /// Caption language for --use-captions
+        #[arg(long, default_value = "en", requires = "use_captions")]
+        captions_lang: String,
+
+        /// Videos downloading and uploading at once (ones already with the service don't count)
+        #[arg(short, long, default_value = "1", value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: u16,
+
+        /// Take the top match when a channel name matches several channels, instead of asking
+        #[arg(long)]
+        first: bool,
+    },
+
+    /// Subscribe to a channel so `sync` transcribes its new uploads (lists subscriptions without
+    /// a channel)
+    Subscribe {
+        /// Channel URL (e.g., https://youtube.com/@CHANNEL), @handle, channel ID or name to search for
+        channel: Option<String>,
+
+        /// Display name (default: the channel's name)
+        #[arg(long, requires = "channel")]
+        name: Option<String>,
+
+        /// Also transcribe the channel's latest videos on the next sync, not just new uploads
+        #[arg(long, requires = "channel")]
+        backfill: bool,
+
+        /// Take the top match when a channel name matches several channels, instead of asking
+        #[arg(long, requires = "channel")]
+        first: bool,
+    },
+
+    /// Remove a channel subscription
+    Unsubscribe {
+        /// Channel URL or subscription name
+        channel: String,
+    },
+
+    /// Transcribe new uploads from all subscribed channels
+    Sync {
+        #[command(flatten)]
+        options: SyncArgs,
+    },
+
+    /// Keep syncing subscribed channels on an interval (for systemd/launchd)
+    Daemon {
+        /// Minutes between syncs
+        #[arg(long, default_value = "60")]
+        interval: u64,
+
+        /// PID file location (default: daemon.pid in the data directory)
+        #[arg(long)]
+        pid_file: Option<PathBuf>,
+
+        #[command(flatten)]
+        options: SyncArgs,
+    },
+
+    /// Finish transcriptions interrupted mid-poll without paying for them again
+    Resume,
+
+    /// Transcribe a stored video again with the current settings, replacing its transcript
+    Retranscribe {
+        /// Video ID of the transcript to redo
+        video_id: String,
+
+        /// Transcription service to use (default: `backend` in config.toml, else assemblyai)
+        #[arg(long, value_enum)]
+        backend: Option<Backend>,
+    },
+
+    /// Check on submitted transcription jobs
+    Jobs {
+        #[command(subcommand)]
+        action: JobsAction,
+    },
+
+    /// Queue transcriptions and work through them later (e.g. overnight)
+    Queue {
+        #[command(subcommand)]
+        action: QueueAction,
+    },
+
+    /// Back up the database (and transcript files), or check a backup of the library
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Backup {
+        /// File to write: a copy of the database, or a .tar.gz with --with-transcripts
+        #[arg(required = true)]
+        path: Option<String>,
+
+        /// Also archive the transcripts directory, with the database, into a tarball
+        #[arg(long)]
+        with_transcripts: bool,
+
+        /// Leave stored audio out of the tarball
+        #[arg(long, requires = "with_transcripts")]
+        no_audio: bool,
+
+        #[command(subcommand)]
+        action: Option<BackupAction>,
+    },
+
+    /// Move the whole library (database and transcript files) between machines
+    Archive {
+        #[command(subcommand)]
+        action: ArchiveAction,
+    },
+
+    /// Keep one library on several machines through an S3 bucket or rclone remote (`[sync]`
+    /// in config.toml)
+    SyncRemote {
+        #[command(subcommand)]
+        action: SyncRemoteAction,
+    },
+
+    /// Run the yt-cli commands listed in a file, one per line, in a single process (much
+    /// faster than a shell loop calling yt-cli); global options apply to every line
+    Batch {
+        /// Script with one command per line, e.g. `tag VIDEO_ID public` (`-` reads stdin)
+        script: String,
+
+        /// Run the remaining commands after one fails (the batch still fails at the end)
+        #[arg(long)]
+        keep_going: bool,
+    },
+
+    /// Move settings between machines
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Reverse the most recent destructive operation (unsubscribe, delete, speakers set, tag --remove)
+    Undo,
+
+    /// Concatenate a channel's transcripts into one corpus file
+    Corpus {
+        /// Channel display name to export (e.g., "Infranomics")
+        #[arg(short, long)]
+        channel: String,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        out: Option<String>,
+
+        /// Strip channel/speaker identities and redact detectable PII
+        #[arg(long)]
+        anonymize: bool,
+    },
+
+    /// Export transcripts as datasets, documents or subtitles
+    Export {
+        /// Video IDs to export (default: all transcripts matching the filters)
+        video_ids: Vec<String>,
+
+        /// Output format (documents and subtitles take a single video ID)
+        #[arg(short, long, value_enum, default_value = "jsonl")]
+        format: ExportFormat,
+
+        /// Filter by platform (youtube, vimeo, etc.)
+        #[arg(short, long)]
+        platform: Option<String>,
+
+        /// Filter by channel display name
+        #[arg(short, long)]
+        channel: Option<String>,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Strip channel/speaker identities and redact detectable PII
+        #[arg(long)]
+        anonymize: bool,
+
+        /// Shift subtitle (srt/vtt/ttml) timestamps by this much, e.g. 12:30 when the audio was
+        /// transcribed from 12:30 into the video; -0:05 shifts them earlier
+        #[arg(long, value_name = "TIME", allow_hyphen_values = true)]
+        offset: Option<String>,
+    },
+
+    /// Export tagged transcripts (markdown and metadata) into a directory a blog or static
+    /// site builds from; rerun to update it, untagged transcripts are removed
+    Publish {
+        /// Tag of the transcripts to publish
+        #[arg(long)]
+        tag: String,
+
+        /// Directory to write into (created if missing)
+        #[arg(long)]
+        out: String,
+    },
+
+    /// Write one note per transcript into an Obsidian vault, with YAML frontmatter
+    ExportVault {
+        /// Vault folder to write notes into (created if missing)
+        dir: String,
+
+        /// Filter by platform (youtube, vimeo, etc.)
+        #[arg(short, long)]
+        platform: Option<String>,
+
+        /// Filter by channel display name
+        #[arg(short, long)]
+        channel: Option<String>,
+
+        /// Only write notes for transcripts that are new or changed since the last export
+        #[arg(long)]
+        sync: bool,
+    },
+
+    /// Replace this binary with the latest GitHub release (checksum-verified)
+    SelfUpdate {
+        /// Only report whether a newer release exists
+        #[arg(long)]
+        check: bool,
+
+        /// Reinstall even if already on the latest version
+        #[arg(long)]
+        force: bool,
     },
 
     /// Search YouTube for videos
@@ -106,23 +1147,423 @@ async fn main() {
 
     let cli = Cli::parse();
 
-    let result = match cli.command {
-        Commands::Transcribe { url } => commands::transcribe::run(&url).await,
-        Commands::List { platform, channel, handle } => {
-            commands::list::run(platform.as_deref(), channel.as_deref(), handle.as_deref())
+    if cli.read_only {
+        set_read_only(true);
+    }
+
+    // First run: offer the wizard instead of failing later with a missing API key
+    if !matches!(cli.command, Commands::Init { .. } | Commands::SelfUpdate { .. })
+        && commands::setup::needed()
+    {
+        match commands::setup::offer().await {
+            Ok(true) => load_env(),
+            Ok(false) => {}
+            Err(e) => exit_with_error(e),
+        }
+    }
+
+    match Settings::load() {
+        Ok(mut settings) => {
+            if cli.detect_language {
+                settings.detect_language = true;
+            } else if cli.language.is_some() {
+                settings.detect_language = false;
+            }
+            settings.language = cli.language.clone().or(settings.language);
+            if let Commands::Transcribe { speakers, boost_words, .. }
+            | Commands::TranscribeUrl { speakers, boost_words, .. } = &cli.command
+            {
+                settings.speakers = *speakers;
+                settings.boost_words = boost_words.clone();
+            }
+            if let Commands::Transcribe {
+                multichannel,
+                no_keep_audio,
+                chapters,
+                highlights,
+                entities,
+                redact_pii,
+                redact_pii_sub,
+                ..
+            } = &cli.command
+            {
+                settings.transcription.multichannel |= multichannel;
+                settings.discard_audio = *no_keep_audio;
+                settings.transcription.chapters |= chapters;
+                settings.transcription.highlights |= highlights;
+                settings.transcription.entities |= entities;
+                if !redact_pii.is_empty() {
+                    settings.transcription.redact_pii = redact_pii.clone();
+                }
+                if let Some(sub) = redact_pii_sub {
+                    settings.transcription.redact_pii_sub = *sub;
+                }
+            }
+            if cli.retranscribe_on_mismatch {
+                settings.language_mismatch = LanguageMismatch::Retranscribe;
+            }
+            settings.transcription.punctuate &= !cli.no_punctuate;
+            settings.transcription.format_text &= !cli.no_format_text;
+            settings.transcription.disfluencies |= cli.disfluencies;
+            settings.transcription.filter_profanity |= cli.filter_profanity;
+            settings.transcription.speech_model = cli.model.or(settings.transcription.speech_model);
+            settings.max_cost = cli.max_cost.or(settings.max_cost);
+            settings.assume_yes = cli.yes;
+            if let Some(args) = &cli.ytdlp_args {
+                settings.ytdlp_args.extend(args.split_whitespace().map(String::from));
+            }
+            settings.cache.bypass = cli.no_cache;
+            if cli.utc {
+                settings.timezone = TimeZone::Utc;
+            } else if cli.local {
+                settings.timezone = TimeZone::Local;
+            }
+            set_settings(settings)
+        }
+        Err(e) => exit_with_error(e),
+    }
+
+    let mut politeness = Politeness::from_env();
+    cli.politeness.apply(&mut politeness);
+    if let Err(e) = politeness.validate() {
+        exit_with_error(e);
+    }
+    set_politeness(politeness);
+
+    if let Err(e) = run(cli.command, cli.language).await {
+        exit_with_error(e);
+    }
+}
+
+/// Run one command; `language` is the `--language` given, which also filters listings
+async fn run(command: Commands, language: Option<String>) -> Result<()> {
+    let default_backend = settings().backend;
+
+    match command {
+        Commands::Transcribe { url, from_file, backend, use_captions, captions_lang, jobs, .. } => {
+            let backend = backend.unwrap_or(default_backend);
+            let captions_lang = use_captions.then_some(captions_lang.as_str());
+            match (url, from_file) {
+                (Some(url), _) => commands::transcribe::run(&url, backend, captions_lang).await,
+                (None, Some(path)) => {
+                    commands::transcribe::run_batch(&path, backend, captions_lang, jobs.into()).await
+                }
+                (None, None) => unreachable!("clap requires a URL or --from-file"),
+            }
+        }
+        Commands::Import { paths, channel, force } => {
+            commands::import::run(&paths, channel.as_deref(), force)
+        }
+        Commands::TranscribeUrl { url, title, channel, backend, .. } => {
+            commands::transcribe_url::run(
+                &url,
+                title.as_deref(),
+                channel.as_deref(),
+                backend.unwrap_or(default_backend),
+            )
+            .await
+        }
+        Commands::List { platform, channel, handle, min_words, min_confidence, source, series, sort, incomplete } => {
+            let filter = TranscriptFilter {
+                min_words,
+                min_confidence,
+                source,
+                language,
+                ..Default::default()
+            };
+            commands::list::run(
+                platform.as_deref(),
+                channel.as_deref(),
+                handle.as_deref(),
+                series.as_deref(),
+                sort,
+                incomplete,
+                &filter,
+            )
+        }
+        Commands::Recent { limit } => commands::recent::run(limit),
+        Commands::Read { path, json, no_words } => commands::read::run(&path, json, !no_words),
+        Commands::Grep { path, pattern, fixed_strings, ignore_case } => {
+            commands::grep::run(&path, &pattern, fixed_strings, ignore_case)
+        }
+        Commands::Describe { path } => commands::describe::run(&path),
+        Commands::Attach { path, file, name, extract, force } => {
+            commands::attach::run(&path, &file, name.as_deref(), extract, force)
+        }
+        Commands::Tag { path, tags, remove } => commands::tag::run(&path, &tags, remove),
+        Commands::Speakers { action: SpeakersAction::Set { path, label, name } } => {
+            commands::speakers::set(&path, &label, &name)
+        }
+        Commands::Speakers { action: SpeakersAction::List { path } } => commands::speakers::list(&path),
+        Commands::Highlights { path, channel, limit } => {
+            commands::highlights::run(path.as_deref(), channel.as_deref(), limit)
+        }
+        Commands::Entities { path, entity_type, channel, limit } => {
+            commands::entities::run(path.as_deref(), channel.as_deref(), entity_type.as_deref(), limit)
+        }
+        Commands::Clip { path, from, to, output } => {
+            commands::clip::run(&path, &from, &to, output.as_deref())
+        }
+        Commands::Align { path, audio, force } => commands::align::run(&path, audio.as_deref(), force),
+        Commands::Search { query, limit, min_words, min_confidence, source, entity, hybrid, weight } => {
+            let filter = TranscriptFilter {
+                min_words,
+                min_confidence,
+                source,
+                entity,
+                language,
+                ..Default::default()
+            };
+            if hybrid {
+                commands::search::run_hybrid(&query, limit, &filter, weight).await
+            } else {
+                commands::search::run(&query, limit, &filter)
+            }
+        }
+        Commands::Embed => commands::embed::run().await,
+        Commands::Ask { question, chunks, sources_only } => {
+            commands::ask::run(&question, chunks, sources_only).await
         }
-        Commands::Read { path, json } => commands::read::run(&path, json),
-        Commands::Search { query, limit } => commands::search::run(&query, limit),
         Commands::Stats => commands::stats::run(),
-        Commands::Init { api_key, force } => commands::init::run(api_key, force),
-        Commands::Reindex => commands::reindex::run(),
-        Commands::Get { url } => commands::get::run(&url).await,
-        Commands::Channel { channel, limit } => commands::channel::run(&channel, limit),
+        Commands::History { limit, action, failed } => {
+            commands::history::run(limit, action.as_deref(), failed)
+        }
+        Commands::Clean { older_than, dry_run, dirs } => commands::clean::run(older_than, dry_run, dirs),
+        Commands::NotifyTest { event } => commands::notify_test::run(event),
+        Commands::PruneAudio { older_than, dry_run } => commands::prune_audio::run(older_than.as_deref(), dry_run),
+        Commands::Delete { video_id, keep_files, dry_run } => commands::delete::run(&video_id, keep_files, dry_run),
+        Commands::Account => commands::account::run().await,
+        Commands::Usage { month } => commands::usage::run(month.as_deref()),
+        Commands::Init { api_key, force, encrypt, skip_verify } => {
+            commands::init::run(api_key, force, encrypt, skip_verify).await
+        }
+        Commands::Reindex { pending } => commands::reindex::run(pending),
+        Commands::Get { url, backend } => commands::get::run(&url, backend.unwrap_or(default_backend)).await,
+        Commands::Channel { channel, limit, first } => commands::channel::run(&channel, limit, first),
+        Commands::Coverage { channel, limit, backend, price_per_hour, first } => {
+            commands::coverage::run(&channel, limit, backend.unwrap_or(default_backend), price_per_hour, first)
+        }
+        Commands::ChannelTranscribe {
+            channel,
+            limit,
+            min_duration,
+            max_duration,
+            backend,
+            use_captions,
+            captions_lang,
+            jobs,
+            first,
+        } => {
+            let durations = DurationFilter {
+                min: min_duration.map(|mins| mins * 60),
+                max: max_duration.map(|mins| mins * 60),
+            };
+            commands::channel_transcribe::run(
+                &channel,
+                limit,
+                durations,
+                backend.unwrap_or(default_backend),
+                use_captions.then_some(captions_lang.as_str()),
+                jobs.into(),
+                first,
+            )
+            .await
+        }
+        Commands::Subscribe { channel: Some(channel), name, backfill, first } => {
+            commands::subscriptions::subscribe(&channel, name.as_deref(), backfill, first)
+        }
+        Commands::Subscribe { channel: None, .. } => commands::subscriptions::list(),
+        Commands::Unsubscribe { channel } => commands::subscriptions::unsubscribe(&channel),
+        Commands::Sync { options } => {
+            commands::subscriptions::sync(&options.into_options(default_backend)).await
+        }
+        Commands::Daemon { interval, pid_file, options } => {
+            commands::daemon::run(interval, pid_file, options.into_options(default_backend)).await
+        }
+        Commands::Resume => commands::resume::run().await,
+        Commands::Retranscribe { video_id, backend } => {
+            commands::retranscribe::run(&video_id, backend.unwrap_or(default_backend)).await
+        }
+        Commands::Queue { action } => match action {
+            QueueAction::Add { urls, from_file, backend, use_captions, captions_lang } => {
+                let captions_lang = use_captions.then_some(captions_lang.as_str());
+                commands::queue::add(
+                    urls,
+                    from_file.as_deref(),
+                    backend.unwrap_or(default_backend),
+                    captions_lang,
+                )
+            }
+            QueueAction::Run { limit, max_attempts, jobs } => {
+                commands::queue::run(limit, max_attempts, jobs.into()).await
+            }
+            QueueAction::Status { all } => commands::queue::status(all),
+            QueueAction::Retry => commands::queue::retry(),
+        },
+        Commands::Backup { action: Some(BackupAction::Verify { location, endpoint, profile, quick }), .. } => {
+            commands::backup::verify(location.as_deref(), endpoint, profile, quick)
+        }
+        Commands::Backup { action: None, path, with_transcripts, no_audio } => {
+            commands::backup::create(&path.unwrap_or_default(), with_transcripts, !no_audio)
+        }
+        Commands::Archive { action: ArchiveAction::Export { file } } => commands::archive::export(&file),
+        Commands::Archive { action: ArchiveAction::Import { file, force } } => commands::archive::import(&file, force),
+        Commands::SyncRemote { action: SyncRemoteAction::Push { force } } => commands::sync_remote::push(force),
+        Commands::SyncRemote { action: SyncRemoteAction::Pull { force } } => commands::sync_remote::pull(force),
+        Commands::SyncRemote { action: SyncRemoteAction::Status } => commands::sync_remote::status(),
+        Commands::Config { action: ConfigAction::Export { file, with_secrets } } => {
+            commands::config_bundle::export(&file, with_secrets)
+        }
+        Commands::Config { action: ConfigAction::Import { file, force } } => {
+            commands::config_bundle::import(&file, force)
+        }
+        Commands::Jobs { action: JobsAction::Status { job_id } } => {
+            commands::jobs::status(job_id.as_deref()).await
+        }
+        Commands::Jobs { action: JobsAction::Cancel { job_id } } => commands::jobs::cancel(&job_id).await,
+        Commands::Undo => commands::undo::run(),
+        Commands::Corpus { channel, out, anonymize } => {
+            commands::corpus::run(&channel, out.as_deref(), anonymize)
+        }
+        Commands::Publish { tag, out } => commands::publish::run(&tag, &out),
+        Commands::ExportVault { dir, platform, channel, sync } => {
+            commands::vault::run(&dir, platform.as_deref(), channel.as_deref(), sync)
+        }
+        Commands::Export { video_ids, format, platform, channel, output, anonymize, offset } => {
+            commands::export::run(
+                &video_ids,
+                platform.as_deref(),
+                channel.as_deref(),
+                format,
+                output.as_deref(),
+                anonymize,
+                offset.as_deref(),
+            )
+        }
         Commands::YtSearch { query, limit } => commands::yt_search::run(&query, limit),
-    };
+        Commands::SelfUpdate { check, force } => commands::self_update::run(check, force).await,
+        Commands::Batch { script, keep_going } => run_script(&script, keep_going, language).await,
+    }
+}
 
-    if let Err(e) = result {
-        eprintln!("Error: {}", e);
-        std::process::exit(1);
+/// A line of a `yt-cli batch` script: a command without the global options, which are set
+/// once for the whole batch
+#[derive(Parser)]
+#[command(name = "yt-cli", no_binary_name = true)]
+struct ScriptLine {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+/// Run the commands in a script one after another in this process, stopping at the first
+/// one that fails unless `keep_going`
+///
+/// Every line is checked before anything runs, so a typo near the end doesn't leave the
+/// script half done.
+async fn run_script(path: &str, keep_going: bool, language: Option<String>) -> Result<()> {
+    let name = if path == "-" { "stdin" } else { path };
+    let mut lines = Vec::new();
+    for (number, words) in commands::script::read(path)? {
+        let line = ScriptLine::try_parse_from(&words).map_err(|e| {
+            let message = e.to_string();
+            let mut message = message.lines().next().unwrap_or_default().trim_start_matches("error: ").to_string();
+            if e.kind() == clap::error::ErrorKind::UnknownArgument {
+                message.push_str(" (global options like --yes go before `batch` and apply to every line)");
+            }
+            Error::Config(format!("{} line {}: {}", name, number, message))
+        })?;
+        let invalid = match &line.command {
+            Commands::Batch { .. } => Some("a batch can't run another batch".to_string()),
+            command => per_run_options(command).map(|options| {
+                format!(
+                    "{} can't change within a batch; set them in config.toml or run this command on its own",
+                    options
+                )
+            }),
+        };
+        if let Some(invalid) = invalid {
+            return Err(Error::Config(format!("{} line {}: {}", name, number, invalid)));
+        }
+        lines.push((number, words.join(" "), line.command));
     }
+
+    let total = lines.len();
+    let mut ran = 0;
+    let mut failed = 0;
+    let mut stopped = None;
+    for (number, text, command) in lines {
+        ran += 1;
+        // Boxed, as `run` is what runs the batch in the first place
+        let Err(e) = Box::pin(run(command, language.clone())).await else {
+            continue;
+        };
+        failed += 1;
+        if !keep_going {
+            eprintln!("Stopped at {} line {} ({}).", name, number, text);
+            stopped = Some(e);
+            break;
+        }
+        eprintln!("{} line {} failed ({}):", name, number, text);
+        print_error(&e);
+    }
+
+    let outcome = format!("{} of {} command(s) run, {} failed", ran, total, failed);
+    history::record("script", Some(name), Some(&outcome), failed == 0);
+    match stopped {
+        Some(e) => Err(e),
+        None if failed > 0 => Err(Error::Script(format!("{} of {} command(s) failed", failed, total))),
+        None => Ok(()),
+    }
+}
+
+/// The options of a command that are applied to the settings at startup (see `main`), so
+/// can't differ from one line of a batch to the next
+fn per_run_options(command: &Commands) -> Option<String> {
+    let mut options = Vec::new();
+    match command {
+        Commands::Transcribe {
+            speakers,
+            boost_words,
+            multichannel,
+            no_keep_audio,
+            chapters,
+            highlights,
+            entities,
+            redact_pii,
+            redact_pii_sub,
+            ..
+        } => {
+            for (set, option) in [
+                (speakers.is_some(), "--speakers"),
+                (!boost_words.is_empty(), "--boost-words"),
+                (*multichannel, "--multichannel"),
+                (*no_keep_audio, "--no-keep-audio"),
+                (*chapters, "--chapters"),
+                (*highlights, "--highlights"),
+                (*entities, "--entities"),
+                (!redact_pii.is_empty(), "--redact-pii"),
+                (redact_pii_sub.is_some(), "--redact-pii-sub"),
+            ] {
+                if set {
+                    options.push(option);
+                }
+            }
+        }
+        Commands::TranscribeUrl { speakers, boost_words, .. } => {
+            if speakers.is_some() {
+                options.push("--speakers");
+            }
+            if !boost_words.is_empty() {
+                options.push("--boost-words");
+            }
+        }
+        _ => {}
+    }
+    (!options.is_empty()).then(|| options.join(", "))
+}
+
+/// Print an error, and what to do about it when known, then exit with status 1
+fn exit_with_error(e: Error) -> ! {
+    print_error(&e);
+    std::process::exit(1);
 }
@@ -0,0 +1,276 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::{LazyLock, OnceLock};
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Serialize;
+
+use crate::commands::history;
+use crate::config::{settings, NotifierConfig, NotifierKind, NotifyEvent};
+use crate::error::{Error, Result};
+
+/// Seconds a notification request may take before it's given up on
+const REQUEST_TIMEOUT_SECONDS: u64 = 30;
+
+/// Client shared by the webhook notifiers
+static CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECONDS))
+        .build()
+        .unwrap_or_default()
+});
+
+/// What happened, as sent to each notifier
+#[derive(Serialize)]
+pub struct Notification<'a> {
+    pub event: NotifyEvent,
+    pub title: &'a str,
+    pub message: &'a str,
+    /// Page of the video concerned, if any
+    pub url: Option<&'a str>,
+}
+
+impl Notification<'_> {
+    /// The message followed by the URL, for notifiers taking plain text
+    fn body(&self) -> String {
+        match self.url {
+            Some(url) => format!("{}\n{}", self.message, url),
+            None => self.message.to_string(),
+        }
+    }
+}
+
+/// Somewhere notifications are delivered
+pub trait Notifier: Send + Sync {
+    /// Notifier name for status output
+    fn name(&self) -> &'static str;
+
+    fn send(&self, notification: &Notification) -> Result<()>;
+}
+
+/// A desktop notification, via `notify-send` (Linux) or `osascript` (macOS)
+pub struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
+    fn name(&self) -> &'static str {
+        "desktop"
+    }
+
+    fn send(&self, notification: &Notification) -> Result<()> {
+        let program = if cfg!(target_os = "macos") { "osascript" } else { "notify-send" };
+        let mut cmd = Command::new(program);
+        if cfg!(target_os = "macos") {
+            let quote = |text: &str| format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""));
+            cmd.arg("-e").arg(format!(
+                "display notification {} with title {}",
+                quote(notification.message),
+                quote(notification.title)
+            ));
+        } else {
+            cmd.args(["--app-name", "yt-cli", notification.title, notification.message]);
+        }
+        run(cmd, None, program)
+    }
+}
+
+/// A Slack incoming webhook
+pub struct SlackNotifier {
+    url: String,
+}
+
+impl Notifier for SlackNotifier {
+    fn name(&self) -> &'static str {
+        "slack"
+    }
+
+    fn send(&self, notification: &Notification) -> Result<()> {
+        let text = format!("*{}*\n{}", notification.title, notification.body());
+        post_json(&self.url, &serde_json::json!({ "text": text }))
+    }
+}
+
+/// A Discord channel webhook
+pub struct DiscordNotifier {
+    url: String,
+}
+
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> &'static str {
+        "discord"
+    }
+
+    fn send(&self, notification: &Notification) -> Result<()> {
+        let content = format!("**{}**\n{}", notification.title, notification.body());
+        post_json(&self.url, &serde_json::json!({ "content": content }))
+    }
+}
+
+/// An email handed to `sendmail -t` or a configured command
+pub struct EmailNotifier {
+    to: String,
+    from: Option<String>,
+    command: Option<String>,
+}
+
+impl Notifier for EmailNotifier {
+    fn name(&self) -> &'static str {
+        "email"
+    }
+
+    fn send(&self, notification: &Notification) -> Result<()> {
+        let mut message = format!("To: {}\n", self.to);
+        if let Some(from) = &self.from {
+            message.push_str(&format!("From: {}\n", from));
+        }
+        message.push_str(&format!(
+            "Subject: [yt-cli] {}\nContent-Type: text/plain; charset=utf-8\n\n{}\n",
+            notification.title,
+            notification.body()
+        ));
+
+        let command = self.command.as_deref().unwrap_or("sendmail -t");
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        run(cmd, Some(message.as_bytes()), "sh")
+    }
+}
+
+/// The notification as JSON, POSTed to any URL
+pub struct WebhookNotifier {
+    url: String,
+}
+
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn send(&self, notification: &Notification) -> Result<()> {
+        post_json(&self.url, &serde_json::to_value(notification)?)
+    }
+}
+
+/// Build the notifier described by a `[[notify]]` table
+pub fn from_config(config: &NotifierConfig) -> Result<Box<dyn Notifier>> {
+    Ok(match config.kind.clone() {
+        NotifierKind::Desktop => Box::new(DesktopNotifier),
+        NotifierKind::Slack { url, url_env } => Box::new(SlackNotifier { url: webhook_url("slack", url, url_env)? }),
+        NotifierKind::Discord { url, url_env } => {
+            Box::new(DiscordNotifier { url: webhook_url("discord", url, url_env)? })
+        }
+        NotifierKind::Email { to, from, command } => Box::new(EmailNotifier { to, from, command }),
+        NotifierKind::Webhook { url, url_env } => {
+            Box::new(WebhookNotifier { url: webhook_url("webhook", url, url_env)? })
+        }
+    })
+}
+
+/// `url`, else the value of the `url_env` variable
+fn webhook_url(kind: &str, url: Option<String>, url_env: Option<String>) -> Result<String> {
+    if let Some(url) = url.filter(|url| !url.trim().is_empty()) {
+        return Ok(url);
+    }
+    let Some(name) = url_env else {
+        return Err(Error::Config(format!("[[notify]] kind = \"{}\" needs `url` or `url_env`", kind)));
+    };
+    std::env::var(&name)
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+        .ok_or_else(|| Error::Config(format!("{} (url_env of a {} notifier) is not set", name, kind)))
+}
+
+/// A configured notifier with the events routed to it
+pub struct Route {
+    pub notifier: Box<dyn Notifier>,
+    /// Empty routes every event
+    pub events: Vec<NotifyEvent>,
+}
+
+impl Route {
+    pub fn wants(&self, event: NotifyEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+static ROUTES: OnceLock<Vec<Route>> = OnceLock::new();
+
+/// The notifiers configured in config.toml; ones that can't be set up are reported once and
+/// left out
+pub fn routes() -> &'static [Route] {
+    ROUTES.get_or_init(|| {
+        settings()
+            .notify
+            .iter()
+            .filter_map(|config| match from_config(config) {
+                Ok(notifier) => Some(Route { notifier, events: config.events.clone() }),
+                Err(e) => {
+                    eprintln!("Warning: skipping notifier: {}", e);
+                    None
+                }
+            })
+            .collect()
+    })
+}
+
+/// Send a notification to every notifier routed `event`
+///
+/// A notifier that fails is reported but doesn't fail the job being announced.
+pub fn notify(event: NotifyEvent, title: &str, message: &str, url: Option<&str>) {
+    let notification = Notification { event, title, message, url };
+    for route in routes().iter().filter(|route| route.wants(event)) {
+        if let Err(e) = route.notifier.send(&notification) {
+            let name = route.notifier.name();
+            eprintln!("Warning: {} notification failed: {}", name, e);
+            history::record("notify", Some(name), Some(&format!("{}: {}", event.id(), e)), false);
+        }
+    }
+}
+
+/// POST `body` as JSON to a webhook URL
+///
+/// Notifications are sent from synchronous code running on the async runtime, so the request
+/// is driven with `block_in_place`, which moves the worker's other tasks to another thread
+/// while this one waits.
+fn post_json(url: &str, body: &serde_json::Value) -> Result<()> {
+    let request = async {
+        // Webhook URLs are secrets, so errors leave them out
+        let response = CLIENT.post(url).json(body).send().await.map_err(|e| Error::Http(e.without_url()))?;
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::Notify(format!("{} {}", status, text.trim()).trim().to_string()));
+        }
+        Ok(())
+    };
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(request)),
+        Err(_) => tokio::runtime::Builder::new_current_thread().enable_all().build()?.block_on(request),
+    }
+}
+
+fn run(mut cmd: Command, stdin: Option<&[u8]>, program: &str) -> Result<()> {
+    let mut child = cmd
+        .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                Error::Notify(format!("`{}` not found in PATH", program))
+            } else {
+                Error::Io(e)
+            }
+        })?;
+
+    if let (Some(data), Some(mut pipe)) = (stdin, child.stdin.take()) {
+        pipe.write_all(data)?;
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(Error::Notify(if stderr.is_empty() { output.status.to_string() } else { stderr }));
+    }
+    Ok(())
+}
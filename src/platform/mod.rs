@@ -0,0 +1,181 @@
+mod odysee;
+mod podcast;
+mod rumble;
+mod twitch;
+mod youtube;
+
+use reqwest::Url;
+
+pub use odysee::Odysee;
+pub use podcast::{ApplePodcasts, Spotify};
+pub use rumble::Rumble;
+pub use twitch::Twitch;
+pub use youtube::YouTube;
+
+/// URL handling for one video platform
+pub trait Platform: Sync {
+    /// Name used for storage directories and the database (e.g. "youtube")
+    fn name(&self) -> &'static str;
+
+    /// Domains handled by this platform (matched as suffixes, without "www.")
+    fn domains(&self) -> &'static [&'static str];
+
+    /// Extract the video ID from a URL (defaults to the last path segment)
+    fn extract_id(&self, url: &str) -> Option<String> {
+        last_path_segment(url)
+    }
+
+    /// Normalize a URL before downloading or deduplicating (defaults to dropping tracking
+    /// parameters)
+    fn normalize_url(&self, url: &str) -> String {
+        strip_tracking(url)
+    }
+
+    /// Canonical URL for a video ID, if the platform has a stable one
+    fn canonical_url(&self, _id: &str) -> Option<String> {
+        None
+    }
+
+    /// URL that starts playback `seconds` into the video, if the platform supports one
+    fn timestamp_url(&self, _url: &str, _seconds: i64) -> Option<String> {
+        None
+    }
+}
+
+/// Platform that only needs domain matching and the default ID/URL handling
+struct Simple {
+    name: &'static str,
+    domains: &'static [&'static str],
+}
+
+impl Platform for Simple {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        self.domains
+    }
+}
+
+/// Query parameters that only record where a link was shared from (besides `utm_*`)
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "dclid", "msclkid", "yclid", "igshid", "si", "ref_src", "mc_cid", "mc_eid", "_hsenc", "_hsmi",
+];
+
+/// Registered platforms, checked in order
+static PLATFORMS: &[&dyn Platform] = &[
+    &YouTube,
+    &Twitch,
+    &Rumble,
+    &Odysee,
+    &Spotify,
+    &ApplePodcasts,
+    &Simple { name: "vimeo", domains: &["vimeo.com"] },
+    &Simple { name: "twitter", domains: &["twitter.com", "x.com"] },
+    &Simple { name: "dailymotion", domains: &["dailymotion.com"] },
+    &Simple { name: "facebook", domains: &["facebook.com", "fb.watch"] },
+    &Simple { name: "instagram", domains: &["instagram.com"] },
+    &Simple { name: "tiktok", domains: &["tiktok.com"] },
+];
+
+/// Lowercased host of a URL without the "www." prefix
+pub fn domain(url: &str) -> String {
+    let url_lower = url.trim().to_lowercase();
+    url_lower
+        .split("://")
+        .nth(1)
+        .unwrap_or(&url_lower)
+        .split(['/', '?', '#'])
+        .next()
+        .unwrap_or("")
+        .trim_start_matches("www.")
+        .to_string()
+}
+
+/// Find the registered platform for a URL
+pub fn detect(url: &str) -> Option<&'static dyn Platform> {
+    let domain = domain(url);
+    PLATFORMS.iter().copied().find(|p| {
+        p.domains()
+            .iter()
+            .any(|d| domain == *d || domain.ends_with(&format!(".{}", d)))
+    })
+}
+
+/// Find a registered platform by its storage name
+pub fn by_name(name: &str) -> Option<&'static dyn Platform> {
+    PLATFORMS.iter().copied().find(|p| p.name() == name)
+}
+
+/// Platform name for a URL, falling back to the domain without its TLD
+pub fn platform_name(url: &str) -> String {
+    if let Some(platform) = detect(url) {
+        return platform.name().to_string();
+    }
+
+    domain(url)
+        .split('.')
+        .next()
+        .filter(|s| !s.is_empty())
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// Extract a video ID from any URL
+pub fn extract_id(url: &str) -> Option<String> {
+    match detect(url) {
+        Some(platform) => platform.extract_id(url),
+        None => last_path_segment(url),
+    }
+}
+
+/// Normalize any URL using its platform's rules
+pub fn normalize_url(url: &str) -> String {
+    match detect(url) {
+        Some(platform) => platform.normalize_url(url),
+        None => strip_tracking(url),
+    }
+}
+
+/// A URL without tracking parameters such as `utm_source`, `fbclid` or `si`
+fn strip_tracking(url: &str) -> String {
+    let url = url.trim();
+    let Some(mut parsed) = parse_url(url).filter(|u| u.query().is_some()) else {
+        return url.to_string();
+    };
+    let kept: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if kept.is_empty() {
+        parsed.set_query(None);
+    } else {
+        parsed.query_pairs_mut().clear().extend_pairs(kept);
+    }
+    parsed.to_string()
+}
+
+/// Link to a moment in a video, or the plain URL when its platform has no timestamp links
+pub fn timestamp_url(url: &str, seconds: i64) -> String {
+    detect(url)
+        .and_then(|platform| platform.timestamp_url(url, seconds.max(0)))
+        .unwrap_or_else(|| url.trim().to_string())
+}
+
+/// Last non-empty path segment of a URL, ignoring query and fragment
+fn last_path_segment(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let without_scheme = path.split("://").nth(1).unwrap_or(path);
+    let (_, path) = without_scheme.split_once('/')?;
+    path.split('/').rfind(|s| !s.is_empty()).map(String::from)
+}
+
+/// Parse a URL, tolerating a missing scheme
+fn parse_url(url: &str) -> Option<Url> {
+    let url = url.trim();
+    Url::parse(url)
+        .or_else(|_| Url::parse(&format!("https://{}", url)))
+        .ok()
+}
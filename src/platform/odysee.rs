@@ -0,0 +1,29 @@
+use super::{parse_url, Platform};
+
+/// Odysee / LBRY videos (odysee.com/@channel:c/video-name:a)
+pub struct Odysee;
+
+impl Platform for Odysee {
+    fn name(&self) -> &'static str {
+        "odysee"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["odysee.com", "lbry.tv"]
+    }
+
+    fn extract_id(&self, url: &str) -> Option<String> {
+        let parsed = parse_url(url)?;
+        let segment = parsed.path_segments()?.rfind(|s| !s.is_empty())?;
+
+        // Claim URLs are "name:claim_id" (percent-encoded as %3A in shared links)
+        let decoded = segment.replace("%3A", ":").replace("%3a", ":");
+        let name = decoded.split([':', '#']).next().unwrap_or(&decoded);
+        Some(name.to_string()).filter(|name| !name.is_empty() && !name.starts_with('@'))
+    }
+
+    fn normalize_url(&self, url: &str) -> String {
+        // Drop referral query strings (e.g. ?r=abc123)
+        url.trim().split(['?', '#']).next().unwrap_or(url).to_string()
+    }
+}
@@ -0,0 +1,71 @@
+use super::{parse_url, strip_tracking, Platform};
+
+/// Spotify podcast episodes (open.spotify.com/episode/ID)
+pub struct Spotify;
+
+impl Platform for Spotify {
+    fn name(&self) -> &'static str {
+        "podcast"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["open.spotify.com", "spotify.com"]
+    }
+
+    fn extract_id(&self, url: &str) -> Option<String> {
+        let parsed = parse_url(url)?;
+        let segments: Vec<&str> = parsed.path_segments()?.filter(|s| !s.is_empty()).collect();
+
+        // Localized links look like /intl-de/episode/ID
+        segments
+            .windows(2)
+            .find(|pair| pair[0] == "episode")
+            .map(|pair| pair[1].to_string())
+    }
+
+    fn normalize_url(&self, url: &str) -> String {
+        self.extract_id(url)
+            .and_then(|id| self.canonical_url(&id))
+            .unwrap_or_else(|| strip_tracking(url))
+    }
+
+    fn canonical_url(&self, id: &str) -> Option<String> {
+        Some(format!("https://open.spotify.com/episode/{}", id))
+    }
+}
+
+/// Apple Podcasts episodes (podcasts.apple.com/us/podcast/show/id123?i=1000456)
+pub struct ApplePodcasts;
+
+impl Platform for ApplePodcasts {
+    fn name(&self) -> &'static str {
+        "podcast"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["podcasts.apple.com"]
+    }
+
+    fn extract_id(&self, url: &str) -> Option<String> {
+        let parsed = parse_url(url)?;
+
+        // Episode links carry the episode ID in ?i=; show links only have /id123
+        if let Some((_, id)) = parsed.query_pairs().find(|(k, _)| k == "i") {
+            return Some(id.into_owned());
+        }
+
+        let segment = parsed.path_segments()?.rfind(|s| !s.is_empty())?;
+        segment.strip_prefix("id").map(String::from)
+    }
+
+    fn normalize_url(&self, url: &str) -> String {
+        // Keep the episode parameter, drop everything else
+        let Some(mut parsed) = parse_url(url) else {
+            return url.trim().to_string();
+        };
+        let episode = parsed.query_pairs().find(|(k, _)| k == "i").map(|(_, v)| v.into_owned());
+        parsed.set_fragment(None);
+        parsed.set_query(episode.map(|id| format!("i={}", id)).as_deref());
+        parsed.to_string()
+    }
+}
@@ -0,0 +1,38 @@
+use super::{parse_url, Platform};
+
+/// Rumble videos (rumble.com/v4abcd-some-title.html, rumble.com/embed/v4xyz/)
+pub struct Rumble;
+
+impl Platform for Rumble {
+    fn name(&self) -> &'static str {
+        "rumble"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["rumble.com"]
+    }
+
+    fn extract_id(&self, url: &str) -> Option<String> {
+        let parsed = parse_url(url)?;
+        let segments: Vec<&str> = parsed.path_segments()?.filter(|s| !s.is_empty()).collect();
+
+        let slug = match segments.as_slice() {
+            ["embed", id, ..] => *id,
+            [slug, ..] => *slug,
+            [] => return None,
+        };
+
+        // The ID is the slug up to the first dash: "v4abcd-some-title.html" -> "v4abcd"
+        let id = slug.split('-').next().unwrap_or(slug).trim_end_matches(".html");
+        Some(id.to_string()).filter(|id| !id.is_empty())
+    }
+
+    fn normalize_url(&self, url: &str) -> String {
+        // Drop referral/tracking query strings (e.g. ?e9s=src_v1_ucp)
+        url.trim().split(['?', '#']).next().unwrap_or(url).to_string()
+    }
+
+    fn canonical_url(&self, id: &str) -> Option<String> {
+        Some(format!("https://rumble.com/embed/{}/", id))
+    }
+}
@@ -0,0 +1,35 @@
+use super::{last_path_segment, parse_url, Platform};
+
+/// Twitch VODs and clips
+pub struct Twitch;
+
+impl Platform for Twitch {
+    fn name(&self) -> &'static str {
+        "twitch"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["twitch.tv"]
+    }
+
+    fn extract_id(&self, url: &str) -> Option<String> {
+        let parsed = parse_url(url)?;
+        let segments: Vec<&str> = parsed.path_segments()?.filter(|s| !s.is_empty()).collect();
+
+        // twitch.tv/videos/123456 -> yt-dlp uses "v123456"
+        match segments.as_slice() {
+            ["videos", id, ..] => Some(format!("v{}", id.trim_start_matches('v'))),
+            _ => last_path_segment(url),
+        }
+    }
+
+    fn canonical_url(&self, id: &str) -> Option<String> {
+        id.strip_prefix('v')
+            .map(|number| format!("https://www.twitch.tv/videos/{}", number))
+    }
+
+    fn timestamp_url(&self, url: &str, seconds: i64) -> Option<String> {
+        let vod = self.canonical_url(&self.extract_id(url)?)?;
+        Some(format!("{}?t={}h{}m{}s", vod, seconds / 3600, seconds / 60 % 60, seconds % 60))
+    }
+}
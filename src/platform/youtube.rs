@@ -0,0 +1,87 @@
+use super::{parse_url, strip_tracking, Platform};
+
+/// YouTube videos, shorts and live streams
+pub struct YouTube;
+
+impl Platform for YouTube {
+    fn name(&self) -> &'static str {
+        "youtube"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["youtube.com", "youtu.be"]
+    }
+
+    fn extract_id(&self, url: &str) -> Option<String> {
+        let parsed = parse_url(url)?;
+        let host = parsed.host_str().unwrap_or_default().to_lowercase();
+
+        // youtube.com/watch?v=VIDEO_ID
+        if let Some((_, id)) = parsed.query_pairs().find(|(k, _)| k == "v") {
+            return Some(id.into_owned()).filter(|id| !id.is_empty());
+        }
+
+        let mut segments = parsed.path_segments()?.filter(|s| !s.is_empty());
+
+        // youtu.be/VIDEO_ID
+        if host.ends_with("youtu.be") {
+            return segments.next().map(String::from);
+        }
+
+        // youtube.com/shorts/VIDEO_ID, /live/VIDEO_ID, /embed/VIDEO_ID
+        match segments.next() {
+            Some("shorts" | "live" | "embed" | "v") => segments.next().map(String::from),
+            _ => None,
+        }
+    }
+
+    fn normalize_url(&self, url: &str) -> String {
+        self.extract_id(url)
+            .and_then(|id| self.canonical_url(&id))
+            .unwrap_or_else(|| strip_tracking(url))
+    }
+
+    fn canonical_url(&self, id: &str) -> Option<String> {
+        Some(format!("https://www.youtube.com/watch?v={}", id))
+    }
+
+    fn timestamp_url(&self, url: &str, seconds: i64) -> Option<String> {
+        let id = self.extract_id(url)?;
+        Some(format!("https://www.youtube.com/watch?v={}&t={}s", id, seconds))
+    }
+}
+
+impl YouTube {
+    /// Videos tab URL for a channel URL, @handle or channel ID
+    pub fn channel_videos_url(url: &str) -> String {
+        let url = url.trim_end_matches('/');
+
+        // If already pointing to /videos, return as-is
+        if url.ends_with("/videos") {
+            return url.to_string();
+        }
+
+        // If it's a channel URL, append /videos
+        if url.contains("youtube.com/") {
+            return format!("{}/videos", url);
+        }
+
+        // Assume it's a channel handle if it starts with @
+        if url.starts_with('@') {
+            return format!("https://www.youtube.com/{}/videos", url);
+        }
+
+        // Assume it's a channel ID
+        format!("https://www.youtube.com/channel/{}/videos", url)
+    }
+
+    /// Whether a channel argument is a bare name ("Lex Fridman") rather than a URL, @handle
+    /// or channel ID, and so has to be looked up
+    pub fn is_channel_name(channel: &str) -> bool {
+        let channel = channel.trim();
+        let is_id = channel.len() == 24
+            && channel.starts_with("UC")
+            && channel.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        !(channel.contains('/') || channel.contains('.') || channel.starts_with('@') || is_id)
+    }
+}
@@ -0,0 +1,324 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::config::{data_dir, settings, transcripts_dir, StorageConfig};
+use crate::error::{Error, Result};
+
+/// Destination for transcript files, addressed by keys relative to the transcripts root
+/// (e.g. `youtube/Channel/VIDEO_ID/transcript.md`)
+pub trait StorageBackend: Send + Sync {
+    /// Backend name for status output
+    fn name(&self) -> &'static str;
+
+    /// Write bytes under a key, replacing any existing object
+    fn put(&self, key: &str, data: &[u8]) -> Result<()>;
+
+    /// Copy a local file into storage under a key, leaving the source in place
+    fn copy_file(&self, key: &str, source: &Path) -> Result<()> {
+        self.put(key, &fs::read(source)?)
+    }
+
+    /// Move a local file into storage under a key
+    fn put_file(&self, key: &str, source: &Path) -> Result<()> {
+        self.copy_file(key, source)?;
+        fs::remove_file(source)?;
+        Ok(())
+    }
+
+    /// Store a key as a hard link to a file already in local storage; false if the
+    /// backend can't, and the data has to be stored normally
+    fn link_file(&self, _key: &str, _existing: &Path) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+/// Files under the local transcripts directory
+pub struct LocalBackend {
+    root: PathBuf,
+}
+
+impl LocalBackend {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(path)
+    }
+}
+
+impl StorageBackend for LocalBackend {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        // Written next to the target and renamed over it, so a replaced file is never
+        // seen half-written
+        let path = self.path(key)?;
+        let mut temp = path.clone().into_os_string();
+        temp.push(".tmp");
+        fs::write(&temp, data)?;
+        fs::rename(&temp, &path)?;
+        Ok(())
+    }
+
+    fn copy_file(&self, key: &str, source: &Path) -> Result<()> {
+        fs::copy(source, self.path(key)?)?;
+        Ok(())
+    }
+
+    fn put_file(&self, key: &str, source: &Path) -> Result<()> {
+        fs::rename(source, self.path(key)?)?;
+        Ok(())
+    }
+
+    fn link_file(&self, key: &str, existing: &Path) -> Result<bool> {
+        let path = self.path(key)?;
+        if path.exists() {
+            fs::remove_file(&path)?;
+        }
+        // Fails across filesystems or on ones without hard links
+        Ok(fs::hard_link(existing, &path).is_ok())
+    }
+}
+
+/// S3 or S3-compatible object storage, through the `aws` CLI
+pub struct S3Backend {
+    bucket: String,
+    prefix: Option<String>,
+    endpoint: Option<String>,
+    profile: Option<String>,
+}
+
+impl S3Backend {
+    fn uri(&self, key: &str) -> String {
+        match self.prefix.as_deref().map(|p| p.trim_matches('/')) {
+            Some(prefix) if !prefix.is_empty() => format!("s3://{}/{}/{}", self.bucket, prefix, key),
+            _ => format!("s3://{}/{}", self.bucket, key),
+        }
+    }
+
+    fn command(&self) -> Command {
+        let mut cmd = Command::new("aws");
+        if let Some(endpoint) = &self.endpoint {
+            cmd.args(["--endpoint-url", endpoint]);
+        }
+        if let Some(profile) = &self.profile {
+            cmd.args(["--profile", profile]);
+        }
+        cmd.args(["s3", "cp", "--only-show-errors"]);
+        cmd
+    }
+}
+
+impl StorageBackend for S3Backend {
+    fn name(&self) -> &'static str {
+        "s3"
+    }
+
+    fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        let mut cmd = self.command();
+        cmd.args(["-", &self.uri(key)]);
+        run_with_stdin(cmd, data, "aws")
+    }
+
+    fn copy_file(&self, key: &str, source: &Path) -> Result<()> {
+        let mut cmd = self.command();
+        cmd.arg(source).arg(self.uri(key));
+        run(cmd, "aws")
+    }
+}
+
+/// A WebDAV collection, through `curl`
+pub struct WebDavBackend {
+    url: String,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl WebDavBackend {
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.url.trim_end_matches('/'), key)
+    }
+
+    /// curl, with the credentials (if any) in a config file only we can read: on the
+    /// command line, other local users could see the password in `ps`
+    ///
+    /// The file is removed when the returned guard is dropped, so keep it until curl exits.
+    fn command(&self) -> Result<(Command, Option<CurlConfig>)> {
+        let mut cmd = Command::new("curl");
+        cmd.args(["--silent", "--show-error", "--fail"]);
+        let Some(username) = &self.username else {
+            return Ok((cmd, None));
+        };
+        let password = self.password.as_deref().unwrap_or_default();
+        let config = CurlConfig::create(&format!("user = \"{}\"\n", quote(&format!("{}:{}", username, password))))?;
+        cmd.arg("--config").arg(&config.0);
+        Ok((cmd, Some(config)))
+    }
+
+    /// Create each missing parent collection of a key (WebDAV PUT doesn't create them)
+    fn make_collections(&self, key: &str) {
+        let mut collection = String::new();
+        let parents: Vec<&str> = key.split('/').collect();
+        for segment in &parents[..parents.len().saturating_sub(1)] {
+            collection.push_str(segment);
+            collection.push('/');
+            // Fails with 405 when the collection already exists, which is fine
+            let Ok((mut cmd, _config)) = self.command() else {
+                return;
+            };
+            cmd.args(["--request", "MKCOL", "--output", "/dev/null", &self.url(&collection)]);
+            let _ = cmd.stderr(Stdio::null()).status();
+        }
+    }
+}
+
+/// A curl config file, removed on drop
+struct CurlConfig(PathBuf);
+
+impl CurlConfig {
+    fn create(content: &str) -> Result<Self> {
+        let path = data_dir().join(format!(".curl-{}.conf", uuid::Uuid::new_v4()));
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let config = Self(path);
+        options.open(&config.0)?.write_all(content.as_bytes())?;
+        Ok(config)
+    }
+}
+
+impl Drop for CurlConfig {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// `text` escaped for a double-quoted curl config value
+fn quote(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl StorageBackend for WebDavBackend {
+    fn name(&self) -> &'static str {
+        "webdav"
+    }
+
+    fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        self.make_collections(key);
+        let (mut cmd, _config) = self.command()?;
+        cmd.args(["--upload-file", "-", &self.url(key)]);
+        run_with_stdin(cmd, data, "curl")
+    }
+
+    fn copy_file(&self, key: &str, source: &Path) -> Result<()> {
+        self.make_collections(key);
+        let (mut cmd, _config) = self.command()?;
+        cmd.arg("--upload-file").arg(source).arg(self.url(key));
+        run(cmd, "curl")
+    }
+}
+
+/// Remote backends keep a local working copy so the index, `list` and `read` work offline
+struct WriteThrough<B> {
+    local: LocalBackend,
+    remote: B,
+}
+
+impl<B: StorageBackend> StorageBackend for WriteThrough<B> {
+    fn name(&self) -> &'static str {
+        self.remote.name()
+    }
+
+    fn put(&self, key: &str, data: &[u8]) -> Result<()> {
+        self.local.put(key, data)?;
+        self.remote.put(key, data)
+    }
+
+    fn copy_file(&self, key: &str, source: &Path) -> Result<()> {
+        self.local.copy_file(key, source)?;
+        self.remote.copy_file(key, source)
+    }
+
+    fn put_file(&self, key: &str, source: &Path) -> Result<()> {
+        self.local.put_file(key, source)?;
+        self.remote.copy_file(key, &self.local.path(key)?)
+    }
+
+    /// Only the working copy is linked; the remote gets its own copy
+    fn link_file(&self, key: &str, existing: &Path) -> Result<bool> {
+        if !self.local.link_file(key, existing)? {
+            return Ok(false);
+        }
+        self.remote.copy_file(key, &self.local.path(key)?)?;
+        Ok(true)
+    }
+}
+
+/// Build the backend described by a `[storage]` config table
+pub fn from_config(config: &StorageConfig) -> Box<dyn StorageBackend> {
+    let local = LocalBackend::new(transcripts_dir());
+    match config.clone() {
+        StorageConfig::Local => Box::new(local),
+        StorageConfig::S3 { bucket, prefix, endpoint, profile } => Box::new(WriteThrough {
+            local,
+            remote: S3Backend { bucket, prefix, endpoint, profile },
+        }),
+        StorageConfig::WebDav { url, username, password } => Box::new(WriteThrough {
+            local,
+            remote: WebDavBackend { url, username, password },
+        }),
+    }
+}
+
+static BACKEND: OnceLock<Box<dyn StorageBackend>> = OnceLock::new();
+
+/// The storage backend configured in config.toml
+pub fn backend() -> &'static dyn StorageBackend {
+    BACKEND.get_or_init(|| from_config(&settings().storage)).as_ref()
+}
+
+fn run(mut cmd: Command, program: &str) -> Result<()> {
+    let output = cmd.output().map_err(|e| spawn_error(program, e))?;
+    if !output.status.success() {
+        return Err(Error::Storage(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(())
+}
+
+fn run_with_stdin(mut cmd: Command, data: &[u8], program: &str) -> Result<()> {
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| spawn_error(program, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(data)?;
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(Error::Storage(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    Ok(())
+}
+
+fn spawn_error(program: &str, e: std::io::Error) -> Error {
+    if e.kind() == std::io::ErrorKind::NotFound {
+        Error::Storage(format!("`{}` not found in PATH (required by the storage backend)", program))
+    } else {
+        Error::Io(e)
+    }
+}
@@ -0,0 +1,560 @@
+mod backend;
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::{ensure_writable, settings, transcripts_dir, DirectoryNaming};
+use crate::database::{audio_files_with_hash, record_audio_file, TranscriptFilter};
+use crate::downloader::{chapter_sections, VideoChapter, VideoMetadata};
+use crate::episode;
+use crate::error::{Error, Result};
+use crate::transcriber::{Section, TranscriptData, TranscriptSource, CAPTIONS_ID_PREFIX};
+
+pub use backend::{backend, from_config, LocalBackend, StorageBackend};
+
+/// Sanitize a string for use as a filename
+pub fn sanitize_filename(name: &str, max_length: usize) -> String {
+    let re = Regex::new(r#"[<>:"/\\|?*]"#).unwrap();
+    let sanitized = re.replace_all(name, "_");
+
+    let re_spaces = Regex::new(r"[\s_]+").unwrap();
+    let sanitized = re_spaces.replace_all(&sanitized, "_");
+
+    let sanitized = sanitized.trim_matches(|c| c == '_' || c == ' ');
+
+    let result = if sanitized.len() > max_length {
+        sanitized[..max_length].trim_end_matches('_').to_string()
+    } else {
+        sanitized.to_string()
+    };
+
+    if result.is_empty() {
+        "untitled".to_string()
+    } else {
+        result
+    }
+}
+
+/// Create organized storage path for a video
+/// Structure: transcripts/{platform}/{channel}/{video_id}/, where {channel} follows the
+/// platform's `[platforms.<name>] directory` setting
+pub fn create_storage_path(platform: &str, metadata: &VideoMetadata, video_id: &str) -> Result<PathBuf> {
+    ensure_writable("write transcript files")?;
+    let safe_channel = sanitize_filename(channel_directory(platform, metadata), 100);
+    // Video ID is already safe (alphanumeric), but sanitize just in case
+    let safe_video_id = sanitize_filename(video_id, 50);
+
+    let storage_path = transcripts_dir().join(platform).join(&safe_channel).join(&safe_video_id);
+    fs::create_dir_all(&storage_path)?;
+
+    Ok(storage_path)
+}
+
+/// Remove the channel and platform directories above `dir` that are left empty, e.g.
+/// after deleting its transcript; stops at the first one still holding something
+pub fn remove_empty_parents(dir: &Path) {
+    let root = transcripts_dir();
+    let mut current = dir.parent();
+    while let Some(parent) = current {
+        if parent == root || !parent.starts_with(&root) || fs::remove_dir(parent).is_err() {
+            break;
+        }
+        current = parent.parent();
+    }
+}
+
+/// Name of the directory grouping a video with others from the same source
+fn channel_directory<'a>(platform: &str, metadata: &'a VideoMetadata) -> &'a str {
+    let naming = settings().platform_config(platform).map(|config| config.directory).unwrap_or_default();
+    let name = match naming {
+        DirectoryNaming::Channel => None,
+        DirectoryNaming::Handle => metadata.uploader_id.as_deref().map(|handle| handle.trim_start_matches('@')),
+        DirectoryNaming::Show => metadata.series.as_deref(),
+    };
+    name.map(str::trim).filter(|name| !name.is_empty()).unwrap_or(&metadata.channel)
+}
+
+/// Save transcript in markdown and JSON formats
+pub fn save_transcript(
+    storage_path: &Path,
+    markdown: &str,
+    structured_data: &TranscriptData,
+) -> Result<(PathBuf, PathBuf)> {
+    let md_path = storage_path.join("transcript.md");
+    let json_path = storage_path.join("transcript.json");
+
+    let storage = backend();
+    storage.put(&storage_key(&md_path), markdown.as_bytes())?;
+    storage.put(&storage_key(&json_path), serde_json::to_string_pretty(structured_data)?.as_bytes())?;
+
+    Ok((md_path, json_path))
+}
+
+/// Save video metadata as JSON
+pub fn save_metadata(storage_path: &Path, metadata: &VideoMetadata) -> Result<PathBuf> {
+    let metadata_path = storage_path.join("metadata.json");
+    backend().put(&storage_key(&metadata_path), serde_json::to_string_pretty(metadata)?.as_bytes())?;
+    Ok(metadata_path)
+}
+
+/// File in a transcript directory naming its speakers (`yt-cli speakers set`)
+pub const SPEAKERS_FILE: &str = "speakers.json";
+
+/// Names given to a transcript's speakers, by original label (empty if none)
+pub fn load_speaker_names(storage_path: &Path) -> BTreeMap<String, String> {
+    fs::read_to_string(storage_path.join(SPEAKERS_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Save the speaker names of a transcript
+pub fn save_speaker_names(storage_path: &Path, names: &BTreeMap<String, String>) -> Result<()> {
+    let path = storage_path.join(SPEAKERS_FILE);
+    backend().put(&storage_key(&path), serde_json::to_string_pretty(names)?.as_bytes())
+}
+
+/// Where [`set_aside_speaker_names`] keeps names that no longer fit a transcript
+pub const OLD_SPEAKERS_FILE: &str = "speakers.old.json";
+
+/// Move a transcript's speaker names to speakers.old.json and clear them, e.g. once it was
+/// transcribed again with new labels; returns the names
+pub fn set_aside_speaker_names(storage_path: &Path) -> Result<BTreeMap<String, String>> {
+    let names = load_speaker_names(storage_path);
+    if !names.is_empty() {
+        let path = storage_path.join(OLD_SPEAKERS_FILE);
+        backend().put(&storage_key(&path), serde_json::to_string_pretty(&names)?.as_bytes())?;
+        save_speaker_names(storage_path, &BTreeMap::new())?;
+    }
+    Ok(names)
+}
+
+/// Load saved video metadata as loose JSON values (empty if missing or unreadable)
+pub fn load_metadata(storage_path: &Path) -> HashMap<String, serde_json::Value> {
+    fs::read_to_string(storage_path.join("metadata.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Platform chapters of a video's metadata.json (from [`load_metadata`]) as transcript sections
+pub fn metadata_sections(metadata: &HashMap<String, serde_json::Value>) -> Vec<Section> {
+    let chapters = metadata
+        .get("chapters")
+        .and_then(|v| serde_json::from_value::<Vec<VideoChapter>>(v.clone()).ok())
+        .unwrap_or_default();
+    chapter_sections(&chapters)
+}
+
+/// Move audio file to storage directory
+///
+/// Audio identical to a file already stored (mirrors, reposts) is hard-linked to it
+/// instead, tracked by hash in the `audio_files` table.
+pub fn move_audio_file(source: &Path, storage_path: &Path) -> Result<PathBuf> {
+    let dest = storage_path.join("audio.mp3");
+    let key = storage_key(&dest);
+    let hash = sha256(fs::File::open(source)?)?;
+    if source == dest {
+        // Already in place (a job resumed from stored audio)
+        record_audio_file(&dest.to_string_lossy(), &hash)?;
+        return Ok(dest);
+    }
+
+    let original = audio_files_with_hash(&hash)?
+        .into_iter()
+        .map(PathBuf::from)
+        .find(|p| *p != dest && p.is_file());
+    match original {
+        Some(original) if backend().link_file(&key, &original)? => {
+            fs::remove_file(source)?;
+            eprintln!("Same audio as {}, hard-linked instead of stored twice.", original.display());
+        }
+        _ => backend().put_file(&key, source)?,
+    }
+
+    record_audio_file(&dest.to_string_lossy(), &hash)?;
+    Ok(dest)
+}
+
+/// Hex SHA-256 of everything `reader` yields
+pub fn sha256(reader: impl Read) -> Result<String> {
+    let mut reader = BufReader::new(reader);
+    let mut hasher = Sha256::new();
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            break;
+        }
+        hasher.update(chunk);
+        let length = chunk.len();
+        reader.consume(length);
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Subdirectory of a transcript directory holding files added with `yt-cli attach`
+pub const ATTACHMENTS_DIR: &str = "attachments";
+
+/// A file attached to a transcript
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+    /// Text extracted from it for search, kept beside it as `.NAME.txt`
+    pub text: Option<PathBuf>,
+}
+
+/// Copy a file into a transcript's attachments under `name`
+pub fn save_attachment(storage_path: &Path, name: &str, source: &Path) -> Result<PathBuf> {
+    let dest = storage_path.join(ATTACHMENTS_DIR).join(name);
+    backend().copy_file(&storage_key(&dest), source)?;
+    Ok(dest)
+}
+
+/// Store the text extracted from an attachment, so `reindex` can index it again
+pub fn save_attachment_text(storage_path: &Path, name: &str, text: &str) -> Result<PathBuf> {
+    let dest = storage_path.join(ATTACHMENTS_DIR).join(format!(".{}.txt", name));
+    backend().put(&storage_key(&dest), text.as_bytes())?;
+    Ok(dest)
+}
+
+/// Files attached to a transcript, by name
+pub fn list_attachments(storage_path: &Path) -> Vec<Attachment> {
+    let dir = storage_path.join(ATTACHMENTS_DIR);
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+
+    let mut attachments: Vec<Attachment> = entries
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                return None;
+            }
+            let text = dir.join(format!(".{}.txt", name));
+            Some(Attachment {
+                size: e.metadata().map(|m| m.len()).unwrap_or(0),
+                path: e.path(),
+                text: text.exists().then_some(text),
+                name,
+            })
+        })
+        .collect();
+    attachments.sort_by(|a, b| a.name.cmp(&b.name));
+    attachments
+}
+
+/// Backend key for a file under the local transcripts directory
+fn storage_key(path: &Path) -> String {
+    let transcripts = transcripts_dir();
+    let relative = path.strip_prefix(&transcripts).unwrap_or(path);
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Directory suffix of imported captions, kept next to the video's API transcript
+pub const CAPTIONS_DIR_SUFFIX: &str = "-captions";
+
+/// Transcript listing info
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptInfo {
+    pub path: String,
+    pub video_id: String,
+    /// `TranscriptSource` ID, when known from the directory name or looked up for
+    /// `--source` and duplicates
+    pub source: Option<String>,
+    pub title: String,
+    pub channel: String,
+    pub channel_handle: Option<String>,
+    pub platform: String,
+    pub duration: Option<i64>,
+    pub upload_date: Option<String>,
+    pub url: Option<String>,
+    pub transcribed_by: Option<String>,
+    /// Show name and episode number, from metadata.json or else detected in the title
+    pub series: Option<String>,
+    pub episode_number: Option<i64>,
+}
+
+/// List available transcripts
+pub fn list_transcripts(
+    platform: Option<&str>,
+    channel: Option<&str>,
+    handle: Option<&str>,
+    filter: &TranscriptFilter,
+) -> Result<Vec<TranscriptInfo>> {
+    let mut results = Vec::new();
+    let base_dir = transcripts_dir();
+
+    if !base_dir.exists() {
+        return Ok(results);
+    }
+
+    // Determine search paths based on platform filter only
+    let search_paths: Vec<PathBuf> = if let Some(p) = platform {
+        vec![base_dir.join(p)]
+    } else {
+        vec![base_dir]
+    };
+
+    for search_path in search_paths {
+        if !search_path.exists() {
+            continue;
+        }
+
+        find_transcripts_recursive(&search_path, &mut results)?;
+    }
+
+    // Filter by channel display name
+    if let Some(channel_filter) = channel {
+        let filter_lower = channel_filter.to_lowercase();
+        results.retain(|t| t.channel.to_lowercase().contains(&filter_lower));
+    }
+
+    // Filter by channel handle
+    if let Some(handle_filter) = handle {
+        let filter_lower = handle_filter.to_lowercase();
+        results.retain(|t| {
+            t.channel_handle
+                .as_ref()
+                .map(|h| h.to_lowercase().contains(&filter_lower))
+                .unwrap_or(false)
+        });
+    }
+
+    // Filter by word count / confidence / language (requires reading transcript.json)
+    if !filter.is_empty() {
+        results.retain(|t| passes_filter(Path::new(&t.path), filter));
+    }
+
+    match filter.source {
+        Some(source) => {
+            for t in &mut results {
+                resolve_source(t);
+            }
+            results.retain(|t| t.source.as_deref() == Some(source.id()));
+        }
+        None => dedupe_sources(&mut results),
+    }
+
+    Ok(results)
+}
+
+/// Keep one transcript per video: the API transcript when there are captions too
+fn dedupe_sources(results: &mut Vec<TranscriptInfo>) {
+    let key = |t: &TranscriptInfo| (t.platform.clone(), t.video_id.clone());
+
+    let mut counts: HashMap<(String, String), usize> = HashMap::new();
+    for t in results.iter() {
+        *counts.entry(key(t)).or_default() += 1;
+    }
+    for t in results.iter_mut() {
+        if counts[&key(t)] > 1 {
+            resolve_source(t);
+        }
+    }
+
+    let is_captions = |t: &TranscriptInfo| t.source.as_deref() == Some(TranscriptSource::Captions.id());
+    let mut preferred: HashMap<(String, String), usize> = HashMap::new();
+    for (i, t) in results.iter().enumerate() {
+        preferred
+            .entry(key(t))
+            .and_modify(|best| {
+                if is_captions(&results[*best]) && !is_captions(t) {
+                    *best = i;
+                }
+            })
+            .or_insert(i);
+    }
+
+    let mut i = 0;
+    results.retain(|t| {
+        let keep = preferred[&key(t)] == i;
+        i += 1;
+        keep
+    });
+}
+
+/// Fill in the source from transcript.json's ID if the directory name didn't tell
+fn resolve_source(t: &mut TranscriptInfo) {
+    if t.source.is_some() {
+        return;
+    }
+
+    #[derive(Deserialize)]
+    struct TranscriptId {
+        id: String,
+    }
+
+    let captions = fs::read_to_string(Path::new(&t.path).join("transcript.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<TranscriptId>(&content).ok())
+        .is_some_and(|transcript| transcript.id.starts_with(CAPTIONS_ID_PREFIX));
+    let source = if captions { TranscriptSource::Captions } else { TranscriptSource::Api };
+    t.source = Some(source.id().to_string());
+}
+
+/// Summary fields of transcript.json (word data is skipped, not allocated)
+#[derive(Deserialize)]
+struct TranscriptSummary {
+    text: String,
+    confidence: Option<f64>,
+    language: Option<String>,
+}
+
+fn passes_filter(path: &Path, filter: &TranscriptFilter) -> bool {
+    let Ok(content) = fs::read_to_string(path.join("transcript.json")) else {
+        return false;
+    };
+    let Ok(summary) = serde_json::from_str::<TranscriptSummary>(&content) else {
+        return false;
+    };
+
+    let word_count = summary.text.split_whitespace().count() as i32;
+    filter.matches(word_count, summary.confidence, summary.language.as_deref())
+}
+
+fn find_transcripts_recursive(path: &Path, results: &mut Vec<TranscriptInfo>) -> Result<()> {
+    if !path.is_dir() {
+        return Ok(());
+    }
+
+    let transcript_file = path.join("transcript.json");
+    if transcript_file.exists() {
+        let metadata_file = path.join("metadata.json");
+
+        let dir_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let captions_dir = dir_name.strip_suffix(CAPTIONS_DIR_SUFFIX);
+
+        let mut info = TranscriptInfo {
+            path: path.to_string_lossy().to_string(),
+            video_id: captions_dir.unwrap_or(&dir_name).to_string(),
+            source: captions_dir.map(|_| TranscriptSource::Captions.id().to_string()),
+            title: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+            channel: path
+                .parent()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "Unknown".to_string()),
+            channel_handle: None,
+            platform: path
+                .parent()
+                .and_then(|p| p.parent())
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "unknown".to_string()),
+            duration: None,
+            upload_date: None,
+            url: None,
+            transcribed_by: None,
+            series: None,
+            episode_number: None,
+        };
+
+        if metadata_file.exists()
+            && let Ok(content) = fs::read_to_string(&metadata_file)
+            && let Ok(metadata) = serde_json::from_str::<HashMap<String, serde_json::Value>>(&content)
+        {
+            info.duration = metadata.get("duration").and_then(|v| v.as_i64());
+            info.upload_date = metadata.get("upload_date").and_then(|v| v.as_str()).map(String::from);
+            info.url = metadata.get("url").and_then(|v| v.as_str()).map(String::from);
+            info.channel_handle = metadata.get("uploader_id").and_then(|v| v.as_str()).map(String::from);
+            info.transcribed_by = metadata.get("transcribed_by").and_then(|v| v.as_str()).map(String::from);
+            if let Some(id) = metadata.get("id").and_then(|v| v.as_str()) {
+                info.video_id = id.to_string();
+            }
+            // Also get channel name from metadata if available
+            if let Some(channel_name) = metadata.get("channel").and_then(|v| v.as_str()) {
+                info.channel = channel_name.to_string();
+            }
+            info.series = metadata.get("series").and_then(|v| v.as_str()).map(String::from);
+            info.episode_number = metadata.get("episode_number").and_then(|v| v.as_i64());
+            // Transcripts saved before episodes were detected
+            if info.episode_number.is_none()
+                && let Some(title) = metadata.get("title").and_then(|v| v.as_str())
+                && let Some(episode) = episode::detect(title, &info.channel, info.channel_handle.as_deref())
+            {
+                info.episode_number = Some(episode.number);
+                info.series = info.series.take().or(episode.series);
+            }
+        }
+
+        results.push(info);
+        return Ok(());
+    }
+
+    // Recurse into subdirectories
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            if entry.path().is_dir() {
+                find_transcripts_recursive(&entry.path(), results)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Transcript content
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscriptContent {
+    pub text: Option<String>,
+    pub structured: Option<TranscriptData>,
+}
+
+/// Text and JSON transcript files for a transcript directory or either file's path
+/// (neither needs to exist)
+pub fn transcript_files(path: &Path) -> (PathBuf, PathBuf) {
+    if path.is_dir() {
+        // Prefer .md, fallback to .txt
+        let md_file = path.join("transcript.md");
+        let txt_file = path.join("transcript.txt");
+        let text_file = if md_file.exists() { md_file } else { txt_file };
+        (text_file, path.join("transcript.json"))
+    } else if path.extension().map(|e| e == "md" || e == "txt").unwrap_or(false) {
+        (path.to_path_buf(), path.with_extension("json"))
+    } else {
+        (path.with_extension("md"), path.to_path_buf())
+    }
+}
+
+/// Get transcript content from a path
+pub fn get_transcript(path: &str) -> Result<TranscriptContent> {
+    let path = PathBuf::from(path);
+    let (text_file, json_file) = transcript_files(&path);
+
+    let mut result = TranscriptContent {
+        text: None,
+        structured: None,
+    };
+
+    if text_file.exists() {
+        result.text = Some(fs::read_to_string(&text_file)?);
+    }
+
+    if json_file.exists() {
+        let content = fs::read_to_string(&json_file)?;
+        result.structured = Some(serde_json::from_str(&content)?);
+    }
+
+    if result.text.is_none() && result.structured.is_none() {
+        return Err(Error::FileNotFound(format!(
+            "No transcript found at {}",
+            path.display()
+        )));
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,196 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+
+use super::{TranscriptData, Utterance, Word};
+
+/// Captions carry no speaker information, so everything is attributed to one speaker
+const CAPTION_SPEAKER: &str = "A";
+
+/// Start a new utterance after a pause this long (ms)
+const UTTERANCE_GAP_MS: i64 = 1500;
+
+/// Start a new utterance once the current one has this many words
+const MAX_UTTERANCE_WORDS: usize = 80;
+
+/// YouTube's json3 subtitle format
+#[derive(Debug, Deserialize)]
+struct Json3 {
+    #[serde(default)]
+    events: Vec<Json3Event>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Json3Event {
+    t_start_ms: Option<i64>,
+    d_duration_ms: Option<i64>,
+    #[serde(default)]
+    segs: Vec<Json3Seg>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Json3Seg {
+    #[serde(default)]
+    utf8: String,
+    t_offset_ms: Option<i64>,
+}
+
+/// Convert a subtitle file written by yt-dlp (json3 or WebVTT) into transcript data
+pub fn parse_captions(path: &Path, id: &str, audio_duration: Option<i64>) -> Result<TranscriptData> {
+    let content = std::fs::read_to_string(path)?;
+
+    let words = match path.extension().and_then(|e| e.to_str()) {
+        Some("json3") => json3_words(&content)?,
+        Some("vtt") => vtt_words(&content),
+        _ => {
+            return Err(Error::Transcription(format!(
+                "Unsupported caption format: {}",
+                path.display()
+            )));
+        }
+    };
+
+    if words.is_empty() {
+        return Err(Error::Transcription(format!("Captions are empty: {}", path.display())));
+    }
+
+    let text = words.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" ");
+
+    Ok(TranscriptData {
+        id: id.to_string(),
+        text,
+        utterances: group_utterances(&words),
+        words,
+        confidence: None,
+        audio_duration,
+        chapters: Vec::new(),
+        highlights: Vec::new(),
+        entities: Vec::new(),
+        language: None,
+    })
+}
+
+fn json3_words(content: &str) -> Result<Vec<Word>> {
+    let json3: Json3 = serde_json::from_str(content)?;
+    let mut words = Vec::new();
+
+    for event in json3.events {
+        let Some(event_start) = event.t_start_ms else {
+            continue;
+        };
+        let event_end = event_start + event.d_duration_ms.unwrap_or(0);
+
+        for (i, seg) in event.segs.iter().enumerate() {
+            let start = event_start + seg.t_offset_ms.unwrap_or(0);
+            let end = event
+                .segs
+                .get(i + 1)
+                .and_then(|next| next.t_offset_ms)
+                .map(|offset| event_start + offset)
+                .unwrap_or(event_end)
+                .max(start);
+            push_words(&mut words, &seg.utf8, start, end);
+        }
+    }
+
+    Ok(words)
+}
+
+fn vtt_words(content: &str) -> Vec<Word> {
+    let tag = regex::Regex::new(r"<[^>]*>").unwrap();
+    let mut words = Vec::new();
+    // Auto-generated VTT repeats the previous line at the top of each cue
+    let mut previous_line = String::new();
+
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        let Some((start, end)) = line.split_once("-->") else {
+            continue;
+        };
+        let (Some(start), Some(end)) = (
+            parse_vtt_time(start.trim()),
+            end.split_whitespace().next().and_then(parse_vtt_time),
+        ) else {
+            continue;
+        };
+
+        let mut cue_text = Vec::new();
+        for text_line in lines.by_ref().take_while(|l| !l.trim().is_empty()) {
+            let text = tag.replace_all(text_line, "");
+            let text = text.trim();
+            if text.is_empty() || text == previous_line {
+                continue;
+            }
+            previous_line = text.to_string();
+            cue_text.push(text.to_string());
+        }
+
+        push_words(&mut words, &cue_text.join(" "), start, end);
+    }
+
+    words
+}
+
+/// Parse `HH:MM:SS.mmm` or `MM:SS.mmm` into milliseconds
+fn parse_vtt_time(value: &str) -> Option<i64> {
+    let (clock, millis) = value.split_once('.')?;
+    let mut seconds = 0i64;
+    for part in clock.split(':') {
+        seconds = seconds * 60 + part.parse::<i64>().ok()?;
+    }
+    Some(seconds * 1000 + millis.parse::<i64>().ok()?)
+}
+
+/// Split caption text into words, spreading the time span evenly across them
+fn push_words(words: &mut Vec<Word>, text: &str, start: i64, end: i64) {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    if tokens.is_empty() {
+        return;
+    }
+
+    let step = (end - start) / tokens.len() as i64;
+    for (i, token) in tokens.iter().enumerate() {
+        let word_start = start + step * i as i64;
+        words.push(Word {
+            text: token.to_string(),
+            start: word_start,
+            end: if i + 1 == tokens.len() { end } else { word_start + step },
+            confidence: None,
+            speaker: Some(CAPTION_SPEAKER.to_string()),
+        });
+    }
+}
+
+/// Group words into utterances at pauses, so paragraphs and timestamps stay readable
+fn group_utterances(words: &[Word]) -> Vec<Utterance> {
+    let mut utterances: Vec<Utterance> = Vec::new();
+    let mut current: Vec<&Word> = Vec::new();
+
+    let mut flush = |current: &mut Vec<&Word>| {
+        if let (Some(first), Some(last)) = (current.first(), current.last()) {
+            utterances.push(Utterance {
+                speaker: CAPTION_SPEAKER.to_string(),
+                text: current.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" "),
+                start: first.start,
+                end: last.end,
+                confidence: None,
+            });
+        }
+        current.clear();
+    };
+
+    for word in words {
+        let pause = current.last().is_some_and(|last| word.start - last.end >= UTTERANCE_GAP_MS);
+        if pause || current.len() >= MAX_UTTERANCE_WORDS {
+            flush(&mut current);
+        }
+        current.push(word);
+    }
+    flush(&mut current);
+
+    utterances
+}
@@ -0,0 +1,220 @@
+use std::path::Path;
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::config::deepgram_api_key;
+use crate::error::{Error, Result};
+
+use super::{Hints, TranscriptData, Utterance, Word};
+
+const DEEPGRAM_LISTEN_URL: &str = "https://api.deepgram.com/v1/listen";
+
+/// Query parameters for pre-recorded transcription with diarization
+const LISTEN_PARAMS: [(&str, &str); 3] = [("model", "nova-2"), ("diarize", "true"), ("utterances", "true")];
+
+#[derive(Debug, Serialize)]
+struct UrlRequest<'a> {
+    url: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenResponse {
+    metadata: ListenMetadata,
+    results: ListenResults,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenMetadata {
+    request_id: String,
+    duration: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenResults {
+    channels: Vec<ApiChannel>,
+    utterances: Option<Vec<ApiUtterance>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiChannel {
+    alternatives: Vec<ApiAlternative>,
+    /// Only present with `detect_language`
+    detected_language: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiAlternative {
+    transcript: String,
+    confidence: Option<f64>,
+    #[serde(default)]
+    words: Vec<ApiWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiUtterance {
+    transcript: String,
+    start: f64,
+    end: f64,
+    confidence: Option<f64>,
+    speaker: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiWord {
+    word: String,
+    punctuated_word: Option<String>,
+    start: f64,
+    end: f64,
+    confidence: Option<f64>,
+    speaker: Option<u32>,
+}
+
+/// Deepgram client
+pub struct Deepgram {
+    client: Client,
+    api_key: String,
+}
+
+impl Deepgram {
+    pub fn new() -> Result<Self> {
+        let api_key = deepgram_api_key()
+            .ok_or_else(|| Error::Config("DEEPGRAM_API_KEY not set".to_string()))?;
+
+        // Pre-recorded requests are answered synchronously, so allow for long episodes
+        let client = Client::builder()
+            .timeout(Duration::from_secs(3600))
+            .build()?;
+
+        Ok(Self { client, api_key })
+    }
+
+    /// Transcribe an audio file
+    pub async fn transcribe(&self, audio_path: &Path, hints: &Hints) -> Result<TranscriptData> {
+        let data = tokio::fs::read(audio_path).await?;
+
+        let request = self
+            .client
+            .post(DEEPGRAM_LISTEN_URL)
+            .header("Content-Type", "application/octet-stream")
+            .body(data);
+
+        self.listen(request, hints).await
+    }
+
+    /// Transcribe audio that Deepgram can fetch directly from a public URL
+    pub async fn transcribe_url(&self, audio_url: &str, hints: &Hints) -> Result<TranscriptData> {
+        let request = self
+            .client
+            .post(DEEPGRAM_LISTEN_URL)
+            .json(&UrlRequest { url: audio_url });
+
+        self.listen(request, hints).await
+    }
+
+    async fn listen(&self, request: reqwest::RequestBuilder, hints: &Hints) -> Result<TranscriptData> {
+        let formatting = &hints.formatting;
+        let mut request = request.query(&LISTEN_PARAMS).query(&[
+            ("punctuate", formatting.punctuate),
+            ("smart_format", formatting.format_text),
+            ("filler_words", formatting.disfluencies),
+            ("profanity_filter", formatting.filter_profanity),
+        ]);
+        // Boosted words are Deepgram's `keywords`, one parameter each
+        for word in &hints.word_boost {
+            request = request.query(&[("keywords", word)]);
+        }
+        if hints.detect_language {
+            request = request.query(&[("detect_language", "true")]);
+        } else if let Some(language) = &hints.language {
+            request = request.query(&[("language", language)]);
+        }
+
+        let response = request
+            .header("Authorization", format!("Token {}", self.api_key))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::Transcription(format!(
+                "Deepgram request failed ({}): {}",
+                status, text
+            )));
+        }
+
+        let listen: ListenResponse = response.json().await?;
+        let mut transcript = into_transcript(listen);
+        if transcript.language.is_none() && !hints.detect_language {
+            // Deepgram transcribes English unless told otherwise
+            transcript.language = Some(hints.language.clone().unwrap_or_else(|| "en".to_string()));
+        }
+        Ok(transcript)
+    }
+}
+
+/// Map a Deepgram response onto the AssemblyAI-shaped transcript (ms timestamps, letter speakers)
+fn into_transcript(listen: ListenResponse) -> TranscriptData {
+    let channel = listen.results.channels.into_iter().next();
+    let language = channel.as_ref().and_then(|c| c.detected_language.clone());
+    let alternative = channel.and_then(|c| c.alternatives.into_iter().next());
+
+    let utterances = listen
+        .results
+        .utterances
+        .unwrap_or_default()
+        .into_iter()
+        .map(|u| Utterance {
+            speaker: speaker_label(u.speaker.unwrap_or(0)),
+            text: u.transcript,
+            start: to_ms(u.start),
+            end: to_ms(u.end),
+            confidence: u.confidence,
+        })
+        .collect();
+
+    let (text, confidence, words) = match alternative {
+        Some(alt) => {
+            let words = alt
+                .words
+                .into_iter()
+                .map(|w| Word {
+                    text: w.punctuated_word.unwrap_or(w.word),
+                    start: to_ms(w.start),
+                    end: to_ms(w.end),
+                    confidence: w.confidence,
+                    speaker: w.speaker.map(speaker_label),
+                })
+                .collect();
+            (alt.transcript, alt.confidence, words)
+        }
+        None => (String::new(), None, Vec::new()),
+    };
+
+    TranscriptData {
+        id: listen.metadata.request_id,
+        text,
+        utterances,
+        words,
+        confidence,
+        audio_duration: listen.metadata.duration.map(|d| d.round() as i64),
+        chapters: Vec::new(),
+        highlights: Vec::new(),
+        entities: Vec::new(),
+        language,
+    }
+}
+
+fn to_ms(seconds: f64) -> i64 {
+    (seconds * 1000.0).round() as i64
+}
+
+/// Speaker 0, 1, ... as "A", "B", ... to match AssemblyAI's labels
+fn speaker_label(speaker: u32) -> String {
+    match char::from_u32('A' as u32 + speaker) {
+        Some(c) if speaker < 26 => c.to_string(),
+        _ => (speaker + 1).to_string(),
+    }
+}
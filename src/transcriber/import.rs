@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+
+use super::{TranscriptData, Utterance, Word};
+
+/// Extensions `parse_import` understands, best first when one recording has several
+pub const IMPORT_EXTENSIONS: &[&str] = &["json", "srt", "vtt", "txt"];
+
+/// Speaker of transcripts that don't label speakers
+const DEFAULT_SPEAKER: &str = "A";
+
+/// Whisper / whisperX JSON output
+#[derive(Debug, Deserialize)]
+struct WhisperJson {
+    segments: Vec<WhisperSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperSegment {
+    start: f64,
+    end: f64,
+    text: String,
+    speaker: Option<String>,
+    #[serde(default)]
+    words: Vec<WhisperWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WhisperWord {
+    word: String,
+    /// Missing for tokens whisperX couldn't align (e.g. numerals)
+    start: Option<f64>,
+    end: Option<f64>,
+    #[serde(alias = "probability")]
+    score: Option<f64>,
+    speaker: Option<String>,
+}
+
+/// Convert another tool's transcript into transcript data
+///
+/// - `.json`: Whisper or whisperX output (segments, plus words and speakers when aligned
+///   and diarized)
+/// - `.srt` / `.vtt`: subtitles from yt-whisper, whisperX or Descript, one utterance per
+///   cue; leading `Name:` or `[SPEAKER_00]:` labels set the speaker
+/// - `.txt`: Descript's transcript export, lines like `[00:01:02] Name: text`
+///
+/// Labels such as `SPEAKER_00` become A, B, ... in order of appearance; real names are kept.
+pub fn parse_import(path: &Path, id: &str) -> Result<TranscriptData> {
+    let content = std::fs::read_to_string(path)?;
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+
+    let mut speakers = SpeakerNames::default();
+    let (utterances, words) = match extension.as_str() {
+        "json" => whisper(&content, &mut speakers)?,
+        "srt" | "vtt" => (subtitle_cues(&content, &mut speakers), Vec::new()),
+        "txt" => (timestamped_lines(&content, &mut speakers), Vec::new()),
+        _ => {
+            return Err(Error::Transcription(format!("Unsupported transcript format: {}", path.display())));
+        }
+    };
+
+    if utterances.is_empty() {
+        return Err(Error::Transcription(format!("No timestamped text found in {}", path.display())));
+    }
+
+    let text = utterances.iter().map(|u| u.text.as_str()).collect::<Vec<_>>().join(" ");
+    let confidences: Vec<f64> = words.iter().filter_map(|w| w.confidence).collect();
+    let confidence = (!confidences.is_empty()).then(|| confidences.iter().sum::<f64>() / confidences.len() as f64);
+    let audio_duration = utterances.iter().map(|u| u.end).max().map(|end| (end + 999) / 1000);
+
+    Ok(TranscriptData {
+        id: id.to_string(),
+        text,
+        utterances,
+        words,
+        confidence,
+        audio_duration,
+        chapters: Vec::new(),
+        highlights: Vec::new(),
+        entities: Vec::new(),
+        language: None,
+    })
+}
+
+/// Maps tool-generated speaker labels to letters, keeping names people gave
+#[derive(Default)]
+struct SpeakerNames {
+    letters: HashMap<String, String>,
+}
+
+impl SpeakerNames {
+    fn name(&mut self, label: Option<&str>) -> String {
+        let Some(label) = label.map(str::trim).filter(|l| !l.is_empty()) else {
+            return DEFAULT_SPEAKER.to_string();
+        };
+        if !label.to_uppercase().starts_with("SPEAKER") {
+            return label.to_string();
+        }
+        let next = self.letters.len();
+        self.letters
+            .entry(label.to_string())
+            .or_insert_with(|| speaker_letter(next))
+            .clone()
+    }
+}
+
+/// A, B, ... Z, then AA, AB, ...
+fn speaker_letter(index: usize) -> String {
+    let letter = |i: usize| char::from(b'A' + (i % 26) as u8);
+    if index < 26 {
+        letter(index).to_string()
+    } else {
+        format!("{}{}", letter(index / 26 - 1), letter(index))
+    }
+}
+
+fn whisper(content: &str, speakers: &mut SpeakerNames) -> Result<(Vec<Utterance>, Vec<Word>)> {
+    let json: WhisperJson = serde_json::from_str(content)?;
+    let mut utterances = Vec::new();
+    let mut words = Vec::new();
+
+    for segment in json.segments {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let start = seconds_to_ms(segment.start);
+        let end = seconds_to_ms(segment.end);
+        let speaker = speakers.name(segment.speaker.as_deref());
+
+        let mut previous_end = start;
+        let mut scores = Vec::new();
+        for word in &segment.words {
+            let word_start = word.start.map(seconds_to_ms).unwrap_or(previous_end);
+            let word_end = word.end.map(seconds_to_ms).unwrap_or(word_start).max(word_start);
+            previous_end = word_end;
+            scores.extend(word.score);
+            words.push(Word {
+                text: word.word.trim().to_string(),
+                start: word_start,
+                end: word_end,
+                confidence: word.score,
+                speaker: Some(match &word.speaker {
+                    Some(label) => speakers.name(Some(label)),
+                    None => speaker.clone(),
+                }),
+            });
+        }
+
+        utterances.push(Utterance {
+            speaker,
+            text: text.to_string(),
+            start,
+            end,
+            confidence: (!scores.is_empty()).then(|| scores.iter().sum::<f64>() / scores.len() as f64),
+        });
+    }
+
+    Ok((utterances, words))
+}
+
+/// Text with its start and end in milliseconds, before speakers are split off
+struct Cue {
+    start: i64,
+    end: i64,
+    text: String,
+}
+
+fn subtitle_cues(content: &str, speakers: &mut SpeakerNames) -> Vec<Utterance> {
+    let tag = Regex::new(r"<[^>]*>").unwrap();
+    let mut cues = Vec::new();
+
+    let mut lines = content.lines();
+    while let Some(line) = lines.next() {
+        let Some((start, end)) = line.split_once("-->") else {
+            continue;
+        };
+        let (Some(start), Some(end)) = (
+            parse_clock(start.trim()),
+            end.split_whitespace().next().and_then(parse_clock),
+        ) else {
+            continue;
+        };
+
+        let text: Vec<String> = lines
+            .by_ref()
+            .take_while(|l| !l.trim().is_empty())
+            .map(|l| tag.replace_all(l, "").trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect();
+        cues.push(Cue { start, end: end.max(start), text: text.join(" ") });
+    }
+
+    into_utterances(cues, speakers)
+}
+
+fn timestamped_lines(content: &str, speakers: &mut SpeakerNames) -> Vec<Utterance> {
+    let line_pattern = Regex::new(r"^\[(\d{1,2}(?::\d{2}){1,2}(?:[.,]\d+)?)\]\s*(.*)$").unwrap();
+    let mut cues: Vec<Cue> = Vec::new();
+
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let Some(captures) = line_pattern.captures(line) else {
+            // Continuation of the previous paragraph
+            if let Some(last) = cues.last_mut() {
+                last.text.push(' ');
+                last.text.push_str(line);
+            }
+            continue;
+        };
+        let Some(start) = parse_clock(&captures[1]) else {
+            continue;
+        };
+        // A paragraph lasts until the next one starts
+        if let Some(previous) = cues.last_mut() {
+            previous.end = start.max(previous.start);
+        }
+        cues.push(Cue { start, end: start, text: captures[2].to_string() });
+    }
+
+    into_utterances(cues, speakers)
+}
+
+/// One utterance per cue, with `Name: text` or `[SPEAKER_00]: text` prefixes taken as
+/// speakers when most cues have one (so a stray "Note: ..." in unlabelled subtitles isn't)
+fn into_utterances(cues: Vec<Cue>, speakers: &mut SpeakerNames) -> Vec<Utterance> {
+    let prefix = Regex::new(
+        r"^\[?(SPEAKER_\d+|\p{Lu}[\p{L}'.-]*(?: (?:\p{Lu}[\p{L}'.-]*|\d+)){0,2})\]?:\s+(.*)$",
+    )
+    .unwrap();
+    let labelled = cues.iter().filter(|c| prefix.is_match(c.text.trim())).count();
+    let use_labels = labelled > 0 && labelled * 2 >= cues.len();
+
+    cues.into_iter()
+        .filter_map(|cue| {
+            let text = cue.text.trim();
+            let (label, text) = match prefix.captures(text).filter(|_| use_labels) {
+                Some(captures) => (Some(captures[1].to_string()), captures[2].trim().to_string()),
+                None => (None, text.to_string()),
+            };
+            (!text.is_empty()).then(|| Utterance {
+                speaker: speakers.name(label.as_deref()),
+                text,
+                start: cue.start,
+                end: cue.end,
+                confidence: None,
+            })
+        })
+        .collect()
+}
+
+/// `HH:MM:SS,mmm`, `HH:MM:SS.mmm`, `MM:SS.mmm` or without the fraction, in milliseconds
+fn parse_clock(value: &str) -> Option<i64> {
+    let (clock, fraction) = value.split_once([',', '.']).unwrap_or((value, ""));
+    let mut seconds = 0i64;
+    for part in clock.split(':') {
+        seconds = seconds.checked_mul(60)?.checked_add(part.parse::<i64>().ok()?)?;
+    }
+    if !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    // Only milliseconds are kept: "5" is 500, "123456" is 123
+    let millis: String = fraction.chars().chain("000".chars()).take(3).collect();
+    seconds.checked_mul(1000)?.checked_add(millis.parse::<i64>().ok()?)
+}
+
+fn seconds_to_ms(seconds: f64) -> i64 {
+    (seconds * 1000.0).round() as i64
+}
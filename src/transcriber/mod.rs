@@ -0,0 +1,952 @@
+mod captions;
+mod deepgram;
+mod import;
+mod sentences;
+mod spelling;
+mod subtitles;
+
+use std::path::Path;
+use std::time::Duration;
+
+use clap::ValueEnum;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{assemblyai_api_key, settings, PiiSubstitution, SpeechModel, TranscriptionConfig};
+use crate::error::{Error, Result};
+
+pub use captions::parse_captions;
+pub use deepgram::Deepgram;
+pub use import::{parse_import, IMPORT_EXTENSIONS};
+pub use sentences::{split_sentences, Sentence};
+pub use spelling::SpellingMap;
+pub use subtitles::{format_srt, format_ttml, format_vtt};
+
+const ASSEMBLYAI_BASE_URL: &str = "https://api.assemblyai.com/v2";
+
+/// Transcription service, selectable per invocation (default set by `backend` in config.toml)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    #[default]
+    #[value(name = "assemblyai")]
+    AssemblyAI,
+    Deepgram,
+}
+
+impl Backend {
+    /// Name as accepted by `--backend` and config.toml
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::AssemblyAI => "assemblyai",
+            Self::Deepgram => "deepgram",
+        }
+    }
+
+    /// Pay-as-you-go list price in USD per hour of audio with `model` (AssemblyAI's
+    /// default when `None`), for cost estimates
+    pub fn price_per_hour(self, model: Option<SpeechModel>) -> f64 {
+        match (self, model) {
+            (Self::AssemblyAI, Some(SpeechModel::Nano)) => 0.12,
+            (Self::AssemblyAI, Some(SpeechModel::Universal)) => 0.15,
+            (Self::AssemblyAI, Some(SpeechModel::Slam1)) => 0.27,
+            (Self::AssemblyAI, Some(SpeechModel::Best) | None) => 0.37,
+            (Self::Deepgram, _) => 0.26,
+        }
+    }
+}
+
+/// Transcript IDs of imported captions start with this, followed by the video ID
+pub const CAPTIONS_ID_PREFIX: &str = "captions-";
+
+/// Where a transcript's text came from; a video can have one transcript of each
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TranscriptSource {
+    /// Platform captions imported with --use-captions
+    Captions,
+    /// A transcription service (AssemblyAI or Deepgram)
+    Api,
+}
+
+impl TranscriptSource {
+    /// Name as stored in the database and accepted by `--source`
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Captions => "captions",
+            Self::Api => "api",
+        }
+    }
+
+    pub fn of(data: &TranscriptData) -> Self {
+        if data.id.starts_with(CAPTIONS_ID_PREFIX) {
+            Self::Captions
+        } else {
+            Self::Api
+        }
+    }
+}
+
+/// A client for the selected transcription backend
+pub struct Transcriber {
+    service: Service,
+    hints: Hints,
+}
+
+/// Options sent along with each transcription request
+#[derive(Debug, Clone)]
+pub struct Hints {
+    /// Spoken language; `None` keeps the service default (English)
+    pub language: Option<String>,
+    /// Let the service work out the language instead (overrides `language`)
+    pub detect_language: bool,
+    /// Number of speakers to diarize (AssemblyAI only)
+    pub speakers: Option<u32>,
+    /// Names and jargon to favour (AssemblyAI `word_boost`, Deepgram `keywords`)
+    pub word_boost: Vec<String>,
+    pub formatting: TranscriptionConfig,
+}
+
+/// Backend-specific client
+enum Service {
+    AssemblyAI(AssemblyAI),
+    Deepgram(Deepgram),
+}
+
+impl Transcriber {
+    /// Create a client, failing early if the backend's API key is missing
+    ///
+    /// The language comes from `language` in config.toml (or `--language`), unless
+    /// `detect_language` (or `--detect-language`) leaves it to the service.
+    pub fn new(backend: Backend) -> Result<Self> {
+        if backend == Backend::Deepgram && !settings().transcription.redact_pii.is_empty() {
+            return Err(Error::Config(
+                "PII redaction (--redact-pii / [transcription] redact_pii) needs the AssemblyAI backend".to_string(),
+            ));
+        }
+        let service = match backend {
+            Backend::AssemblyAI => Service::AssemblyAI(AssemblyAI::new()?),
+            Backend::Deepgram => Service::Deepgram(Deepgram::new()?),
+        };
+        Ok(Self {
+            service,
+            hints: Hints {
+                language: settings().language.clone().filter(|_| !settings().detect_language),
+                detect_language: settings().detect_language,
+                speakers: None,
+                word_boost: Vec::new(),
+                formatting: settings().transcription.clone(),
+            },
+        })
+    }
+
+    /// Display name for progress output
+    pub fn name(&self) -> &'static str {
+        match self.service {
+            Service::AssemblyAI(_) => "AssemblyAI",
+            Service::Deepgram(_) => "Deepgram",
+        }
+    }
+
+    /// Language code audio is transcribed in, if one was requested
+    pub fn language(&self) -> Option<&str> {
+        self.hints.language.as_deref()
+    }
+
+    /// Whether the service detects the language of each recording
+    pub fn detects_language(&self) -> bool {
+        self.hints.detect_language
+    }
+
+    /// Transcribe in another language from now on
+    pub fn set_language(&mut self, language: Option<String>) {
+        self.hints.language = language;
+        self.hints.detect_language = false;
+    }
+
+    /// Expect this many speakers from now on (`None` lets the service decide)
+    pub fn set_speakers(&mut self, speakers: Option<u32>) {
+        self.hints.speakers = speakers;
+    }
+
+    /// Favour these words from now on
+    pub fn set_word_boost(&mut self, words: Vec<String>) {
+        self.hints.word_boost = words;
+    }
+
+    /// List price per audio hour of the service and model in use
+    pub fn price_per_hour(&self) -> f64 {
+        self.backend().price_per_hour(self.hints.formatting.speech_model)
+    }
+
+    /// Backend this client talks to
+    pub fn backend(&self) -> Backend {
+        match self.service {
+            Service::AssemblyAI(_) => Backend::AssemblyAI,
+            Service::Deepgram(_) => Backend::Deepgram,
+        }
+    }
+
+    /// Hand audio to the service without waiting for asynchronous (AssemblyAI) jobs to finish
+    pub async fn submit(&self, source: AudioSource<'_>) -> Result<Submission> {
+        let hints = &self.hints;
+        Ok(match (&self.service, source) {
+            (Service::AssemblyAI(client), AudioSource::File(path)) => {
+                Submission::Pending(client.submit(path, hints).await?)
+            }
+            (Service::AssemblyAI(client), AudioSource::Url(url)) => {
+                Submission::Pending(client.submit_url(url, hints).await?)
+            }
+            (Service::Deepgram(client), source) => {
+                if let Some(speakers) = hints.speakers {
+                    eprintln!("Note: Deepgram takes no speaker count, ignoring the hint of {}", speakers);
+                }
+                if hints.formatting.speech_model.is_some() {
+                    eprintln!("Note: the speech model is an AssemblyAI setting, Deepgram uses its default");
+                }
+                if hints.formatting.multichannel {
+                    eprintln!("Note: multichannel transcription needs AssemblyAI, Deepgram diarizes instead");
+                }
+                if hints.formatting.chapters {
+                    eprintln!("Note: Deepgram doesn't detect chapters, transcribing without them");
+                }
+                if hints.formatting.highlights {
+                    eprintln!("Note: Deepgram doesn't detect key phrases, transcribing without them");
+                }
+                if hints.formatting.entities {
+                    eprintln!("Note: Deepgram doesn't detect entities, transcribing without them");
+                }
+                Submission::Done(match source {
+                    AudioSource::File(path) => client.transcribe(path, hints).await?,
+                    AudioSource::Url(url) => client.transcribe_url(url, hints).await?,
+                })
+            }
+        })
+    }
+
+    /// Wait for a submitted job to finish (also one submitted by an earlier, interrupted run)
+    pub async fn wait(&self, transcript_id: &str) -> Result<TranscriptData> {
+        match &self.service {
+            Service::AssemblyAI(client) => client.poll_transcript(transcript_id).await,
+            Service::Deepgram(_) => Err(Error::Transcription(
+                "Deepgram answers synchronously; there is no job to wait for".to_string(),
+            )),
+        }
+    }
+}
+
+/// Audio handed to a transcription service
+#[derive(Debug, Clone, Copy)]
+pub enum AudioSource<'a> {
+    /// Local file, uploaded first
+    File(&'a Path),
+    /// Public URL the service fetches itself
+    Url(&'a str),
+}
+
+/// Outcome of `Transcriber::submit`
+pub enum Submission {
+    /// Job accepted (transcript ID); finish it with `Transcriber::wait`
+    Pending(String),
+    /// The service answered straight away
+    Done(TranscriptData),
+}
+
+/// Utterance from speaker diarization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Utterance {
+    pub speaker: String,
+    pub text: String,
+    pub start: i64,
+    pub end: i64,
+    pub confidence: Option<f64>,
+}
+
+/// Word-level data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Word {
+    pub text: String,
+    pub start: i64,
+    pub end: i64,
+    pub confidence: Option<f64>,
+    pub speaker: Option<String>,
+}
+
+/// Full transcript data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranscriptData {
+    pub id: String,
+    pub text: String,
+    pub utterances: Vec<Utterance>,
+    pub words: Vec<Word>,
+    pub confidence: Option<f64>,
+    pub audio_duration: Option<i64>,
+    /// AssemblyAI `auto_chapters`, only present when requested
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chapters: Vec<Chapter>,
+    /// AssemblyAI `auto_highlights` key phrases, only present when requested
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<Highlight>,
+    /// AssemblyAI `entity_detection` results, only present when requested
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entities: Vec<Entity>,
+    /// Language code the transcript is in (detected, requested or of the captions);
+    /// missing in transcripts saved before languages were recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+/// Seconds of audio after the last transcribed word that count as a missing tail, if they
+/// are also at least `MISSING_TAIL_SHARE` of the recording
+const MISSING_TAIL_MIN_SECONDS: i64 = 120;
+const MISSING_TAIL_SHARE: f64 = 0.05;
+
+impl TranscriptData {
+    /// Seconds at the end of the recording that the transcript doesn't reach, when that
+    /// looks like a truncated download or transcription rather than an outro
+    ///
+    /// The recording is as long as the longer of `audio_duration` and `length` (the
+    /// platform's duration, in seconds). Transcripts without any words are not judged.
+    pub fn missing_tail(&self, length: Option<i64>) -> Option<i64> {
+        let length = self.audio_duration.max(length)?;
+        let last_end = self.utterances.iter().map(|u| u.end).chain(self.words.iter().map(|w| w.end)).max()?;
+        let missing = length - last_end / 1000;
+        (missing >= MISSING_TAIL_MIN_SECONDS && missing as f64 >= length as f64 * MISSING_TAIL_SHARE)
+            .then_some(missing)
+    }
+}
+
+/// Chapter detected by the service, times in milliseconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Chapter {
+    pub headline: String,
+    /// A few words naming the topic
+    pub gist: String,
+    pub summary: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Key phrase detected by the service, with every moment it was said
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Highlight {
+    pub text: String,
+    /// Times the phrase occurs
+    pub count: u32,
+    /// Relevance from 0 to 1
+    pub rank: f64,
+    pub timestamps: Vec<Span>,
+}
+
+/// Person, organization, location, ... named in the recording, times in milliseconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entity {
+    /// e.g. `person_name`, `organization`, `location`
+    pub entity_type: String,
+    pub text: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// Start and end of a moment in the recording, in milliseconds
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Span {
+    pub start: i64,
+    pub end: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadResponse {
+    upload_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct TranscriptRequest {
+    audio_url: String,
+    speaker_labels: bool,
+    punctuate: bool,
+    format_text: bool,
+    disfluencies: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    filter_profanity: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language_code: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    language_detection: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speakers_expected: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    multichannel: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speech_model: Option<SpeechModel>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    word_boost: Vec<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    auto_chapters: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    auto_highlights: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    entity_detection: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    redact_pii: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    redact_pii_policies: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    redact_pii_sub: Option<PiiSubstitution>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptResponse {
+    id: String,
+    status: String,
+    text: Option<String>,
+    utterances: Option<Vec<ApiUtterance>>,
+    words: Option<Vec<ApiWord>>,
+    confidence: Option<f64>,
+    audio_duration: Option<i64>,
+    chapters: Option<Vec<Chapter>>,
+    auto_highlights_result: Option<HighlightsResult>,
+    entities: Option<Vec<Entity>>,
+    language_code: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HighlightsResult {
+    /// Left out when highlighting found nothing (`"status": "unavailable"`)
+    #[serde(default)]
+    results: Vec<Highlight>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiUtterance {
+    /// Missing from multichannel transcripts, which have `channel` instead
+    speaker: Option<String>,
+    channel: Option<String>,
+    text: String,
+    start: i64,
+    end: i64,
+    confidence: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiWord {
+    text: String,
+    start: i64,
+    end: i64,
+    confidence: Option<f64>,
+    speaker: Option<String>,
+    channel: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptListResponse {
+    transcripts: Vec<TranscriptListItem>,
+}
+
+/// Entry of the account's transcript history, as returned by the listing endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct TranscriptListItem {
+    pub id: String,
+    pub status: String,
+    pub created: Option<String>,
+}
+
+/// A job's progress as AssemblyAI reports it, without its transcript
+#[derive(Debug, Clone, Deserialize)]
+pub struct JobStatus {
+    pub id: String,
+    /// `queued`, `processing`, `completed` or `error`
+    pub status: String,
+    pub error: Option<String>,
+    pub audio_duration: Option<i64>,
+}
+
+/// AssemblyAI client
+pub struct AssemblyAI {
+    client: Client,
+    api_key: String,
+}
+
+impl AssemblyAI {
+    pub fn new() -> Result<Self> {
+        let api_key = assemblyai_api_key().ok_or(Error::ApiKeyMissing)?;
+        Self::with_key(api_key)
+    }
+
+    /// Create a client for an explicit API key
+    pub fn with_key(api_key: String) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(300))
+            .build()?;
+
+        Ok(Self { client, api_key })
+    }
+
+    /// Check the key against the API with a one-item transcript listing (free, no job created)
+    ///
+    /// Returns `Ok(false)` when the key is rejected and an error when the API can't be reached.
+    pub async fn verify_key(&self) -> Result<bool> {
+        let response = self
+            .client
+            .get(format!("{}/transcript", ASSEMBLYAI_BASE_URL))
+            .query(&[("limit", "1")])
+            .header("Authorization", &self.api_key)
+            .timeout(Duration::from_secs(15))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Ok(false);
+        }
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::Transcription(format!(
+                "Key check failed ({}): {}",
+                status, text
+            )));
+        }
+
+        Ok(true)
+    }
+
+    /// List the account's most recent transcription jobs (newest first, at most 200)
+    ///
+    /// The API exposes no balance or usage totals, so job history is all there is to show.
+    pub async fn recent_transcripts(&self, limit: u32) -> Result<Vec<TranscriptListItem>> {
+        let response = self
+            .client
+            .get(format!("{}/transcript", ASSEMBLYAI_BASE_URL))
+            .query(&[("limit", limit.min(200).to_string())])
+            .header("Authorization", &self.api_key)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::Transcription(format!(
+                "Listing transcripts failed ({}): {}",
+                status, text
+            )));
+        }
+
+        let list: TranscriptListResponse = response.json().await?;
+        Ok(list.transcripts)
+    }
+
+    /// Look up a job once, without waiting for it to finish
+    pub async fn job_status(&self, transcript_id: &str) -> Result<JobStatus> {
+        let response = self
+            .client
+            .get(format!("{}/transcript/{}", ASSEMBLYAI_BASE_URL, transcript_id))
+            .header("Authorization", &self.api_key)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::Transcription(format!(
+                "Status check failed ({}): {}",
+                status, text
+            )));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Delete a job and its transcript from AssemblyAI
+    ///
+    /// AssemblyAI refuses this while the job is still queued or processing; it can't be
+    /// stopped then, only deleted once it finishes.
+    pub async fn delete_transcript(&self, transcript_id: &str) -> Result<()> {
+        let response = self
+            .client
+            .delete(format!("{}/transcript/{}", ASSEMBLYAI_BASE_URL, transcript_id))
+            .header("Authorization", &self.api_key)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::Transcription(format!(
+                "Delete failed ({}): {}",
+                status, text
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Upload an audio file and return the upload URL
+    async fn upload_file(&self, path: &Path) -> Result<String> {
+        let data = tokio::fs::read(path).await?;
+
+        let response = self
+            .client
+            .post(format!("{}/upload", ASSEMBLYAI_BASE_URL))
+            .header("Authorization", &self.api_key)
+            .header("Content-Type", "application/octet-stream")
+            .body(data)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::Transcription(format!(
+                "Upload failed ({}): {}",
+                status, text
+            )));
+        }
+
+        let upload: UploadResponse = response.json().await?;
+        Ok(upload.upload_url)
+    }
+
+    /// Start a transcription job
+    async fn create_transcript(&self, audio_url: &str, hints: &Hints) -> Result<String> {
+        let request = TranscriptRequest {
+            audio_url: audio_url.to_string(),
+            // Each channel is one speaker; diarizing within channels would split them further
+            speaker_labels: !hints.formatting.multichannel,
+            punctuate: hints.formatting.punctuate,
+            format_text: hints.formatting.format_text,
+            disfluencies: hints.formatting.disfluencies,
+            filter_profanity: hints.formatting.filter_profanity,
+            language_code: hints.language.clone().filter(|_| !hints.detect_language),
+            language_detection: hints.detect_language,
+            speakers_expected: hints.speakers.filter(|_| !hints.formatting.multichannel),
+            multichannel: hints.formatting.multichannel,
+            speech_model: hints.formatting.speech_model,
+            word_boost: hints.word_boost.clone(),
+            auto_chapters: hints.formatting.chapters,
+            auto_highlights: hints.formatting.highlights,
+            entity_detection: hints.formatting.entities,
+            redact_pii: !hints.formatting.redact_pii.is_empty(),
+            redact_pii_policies: hints.formatting.redact_pii.clone(),
+            redact_pii_sub: (!hints.formatting.redact_pii.is_empty()).then_some(hints.formatting.redact_pii_sub),
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/transcript", ASSEMBLYAI_BASE_URL))
+            .header("Authorization", &self.api_key)
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(Error::Transcription(format!(
+                "Create transcript failed ({}): {}",
+                status, text
+            )));
+        }
+
+        let transcript: TranscriptResponse = response.json().await?;
+        Ok(transcript.id)
+    }
+
+    /// Poll for transcript completion
+    pub async fn poll_transcript(&self, transcript_id: &str) -> Result<TranscriptData> {
+        loop {
+            let response = self
+                .client
+                .get(format!("{}/transcript/{}", ASSEMBLYAI_BASE_URL, transcript_id))
+                .header("Authorization", &self.api_key)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                let text = response.text().await.unwrap_or_default();
+                return Err(Error::Transcription(format!(
+                    "Poll failed ({}): {}",
+                    status, text
+                )));
+            }
+
+            let transcript: TranscriptResponse = response.json().await?;
+
+            match transcript.status.as_str() {
+                "completed" => {
+                    let utterances = transcript
+                        .utterances
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|u| Utterance {
+                            speaker: channel_speaker(u.channel.as_deref())
+                                .or(u.speaker)
+                                .unwrap_or_else(|| "A".to_string()),
+                            text: u.text,
+                            start: u.start,
+                            end: u.end,
+                            confidence: u.confidence,
+                        })
+                        .collect();
+
+                    let words = transcript
+                        .words
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|w| Word {
+                            text: w.text,
+                            start: w.start,
+                            end: w.end,
+                            confidence: w.confidence,
+                            speaker: channel_speaker(w.channel.as_deref()).or(w.speaker),
+                        })
+                        .collect();
+
+                    return Ok(TranscriptData {
+                        id: transcript.id,
+                        text: transcript.text.unwrap_or_default(),
+                        utterances,
+                        words,
+                        confidence: transcript.confidence,
+                        audio_duration: transcript.audio_duration,
+                        chapters: transcript.chapters.unwrap_or_default(),
+                        highlights: transcript
+                            .auto_highlights_result
+                            .map(|r| r.results)
+                            .unwrap_or_default(),
+                        entities: transcript.entities.unwrap_or_default(),
+                        language: transcript.language_code,
+                    });
+                }
+                "error" => {
+                    return Err(Error::Transcription(
+                        transcript.error.unwrap_or_else(|| "Unknown error".to_string()),
+                    ));
+                }
+                _ => {
+                    // Still processing, wait and retry
+                    tokio::time::sleep(Duration::from_secs(3)).await;
+                }
+            }
+        }
+    }
+
+    /// Upload an audio file and start its transcription, returning the transcript ID
+    pub async fn submit(&self, audio_path: &Path, hints: &Hints) -> Result<String> {
+        let upload_url = self.upload_file(audio_path).await?;
+        self.create_transcript(&upload_url, hints).await
+    }
+
+    /// Start transcribing audio that AssemblyAI can fetch directly from a public URL
+    pub async fn submit_url(&self, audio_url: &str, hints: &Hints) -> Result<String> {
+        self.create_transcript(audio_url, hints).await
+    }
+}
+
+/// Format timestamp from milliseconds to MM:SS or HH:MM:SS
+pub fn format_timestamp(ms: i64) -> String {
+    let seconds = ms / 1000;
+    let minutes = seconds / 60;
+    let hours = minutes / 60;
+
+    if hours > 0 {
+        format!("{:02}:{:02}:{:02}", hours, minutes % 60, seconds % 60)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds % 60)
+    }
+}
+
+/// Titled section of a transcript (chapter, game segment), start in milliseconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Section {
+    pub title: String,
+    pub start: i64,
+}
+
+/// Speaker label for a multichannel transcript's audio channel: channel 1 is A, 2 is B, ...
+fn channel_speaker(channel: Option<&str>) -> Option<String> {
+    let index = channel?.parse::<u8>().ok()?.checked_sub(1).filter(|i| *i < 26)?;
+    Some(char::from(b'A' + index).to_string())
+}
+
+/// Primary language subtags of languages written right to left
+const RTL_LANGUAGES: &[&str] = &["ar", "ckb", "dv", "fa", "he", "iw", "ps", "sd", "ug", "ur", "yi"];
+
+/// Whether `language` (e.g. "ar" or "he_il") is written right to left
+pub fn is_rtl(language: &str) -> bool {
+    let primary = language.split(['_', '-']).next().unwrap_or_default().to_lowercase();
+    RTL_LANGUAGES.contains(&primary.as_str())
+}
+
+/// `language` as an HTML `lang` value ("he_il" becomes "he-IL")
+pub fn html_lang(language: &str) -> String {
+    let mut parts = language.split(['_', '-']);
+    let mut lang = parts.next().unwrap_or_default().to_lowercase();
+    for part in parts {
+        lang.push('-');
+        lang.push_str(&if part.len() == 2 { part.to_uppercase() } else { part.to_string() });
+    }
+    lang
+}
+
+/// Wrap markdown in a right-to-left language in a `<div dir="rtl" lang="...">` so
+/// renderers lay it out correctly; markdown in other languages is returned as is
+///
+/// The blank lines keep the content markdown rather than raw HTML (CommonMark, GFM).
+pub fn mark_direction(markdown: &str, language: Option<&str>) -> String {
+    match language.filter(|l| is_rtl(l)) {
+        Some(language) => format!(
+            "<div dir=\"rtl\" lang=\"{}\">\n\n{}\n\n</div>",
+            html_lang(language),
+            markdown.trim_end()
+        ),
+        None => markdown.to_string(),
+    }
+}
+
+/// Format transcript as markdown with speaker labels
+/// Batches consecutive utterances from the same speaker into paragraphs
+pub fn format_transcript_markdown(data: &TranscriptData) -> String {
+    format_transcript_markdown_sections(data, &[])
+}
+
+/// Format transcript as markdown, inserting a heading at the start of each section
+/// Paragraphs are split at section boundaries even when the speaker doesn't change
+pub fn format_transcript_markdown_sections(data: &TranscriptData, sections: &[Section]) -> String {
+    let mut output = String::new();
+
+    // Table of contents from the service's chapters
+    if !data.chapters.is_empty() {
+        output.push_str("## Chapters\n\n");
+        for chapter in &data.chapters {
+            output.push_str(&format!("- [{}] {}\n", format_timestamp(chapter.start), chapter.headline));
+        }
+        output.push('\n');
+    }
+
+    if !data.highlights.is_empty() {
+        output.push_str("## Key Phrases\n\n");
+        output.push_str(&format_highlights(data));
+        output.push_str("\n\n");
+    }
+
+    // Add transcript section
+    output.push_str("## Transcript\n\n");
+
+    if data.utterances.is_empty() {
+        output.push_str(&data.text);
+        return output;
+    }
+
+    let mut sections: Vec<&Section> = sections.iter().collect();
+    sections.sort_by_key(|s| s.start);
+    let mut next_section = 0;
+
+    let mut result = Vec::new();
+    let mut current_speaker: Option<&str> = None;
+    let mut current_texts: Vec<&str> = Vec::new();
+    let mut paragraph_start: i64 = 0;
+
+    for utterance in &data.utterances {
+        // Close the paragraph and emit headings for any sections starting here
+        while next_section < sections.len() && sections[next_section].start <= utterance.start {
+            if let Some(speaker) = current_speaker.take() {
+                let timestamp = format_timestamp(paragraph_start);
+                let text = current_texts.join(" ");
+                result.push(format!("**{}** [{}]: {}", speaker_label(speaker), timestamp, text));
+            }
+            let section = sections[next_section];
+            result.push(format!("### {} [{}]", section.title, format_timestamp(section.start)));
+            next_section += 1;
+        }
+
+        if current_speaker == Some(&utterance.speaker) {
+            current_texts.push(&utterance.text);
+        } else {
+            if let Some(speaker) = current_speaker {
+                let timestamp = format_timestamp(paragraph_start);
+                let text = current_texts.join(" ");
+                result.push(format!("**{}** [{}]: {}", speaker_label(speaker), timestamp, text));
+            }
+            current_speaker = Some(&utterance.speaker);
+            current_texts = vec![&utterance.text];
+            paragraph_start = utterance.start;
+        }
+    }
+
+    if let Some(speaker) = current_speaker {
+        let timestamp = format_timestamp(paragraph_start);
+        let text = current_texts.join(" ");
+        result.push(format!("**{}** [{}]: {}", speaker_label(speaker), timestamp, text));
+    }
+
+    output.push_str(&result.join("\n\n"));
+    output
+}
+
+/// How a speaker is shown: diarization labels as "Speaker A" (or "Speaker 1" once
+/// anonymized), names given with `yt-cli speakers set` or by an import as they are
+pub fn speaker_label(speaker: &str) -> String {
+    let letters = speaker.len() <= 2 && speaker.bytes().all(|b| b.is_ascii_uppercase());
+    let number = speaker.bytes().all(|b| b.is_ascii_digit());
+    if !speaker.is_empty() && (letters || number) {
+        format!("Speaker {}", speaker)
+    } else {
+        speaker.to_string()
+    }
+}
+
+/// Chapter headlines, one per line, for the search index
+pub fn chapter_headlines(data: &TranscriptData) -> String {
+    data.chapters
+        .iter()
+        .map(|c| c.headline.as_str())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Key phrases, most relevant first, with the moments they were said
+pub fn format_highlights(data: &TranscriptData) -> String {
+    let mut highlights: Vec<&Highlight> = data.highlights.iter().collect();
+    highlights.sort_by(|a, b| b.rank.total_cmp(&a.rank));
+    highlights
+        .iter()
+        .map(|h| {
+            let moments: Vec<String> = h.timestamps.iter().map(|t| format_timestamp(t.start)).collect();
+            format!("- {} ({}x: {})", h.text, h.count, moments.join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Format transcript data as plain text (no formatting)
+pub fn format_transcript(data: &TranscriptData) -> String {
+    if data.utterances.is_empty() {
+        return data.text.clone();
+    }
+
+    // Same batching logic but simpler output
+    let mut result = Vec::new();
+    let mut current_speaker: Option<&str> = None;
+    let mut current_texts: Vec<&str> = Vec::new();
+
+    for utterance in &data.utterances {
+        if current_speaker == Some(&utterance.speaker) {
+            current_texts.push(&utterance.text);
+        } else {
+            if let Some(speaker) = current_speaker {
+                let text = current_texts.join(" ");
+                result.push(format!("{}: {}", speaker_label(speaker), text));
+            }
+            current_speaker = Some(&utterance.speaker);
+            current_texts = vec![&utterance.text];
+        }
+    }
+
+    if let Some(speaker) = current_speaker {
+        let text = current_texts.join(" ");
+        result.push(format!("{}: {}", speaker_label(speaker), text));
+    }
+
+    result.join("\n\n")
+}
@@ -0,0 +1,103 @@
+use super::TranscriptData;
+
+/// Characters that end a sentence, including CJK full-width forms
+const TERMINATORS: &[char] = &['.', '?', '!', '…', '。', '？', '！'];
+/// Pause (ms) that ends a sentence in unpunctuated text such as auto-captions
+const SENTENCE_GAP_MS: i64 = 1500;
+/// Longest sentence in words, so run-ons still make readable search snippets
+const MAX_SENTENCE_WORDS: usize = 60;
+
+/// A sentence of a transcript, start in milliseconds
+#[derive(Debug, Clone)]
+pub struct Sentence {
+    pub speaker: Option<String>,
+    pub start: i64,
+    pub text: String,
+}
+
+/// Split a transcript into sentences for the search index
+///
+/// Word timings give each sentence its own start time; sentences end at punctuation, a
+/// speaker change or a long pause. Transcripts without words are split by punctuation
+/// within each utterance, with start times spread across the utterance by position.
+pub fn split_sentences(data: &TranscriptData) -> Vec<Sentence> {
+    if !data.words.is_empty() {
+        return from_words(data);
+    }
+    data.utterances
+        .iter()
+        .flat_map(|u| {
+            let length = u.text.len().max(1) as i64;
+            split_text(&u.text).into_iter().map(move |(offset, text)| Sentence {
+                speaker: Some(u.speaker.clone()),
+                start: u.start + (u.end - u.start) * offset as i64 / length,
+                text: text.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn from_words(data: &TranscriptData) -> Vec<Sentence> {
+    let mut sentences: Vec<Sentence> = Vec::new();
+    let mut current: Option<(Sentence, usize, i64)> = None;
+
+    for word in &data.words {
+        if let Some((sentence, count, end)) = &current
+            && (sentence.text.ends_with(TERMINATORS)
+                || *count >= MAX_SENTENCE_WORDS
+                || word.start - end > SENTENCE_GAP_MS
+                || word.speaker != sentence.speaker)
+        {
+            sentences.extend(current.take().map(|(sentence, _, _)| sentence));
+        }
+
+        match &mut current {
+            Some((sentence, count, end)) => {
+                sentence.text.push(' ');
+                sentence.text.push_str(&word.text);
+                *count += 1;
+                *end = word.end;
+            }
+            None => {
+                let sentence = Sentence {
+                    speaker: word.speaker.clone(),
+                    start: word.start,
+                    text: word.text.clone(),
+                };
+                current = Some((sentence, 1, word.end));
+            }
+        }
+    }
+
+    sentences.extend(current.map(|(sentence, _, _)| sentence));
+    sentences
+}
+
+/// Sentences of `text` with their byte offsets, split after terminators
+fn split_text(text: &str) -> Vec<(usize, &str)> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if !TERMINATORS.contains(&c) {
+            continue;
+        }
+        let end = i + c.len_utf8();
+        // Full-width terminators aren't followed by spaces; "3.5" or "?!" isn't a break
+        if !c.is_ascii() || text[end..].chars().next().is_none_or(char::is_whitespace) {
+            push_piece(&mut pieces, text, start, end);
+            start = end;
+        }
+    }
+    push_piece(&mut pieces, text, start, text.len());
+    pieces
+}
+
+fn push_piece<'a>(pieces: &mut Vec<(usize, &'a str)>, text: &'a str, start: usize, end: usize) {
+    let piece = &text[start..end];
+    let trimmed = piece.trim_start();
+    let offset = start + piece.len() - trimmed.len();
+    let trimmed = trimmed.trim_end();
+    if !trimmed.is_empty() {
+        pieces.push((offset, trimmed));
+    }
+}
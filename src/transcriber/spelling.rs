@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use regex::{NoExpand, Regex};
+
+use crate::error::{Error, Result};
+
+use super::TranscriptData;
+
+/// User-maintained corrections (`[spelling]` in config.toml), applied before indexing
+pub struct SpellingMap {
+    /// Case-insensitive whole-word patterns with their replacements, longest first
+    rules: Vec<(Regex, String)>,
+}
+
+impl SpellingMap {
+    pub fn new(corrections: &HashMap<String, String>) -> Result<Self> {
+        let mut entries: Vec<(&String, &String)> = corrections.iter().collect();
+        // Longer phrases first, so "Jay Powell" wins over "Jay"
+        entries.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.0.cmp(b.0)));
+
+        let rules = entries
+            .into_iter()
+            .map(|(from, to)| {
+                let pattern = format!(r"(?i)\b{}\b", regex::escape(from.trim()));
+                Regex::new(&pattern)
+                    .map(|regex| (regex, to.clone()))
+                    .map_err(|e| Error::Config(format!("Invalid spelling entry '{}': {}", from, e)))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Correct the full text and every utterance; single words are corrected too when
+    /// a rule maps one word to one word
+    pub fn apply(&self, data: &mut TranscriptData) {
+        data.text = self.correct(&data.text);
+        for utterance in &mut data.utterances {
+            utterance.text = self.correct(&utterance.text);
+        }
+        for word in &mut data.words {
+            let corrected = self.correct(&word.text);
+            if corrected.split_whitespace().count() == 1 {
+                word.text = corrected;
+            }
+        }
+    }
+
+    fn correct(&self, text: &str) -> String {
+        self.rules.iter().fold(text.to_string(), |text, (regex, to)| {
+            regex.replace_all(&text, NoExpand(to)).into_owned()
+        })
+    }
+}
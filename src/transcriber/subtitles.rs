@@ -0,0 +1,213 @@
+use std::collections::HashSet;
+
+use super::{speaker_label, TranscriptData};
+
+/// Longest cue, in characters across both lines
+const MAX_CUE_CHARS: usize = 84;
+/// Characters per subtitle line before wrapping onto a second one
+const MAX_LINE_CHARS: usize = 42;
+/// Longest cue duration in milliseconds
+const MAX_CUE_MS: i64 = 6000;
+/// Pause that ends a cue
+const CUE_GAP_MS: i64 = 1000;
+
+/// A subtitle cue, times in milliseconds
+struct Cue {
+    start: i64,
+    end: i64,
+    speaker: Option<String>,
+    text: String,
+}
+
+/// Format a transcript as SubRip (.srt) subtitles
+///
+/// Cues are built from word timings so they stay short enough to read; transcripts
+/// without words fall back to one cue per utterance. With several speakers, each
+/// speaker turn starts with the speaker label.
+pub fn format_srt(data: &TranscriptData) -> String {
+    let mut output = String::new();
+    for (i, (cue, speaker)) in labeled_cues(data).iter().enumerate() {
+        let text = match speaker {
+            Some(speaker) => format!("{}: {}", speaker_label(speaker), cue.text),
+            None => cue.text.clone(),
+        };
+        output.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            timestamp(cue.start, ','),
+            timestamp(cue.end, ','),
+            wrap_lines(&text)
+        ));
+    }
+    output
+}
+
+/// Format a transcript as WebVTT subtitles, marking speaker turns with voice tags
+pub fn format_vtt(data: &TranscriptData) -> String {
+    let mut output = String::from("WEBVTT\n\n");
+    for (cue, speaker) in labeled_cues(data) {
+        let text = escape_xml(&wrap_lines(&cue.text));
+        let text = match speaker {
+            Some(speaker) => format!("<v {}>{}", escape_xml(&speaker_label(&speaker)), text),
+            None => text,
+        };
+        output.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            timestamp(cue.start, '.'),
+            timestamp(cue.end, '.'),
+            text
+        ));
+    }
+    output
+}
+
+/// Format a transcript as a TTML (Timed Text Markup Language) document
+///
+/// An unknown language is written as `xml:lang=""`, which TTML reads as undetermined.
+pub fn format_ttml(data: &TranscriptData, language: Option<&str>) -> String {
+    let mut output = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <tt xmlns=\"http://www.w3.org/ns/ttml\" xml:lang=\"{}\">\n  <body>\n    <div>\n",
+        escape_xml(language.unwrap_or(""))
+    );
+    for (cue, speaker) in labeled_cues(data) {
+        let text = match speaker {
+            Some(speaker) => format!("{}: {}", speaker_label(&speaker), cue.text),
+            None => cue.text.clone(),
+        };
+        output.push_str(&format!(
+            "      <p begin=\"{}\" end=\"{}\">{}</p>\n",
+            timestamp(cue.start, '.'),
+            timestamp(cue.end, '.'),
+            escape_xml(&wrap_lines(&text)).replace('\n', "<br/>")
+        ));
+    }
+    output.push_str("    </div>\n  </body>\n</tt>\n");
+    output
+}
+
+/// Cues paired with the speaker to announce: set on the first cue of each speaker turn,
+/// and only when the transcript has more than one speaker
+fn labeled_cues(data: &TranscriptData) -> Vec<(Cue, Option<String>)> {
+    let cues = build_cues(data);
+    let multiple_speakers = cues
+        .iter()
+        .filter_map(|c| c.speaker.as_deref())
+        .collect::<HashSet<_>>()
+        .len()
+        > 1;
+
+    let mut previous_speaker: Option<String> = None;
+    cues.into_iter()
+        .map(|cue| {
+            let label = (multiple_speakers && cue.speaker != previous_speaker)
+                .then(|| cue.speaker.clone())
+                .flatten();
+            previous_speaker = cue.speaker.clone();
+            (cue, label)
+        })
+        .collect()
+}
+
+fn build_cues(data: &TranscriptData) -> Vec<Cue> {
+    if !data.words.is_empty() {
+        return cues_from_words(data);
+    }
+    if !data.utterances.is_empty() {
+        return data
+            .utterances
+            .iter()
+            .map(|u| Cue {
+                start: u.start,
+                end: u.end,
+                speaker: Some(u.speaker.clone()),
+                text: u.text.trim().to_string(),
+            })
+            .collect();
+    }
+    if data.text.trim().is_empty() {
+        return Vec::new();
+    }
+    vec![Cue {
+        start: 0,
+        end: data.audio_duration.unwrap_or(0) * 1000,
+        speaker: None,
+        text: data.text.trim().to_string(),
+    }]
+}
+
+fn cues_from_words(data: &TranscriptData) -> Vec<Cue> {
+    let mut cues: Vec<Cue> = Vec::new();
+    let mut current: Option<Cue> = None;
+
+    for word in &data.words {
+        if let Some(cue) = &current {
+            let ends_sentence = cue.text.ends_with(['.', '?', '!']);
+            let full = cue.text.len() + 1 + word.text.len() > MAX_CUE_CHARS;
+            let too_long = word.end - cue.start > MAX_CUE_MS;
+            let paused = word.start - cue.end > CUE_GAP_MS;
+            let new_speaker = word.speaker != cue.speaker;
+            if ends_sentence || full || too_long || paused || new_speaker {
+                cues.extend(current.take());
+            }
+        }
+
+        match &mut current {
+            Some(cue) => {
+                cue.text.push(' ');
+                cue.text.push_str(&word.text);
+                cue.end = word.end;
+            }
+            None => {
+                current = Some(Cue {
+                    start: word.start,
+                    end: word.end,
+                    speaker: word.speaker.clone(),
+                    text: word.text.clone(),
+                })
+            }
+        }
+    }
+
+    cues.extend(current);
+    cues
+}
+
+/// Split text longer than one line at the space closest to its middle
+fn wrap_lines(text: &str) -> String {
+    if text.chars().count() <= MAX_LINE_CHARS {
+        return text.to_string();
+    }
+
+    let middle = text.len() / 2;
+    let split = text
+        .match_indices(' ')
+        .map(|(i, _)| i)
+        .min_by_key(|i| i.abs_diff(middle));
+
+    match split {
+        Some(i) => format!("{}\n{}", &text[..i], &text[i + 1..]),
+        None => text.to_string(),
+    }
+}
+
+/// Milliseconds as HH:MM:SS,mmm (SRT) or HH:MM:SS.mmm (VTT, TTML)
+fn timestamp(ms: i64, separator: char) -> String {
+    let ms = ms.max(0);
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+/// Escape markup characters (also required in WebVTT cue text)
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,88 @@
+//! Platform captions (`transcribe --use-captions`), one fixture per case in
+//! `tests/fixtures/captions/`
+
+use std::path::{Path, PathBuf};
+
+use yt_cli::transcriber::{parse_captions, TranscriptData};
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/captions").join(name)
+}
+
+fn captions(name: &str) -> TranscriptData {
+    parse_captions(&fixture(name), "captions-test", None).unwrap_or_else(|e| panic!("{} doesn't parse: {}", name, e))
+}
+
+/// Text, start and end of each word
+fn words(data: &TranscriptData) -> Vec<(&str, i64, i64)> {
+    data.words.iter().map(|w| (w.text.as_str(), w.start, w.end)).collect()
+}
+
+/// Text, start and end of each utterance
+fn utterances(data: &TranscriptData) -> Vec<(&str, i64, i64)> {
+    data.utterances.iter().map(|u| (u.text.as_str(), u.start, u.end)).collect()
+}
+
+#[test]
+fn auto_generated_vtt() {
+    let data = captions("auto.en.vtt");
+    // Inline timing tags are stripped, and the line each cue repeats from the one before is
+    // dropped; a cue's span is spread evenly over its words
+    assert_eq!(
+        words(&data),
+        [
+            ("hello", 0, 1000),
+            ("there", 1000, 2000),
+            ("general", 2000, 3000),
+            ("kenobi", 3000, 4000),
+            ("you", 6000, 6500),
+            ("are", 6500, 7000),
+            ("bold", 7000, 7500),
+        ]
+    );
+    // A two-second pause starts a new utterance
+    assert_eq!(utterances(&data), [("hello there general kenobi", 0, 4000), ("you are bold", 6000, 7500)]);
+    assert!(data.utterances.iter().all(|u| u.speaker == "A"));
+    assert_eq!(data.text, "hello there general kenobi you are bold");
+}
+
+#[test]
+fn vtt_timestamps() {
+    let data = captions("short.vtt");
+    // `MM:SS.mmm` and `H:MM:SS.mmm` cues; the last word takes what an uneven split leaves over
+    assert_eq!(
+        words(&data),
+        [
+            ("one", 62000, 62333),
+            ("two", 62333, 62666),
+            ("three", 62666, 63000),
+            ("an", 3600500, 3600833),
+            ("hour", 3600833, 3601166),
+            ("in", 3601166, 3601500),
+        ]
+    );
+    assert_eq!(utterances(&data), [("one two three", 62000, 63000), ("an hour in", 3600500, 3601500)]);
+}
+
+#[test]
+fn json3() {
+    let data = captions("auto.en.json3");
+    // A segment runs until the next one's offset, the last one until its event ends; events
+    // without a start time or words are skipped
+    assert_eq!(
+        words(&data),
+        [
+            ("Hi", 0, 1000),
+            ("all", 1000, 2000),
+            ("of", 2000, 3000),
+            ("you", 10000, 10500),
+            ("too", 10500, 12000),
+        ]
+    );
+    assert_eq!(utterances(&data), [("Hi all of", 0, 3000), ("you too", 10000, 12000)]);
+}
+
+#[test]
+fn empty_captions_are_an_error() {
+    assert!(parse_captions(&fixture("empty.json3"), "captions-test", None).is_err());
+}
@@ -0,0 +1,81 @@
+//! Golden-file tests for the transcript formatters
+//!
+//! Every `tests/fixtures/<name>.json` is a `TranscriptData`; what each formatter makes of it
+//! is kept in `tests/golden/<name>.<extension>`. After changing a formatter on purpose,
+//! rewrite the golden files with `UPDATE_GOLDEN=1 cargo test --test formatters` and review
+//! their diff like any other change.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use yt_cli::transcriber::{
+    format_srt, format_transcript, format_transcript_markdown, format_ttml, format_vtt, TranscriptData,
+};
+
+type Formatter = fn(&TranscriptData) -> String;
+
+/// Golden file extension and the formatter producing it
+const FORMATS: &[(&str, Formatter)] = &[
+    ("md", format_transcript_markdown),
+    ("txt", format_transcript),
+    ("srt", format_srt),
+    ("vtt", format_vtt),
+    ("ttml", |data| format_ttml(data, data.language.as_deref())),
+];
+
+fn tests_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+fn fixtures() -> Vec<(String, TranscriptData)> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(tests_dir().join("fixtures"))
+        .expect("tests/fixtures is readable")
+        .map(|entry| entry.expect("fixture entry").path())
+        .filter(|path| path.extension().is_some_and(|e| e == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().to_string();
+            let content = fs::read_to_string(&path).unwrap();
+            let data = serde_json::from_str(&content)
+                .unwrap_or_else(|e| panic!("{} is not transcript data: {}", path.display(), e));
+            (name, data)
+        })
+        .collect()
+}
+
+#[test]
+fn formatters_match_golden_files() {
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let golden_dir = tests_dir().join("golden");
+    let fixtures = fixtures();
+    assert!(!fixtures.is_empty(), "no fixtures in tests/fixtures");
+
+    let mut mismatches = Vec::new();
+    for (name, data) in &fixtures {
+        for (extension, format) in FORMATS {
+            let golden = golden_dir.join(format!("{}.{}", name, extension));
+            let actual = format(data);
+            if update {
+                fs::write(&golden, &actual).unwrap();
+                continue;
+            }
+
+            let expected = fs::read_to_string(&golden).unwrap_or_default();
+            if actual != expected {
+                let line = actual.lines().zip(expected.lines()).position(|(a, e)| a != e);
+                let line = line.unwrap_or_else(|| actual.lines().count().min(expected.lines().count()));
+                mismatches.push(format!("{} (first difference on line {})", golden.display(), line + 1));
+            }
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "output differs from golden files (UPDATE_GOLDEN=1 rewrites them):\n  {}",
+        mismatches.join("\n  ")
+    );
+}
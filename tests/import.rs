@@ -0,0 +1,85 @@
+//! `yt-cli import` parsers, one fixture per format in `tests/fixtures/import/`
+
+use std::path::{Path, PathBuf};
+
+use yt_cli::transcriber::{parse_import, TranscriptData};
+
+fn import(name: &str) -> TranscriptData {
+    let path: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/import").join(name);
+    parse_import(&path, "imported").unwrap_or_else(|e| panic!("{} doesn't import: {}", name, e))
+}
+
+/// Speaker, text, start and end of each utterance
+fn utterances(data: &TranscriptData) -> Vec<(&str, &str, i64, i64)> {
+    data.utterances
+        .iter()
+        .map(|u| (u.speaker.as_str(), u.text.as_str(), u.start, u.end))
+        .collect()
+}
+
+#[test]
+fn whisperx_json() {
+    let data = import("whisperx.json");
+    // Diarization labels become letters in order of appearance; blank segments are dropped
+    assert_eq!(
+        utterances(&data),
+        [("A", "Welcome back to the show.", 500, 2250), ("B", "Thanks, it's 2024.", 2500, 4000)]
+    );
+    assert_eq!(data.words.len(), 8);
+    // An unaligned word is placed where the previous one ended
+    let numeral = data.words.last().unwrap();
+    assert_eq!((numeral.text.as_str(), numeral.start, numeral.end), ("2024.", 3200, 3200));
+    assert_eq!(numeral.speaker.as_deref(), Some("B"));
+    assert_eq!(data.utterances[1].confidence, Some(0.8));
+    assert_eq!(data.audio_duration, Some(4));
+    assert_eq!(data.text, "Welcome back to the show. Thanks, it's 2024.");
+}
+
+#[test]
+fn unlabelled_srt() {
+    let data = import("yt-whisper.srt");
+    // One "Note:" among unlabelled cues isn't a speaker; markup is stripped
+    assert_eq!(
+        utterances(&data),
+        [
+            ("A", "Welcome back to the show.", 0, 2500),
+            ("A", "Note: this episode was recorded live.", 2500, 5000),
+            ("A", "Applause", 5000, 7250),
+        ]
+    );
+    assert!(data.words.is_empty());
+}
+
+#[test]
+fn labelled_vtt() {
+    let data = import("descript.vtt");
+    assert_eq!(
+        utterances(&data),
+        [
+            ("Zoë Müller", "Hallo und willkommen.", 0, 3500),
+            ("A", "Thanks for having me, glad to be here.", 3500, 6000),
+            ("Zoë Müller", "Let's begin.", 6000, 8000),
+        ]
+    );
+}
+
+#[test]
+fn descript_txt() {
+    let data = import("descript.txt");
+    // Paragraphs run until the next one starts; unstamped lines continue the previous one
+    assert_eq!(
+        utterances(&data),
+        [
+            ("Ana", "First paragraph starts here and carries on over a second line.", 1000, 12500),
+            ("Bo Li", "A reply.", 12500, 62000),
+            ("Ana", "Closing words.", 62000, 62000),
+        ]
+    );
+}
+
+#[test]
+fn unreadable_timestamps_are_skipped() {
+    // Non-ASCII and overflowing timecodes drop their cue instead of failing the import
+    let data = import("broken_clock.srt");
+    assert_eq!(utterances(&data), [("A", "This cue is kept.", 2000, 3000)]);
+}
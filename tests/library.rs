@@ -0,0 +1,81 @@
+//! Looking up stored transcripts the way `yt-cli get` does, against a library in a
+//! temporary data directory
+//!
+//! Everything runs in one test: the data directory comes from the environment, which the
+//! whole test binary shares.
+
+use std::fs;
+use std::path::PathBuf;
+
+use yt_cli::commands::get;
+use yt_cli::database::{add_transcript, get_transcript_by_url, IndexOutcome, TranscriptMetadata};
+use yt_cli::transcriber::{Backend, TranscriptSource};
+
+fn data_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("yt-cli-library-test-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn store(video_id: &str, url: &str, platform: &str, path: &str) {
+    let outcome = add_transcript(&TranscriptMetadata {
+        video_id,
+        url,
+        title: "Episode 1",
+        channel: "Some Channel",
+        channel_handle: None,
+        channel_id: None,
+        platform,
+        duration: Some(60),
+        upload_date: None,
+        description: None,
+        thumbnail: None,
+        view_count: None,
+        like_count: None,
+        path,
+        speaker_count: 1,
+        word_count: 2,
+        confidence: None,
+        transcript_text: "Hello there",
+        chapters: "",
+        sentences: &[],
+        highlights: &[],
+        entities: &[],
+        source: TranscriptSource::Api.id(),
+        transcribed_by: None,
+        language: None,
+        missing_tail: None,
+        transcribed_now: true,
+    })
+    .expect("transcript is stored");
+    assert!(matches!(outcome, IndexOutcome::Complete), "{:?}", outcome);
+}
+
+#[tokio::test]
+async fn stored_transcripts_are_found_by_url() {
+    let dir = data_dir();
+    // SAFETY: set before anything reads the environment, and this binary runs no other test
+    unsafe { std::env::set_var("YT_TRANSCRIBE_DATA_DIR", &dir) };
+
+    // Rumble URLs carry a stable ID, so `get` needs neither yt-dlp nor the network
+    let rumble = "https://rumble.com/v4abcd-some-title.html";
+    assert_eq!(get::stored_video_id(rumble).as_deref(), Some("v4abcd"));
+    let rumble_path = dir.join("transcripts/rumble/Some Channel/v4abcd");
+    store("v4abcd", rumble, "rumble", &rumble_path.to_string_lossy());
+    get::run(&format!("{}?e9s=src_v1_ucp", rumble), Backend::AssemblyAI)
+        .await
+        .expect("a stored Rumble transcript is found without transcribing");
+
+    // yt-dlp's ID for an Odysee video is the claim ID, not the name in its URL, so the
+    // transcript is found by the URL it was made from
+    let odysee = "https://odysee.com/@SomeChannel:c/video-name:a";
+    let odysee_path = dir.join("transcripts/odysee/Some Channel/0123abcd");
+    store("0123abcd", odysee, "odysee", &odysee_path.to_string_lossy());
+    let record = get_transcript_by_url(odysee).unwrap().expect("found by URL");
+    assert_eq!(record.video_id, "0123abcd");
+    assert_eq!(record.platform, "odysee");
+    assert!(get_transcript_by_url("https://odysee.com/@SomeChannel:c/other:b").unwrap().is_none());
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,64 @@
+//! URL handling for the platforms with their own ID rules, and the `[platforms]` settings
+//! their transcripts are stored under
+
+use yt_cli::config::{DirectoryNaming, Settings};
+use yt_cli::platform;
+
+#[test]
+fn rumble_urls() {
+    let url = "https://rumble.com/v4abcd-some-title.html?e9s=src_v1_ucp";
+    assert_eq!(platform::platform_name(url), "rumble");
+    assert_eq!(platform::extract_id(url).as_deref(), Some("v4abcd"));
+    assert_eq!(platform::normalize_url(url), "https://rumble.com/v4abcd-some-title.html");
+
+    let embed = "https://rumble.com/embed/v4xyz/";
+    assert_eq!(platform::extract_id(embed).as_deref(), Some("v4xyz"));
+    let rumble = platform::detect(embed).expect("rumble.com is registered");
+    assert_eq!(rumble.canonical_url("v4xyz").as_deref(), Some("https://rumble.com/embed/v4xyz/"));
+
+    assert_eq!(platform::extract_id("https://rumble.com/"), None);
+}
+
+#[test]
+fn odysee_urls() {
+    let url = "https://odysee.com/@SomeChannel:c/video-name:a?r=abc123";
+    assert_eq!(platform::platform_name(url), "odysee");
+    assert_eq!(platform::extract_id(url).as_deref(), Some("video-name"));
+    assert_eq!(platform::normalize_url(url), "https://odysee.com/@SomeChannel:c/video-name:a");
+
+    // Shared links percent-encode the claim separator
+    let shared = "https://odysee.com/@SomeChannel%3Ac/video-name%3Aa";
+    assert_eq!(platform::extract_id(shared).as_deref(), Some("video-name"));
+
+    assert_eq!(platform::platform_name("https://lbry.tv/@SomeChannel:c/video-name:a"), "odysee");
+    // A channel page is not a video
+    assert_eq!(platform::extract_id("https://odysee.com/@SomeChannel:c"), None);
+    assert_eq!(platform::detect(url).and_then(|p| p.canonical_url("video-name")), None);
+}
+
+#[test]
+fn platform_settings() {
+    let settings: Settings = toml::from_str(
+        r#"
+        keep_audio = true
+
+        [platforms.rumble]
+        keep_audio = false
+        directory = "handle"
+
+        [platforms.Odysee]
+        prune_audio_after = "30d"
+        "#,
+    )
+    .expect("valid config.toml");
+
+    assert!(!settings.keeps_audio("rumble"));
+    assert_eq!(settings.platform_config("rumble").map(|c| c.directory), Some(DirectoryNaming::Handle));
+    assert!(settings.keeps_audio("odysee"));
+    // Table names match platforms regardless of case
+    let odysee = settings.platform_config("odysee").expect("[platforms.Odysee] applies to odysee");
+    assert_eq!(odysee.prune_audio_after.as_deref(), Some("30d"));
+
+    let unknown = toml::from_str::<Settings>("[platforms.rumble]\nkeep_audo = false\n");
+    assert!(unknown.is_err(), "misspelled platform keys are rejected");
+}
@@ -0,0 +1,56 @@
+//! Reading `yt-cli batch` scripts: comments, a leading `yt-cli`, and shell-style words
+
+use std::fs;
+use std::path::PathBuf;
+
+use yt_cli::commands::script;
+use yt_cli::error::Result;
+
+/// `script::read` on `content`, from a file named after the test so tests don't share one
+fn read(name: &str, content: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let path: PathBuf = std::env::temp_dir().join(format!("yt-cli-script-test-{}-{}", std::process::id(), name));
+    fs::write(&path, content).unwrap();
+    let commands = script::read(&path.to_string_lossy());
+    let _ = fs::remove_file(&path);
+    commands
+}
+
+#[test]
+fn words() {
+    let cases: &[(&str, &[&str])] = &[
+        ("get  URL\t--format json", &["get", "URL", "--format", "json"]),
+        ("search 'two words'", &["search", "two words"]),
+        (r#"search "two words""#, &["search", "two words"]),
+        // Adjacent quoted and unquoted parts make one word
+        (r"search 'it'\''s'", &["search", "it's"]),
+        (r"search a\ b\'c", &["search", "a b'c"]),
+        // Inside double quotes only \" \\ \$ and \` are escapes; single quotes keep everything
+        (r#"search "say \"hi\" \\ \$x \n""#, &["search", r#"say "hi" \ $x \n"#]),
+        (r"search 'a \' b", &["search", r"a \", "b"]),
+        (r#"tag add ID """#, &["tag", "add", "ID", ""]),
+        ("search ''", &["search", ""]),
+        // Only a leading yt-cli is dropped
+        ("yt-cli list yt-cli", &["list", "yt-cli"]),
+    ];
+    for (i, (line, expected)) in cases.iter().enumerate() {
+        let commands = read(&format!("words-{}", i), line).unwrap_or_else(|e| panic!("{}: {}", line, e));
+        assert_eq!(commands, [(1, expected.iter().map(|w| w.to_string()).collect())], "{}", line);
+    }
+}
+
+#[test]
+fn unclosed_quotes() {
+    for (i, line) in [r#"search "oops"#, "search 'oops", r#"search "oops\""#].iter().enumerate() {
+        let error = read(&format!("unclosed-{}", i), &format!("list\n{}\n", line))
+            .expect_err(line)
+            .to_string();
+        assert!(error.contains("line 2: unclosed quote"), "{}: {}", line, error);
+    }
+}
+
+#[test]
+fn skipped_lines() {
+    let commands = read("skipped", "# nightly\n\n  yt-cli sync  \nyt-cli\n   # indented comment\nstats\n").unwrap();
+    // Commands keep their line numbers; a bare yt-cli is nothing to run
+    assert_eq!(commands, [(3, vec!["sync".to_string()]), (6, vec!["stats".to_string()])]);
+}